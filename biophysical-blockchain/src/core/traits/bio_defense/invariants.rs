@@ -115,7 +115,7 @@ pub fn assert_policy_respects_doctrine(
 
 /// Clamp Karma / aura decay multiplier into [0.0, 1.0], rejecting amplification.
 pub fn enforce_decay_multiplier_bounds(raw: f32) -> Result<f32, BioDefenseDoctrineError> {
-    if raw > 1.0 {
+    if raw.is_nan() || raw > 1.0 {
         return Err(BioDefenseDoctrineError::KarmaAmplificationForbidden);
     }
     let clamped = if raw < 0.0 { 0.0 } else { raw };
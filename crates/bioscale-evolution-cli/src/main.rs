@@ -12,6 +12,10 @@ use bioscale_metrics::validate_metrics_schema;
 use bioscale_evidence::EvidenceTag;
 use bioscale_envelopes::{GlobalEnvelopes, EnvelopeViolation};
 use bioscale_tests_index::{UpgradeTestIndex, KaniHarnessIndex};
+use organic_cpu::evidence::merkle::{DayRoot, DayTree};
+use organic_cpu::evidence::{current_utc_ms, segment};
+use sovereigntycore::audit::JsonlAuditLog;
+use sovereigntycore::export::{ExportConsentSet, ExportGateway, ExportKind, ExportRequest, FieldClassifier};
 
 /// CLI for emitting daily bioscale evolution manifests and gating CI.
 ///
@@ -27,6 +31,16 @@ struct Args {
     /// Optional path override for workspace root (defaults to CWD)
     #[arg(long)]
     workspace_root: Option<PathBuf>,
+
+    /// Chained proof-artifact log, or a segmented log directory; the day's
+    /// Merkle root goes into the manifest
+    #[arg(long)]
+    proof_log: Option<PathBuf>,
+
+    /// JSON array of `ExportConsent`s for raw manifest fields; without it
+    /// a manifest carrying raw fields is not written
+    #[arg(long)]
+    export_consents: Option<PathBuf>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -40,6 +54,8 @@ pub struct DailyManifest {
     pub bci_snapshots: BciAggregateSnapshot,
     pub aln_particles: Vec<AlnParticleEntry>,
     pub metrics_schema_version: MetricsSchemaVersion,
+    /// Merkle root over this date's `BiophysicalProofArtifact`s.
+    pub proof_artifacts: Option<DayRoot>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -128,7 +144,14 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
-    // 7. Build manifest.
+    // 7. Commit to today's proof artifacts (chain verified while reading).
+    let proof_artifacts = match &args.proof_log {
+        Some(log) if log.is_dir() => Some(segment::day_root(log, &args.date)?),
+        Some(log) => Some(DayTree::from_jsonl(log, &args.date)?.root()),
+        None => None,
+    };
+
+    // 8. Build manifest.
     let manifest = build_manifest(
         &root,
         &args.date,
@@ -136,10 +159,17 @@ fn main() -> anyhow::Result<()> {
         bci_snapshots,
         aln_particles,
         metrics_registry.schema_version.clone(),
+        proof_artifacts,
     )?;
 
-    // 8. Write manifest and exit non-zero on any safety failure.
+    // 9. Pass the export gateway, write manifest and exit non-zero on any
+    //    safety failure.
     let out_path = manifest_path(&root, &args.date);
+    let consents = match &args.export_consents {
+        Some(path) => ExportConsentSet::load(path).map_err(anyhow::Error::msg)?,
+        None => ExportConsentSet::default(),
+    };
+    gate_export(&root, &out_path, &manifest, &consents)?;
     write_manifest(&out_path, &manifest)?;
 
     println!("bioscale-evolution-cli: wrote manifest to {:?}", out_path);
@@ -275,6 +305,7 @@ fn build_manifest(
     bci: BciAggregateSnapshot,
     aln_particles: Vec<AlnParticleEntry>,
     metrics_schema_version: MetricsSchemaVersion,
+    proof_artifacts: Option<DayRoot>,
 ) -> anyhow::Result<DailyManifest> {
     let git_commit = bioscale_git::current_commit(root)?;
     let crate_versions = bioscale_git::crate_versions(root)?;
@@ -308,6 +339,7 @@ fn build_manifest(
         bci_snapshots: bci,
         aln_particles,
         metrics_schema_version,
+        proof_artifacts,
     })
 }
 
@@ -319,6 +351,33 @@ fn manifest_path(root: &PathBuf, date: &str) -> PathBuf {
     p
 }
 
+/// HCI export gateway for the manifest; every attempt lands in
+/// `research/export-audit.jsonl`.
+fn gate_export(
+    root: &PathBuf,
+    out_path: &PathBuf,
+    manifest: &DailyManifest,
+    consents: &ExportConsentSet,
+) -> anyhow::Result<()> {
+    let audit = JsonlAuditLog::new(root.join("research").join("export-audit.jsonl"));
+    let classifier = FieldClassifier::for_kind(&ExportKind::Manifest);
+    let gateway = ExportGateway {
+        classifier: &classifier,
+        consents,
+        audit: &audit,
+    };
+    let request = ExportRequest {
+        subjectid: manifest.host_did.clone(),
+        destination: out_path.display().to_string(),
+        kind: ExportKind::Manifest,
+        payload: serde_json::to_value(manifest)?,
+    };
+    gateway
+        .export(&request, current_utc_ms())
+        .map_err(|e| anyhow::anyhow!("manifest export blocked: {e}"))?;
+    Ok(())
+}
+
 fn write_manifest(path: &PathBuf, manifest: &DailyManifest) -> anyhow::Result<()> {
     let file = File::create(path)?;
     serde_json::to_writer_pretty(file, manifest)?;
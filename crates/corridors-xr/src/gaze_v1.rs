@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use bioscale_core::{BrainSpecs, EvolutionDecision, EvolutionDecisionKind, HostBudget};
 use bioscale_metrics::CorridorMetricsSink;
 use bioscale_neuro::BciHostSnapshot;
+use sovereigntycore::downgrade::PolicySurface;
 
 /// Stable identifier for this corridor.
 pub const XR_GAZE_CORRIDOR_ID: &str = "bio.corridor.xr.gaze.v1";
@@ -78,6 +79,29 @@ impl Default for XrGazeCorridorEnvelopeV1 {
     }
 }
 
+impl XrGazeCorridorEnvelopeV1 {
+    /// Comparable view for `sovereigntycore::downgrade`; timing minimums
+    /// and the HRV ratio are floors, everything else is a ceiling.
+    pub fn policy_surface(&self) -> PolicySurface {
+        PolicySurface::default()
+            .ceiling("max_spatial_error_cm", self.max_spatial_error_cm as f64)
+            .ceiling("per_event_energy_j", self.per_event_energy_j as f64)
+            .ceiling("per_session_energy_j", self.per_session_energy_j as f64)
+            .ceiling("daily_energy_j", self.daily_energy_j as f64)
+            .ceiling("sbio_load_index_max", self.sbio_load_index_max as f64)
+            .ceiling("local_thermal_delta_c_max", self.local_thermal_delta_c_max as f64)
+            .ceiling("global_thermal_delta_c_max", self.global_thermal_delta_c_max as f64)
+            .ceiling("max_duty_fraction_session", self.max_duty_fraction_session as f64)
+            .floor("min_inter_event_ms", self.min_inter_event_ms as f64)
+            .ceiling("max_continuous_burst_ms", self.max_continuous_burst_ms as f64)
+            .floor("min_cooldown_between_bursts_ms", self.min_cooldown_between_bursts_ms as f64)
+            .floor("hrv_drop_allowed_ratio", self.hrv_drop_allowed_ratio as f64)
+            .ceiling("eeg_beta_gamma_ceiling", self.eeg_beta_gamma_ceiling as f64)
+            .ceiling("roh_ceiling", self.roh_ceiling as f64)
+            .ceiling("roh_target", self.roh_target as f64)
+    }
+}
+
 /// Observables projected into the corridor state space for one decision step.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct XrGazeCorridorStateV1 {
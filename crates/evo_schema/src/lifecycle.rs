@@ -0,0 +1,543 @@
+//! Consent lifecycle for `EvoConsent`: grant, amend, revoke.
+//!
+//! Every operation is a `ConsentRecord` with a per-consent version and a
+//! hash chain over all records (`prev_hash` -> `hash`), optionally persisted
+//! as JSONL. Tokens, SMART automation and applied upgrades are bound to the
+//! consent that authorized them through `Bind` records in the same chain, so
+//! bindings survive a restart. Revoking a consent invalidates its tokens
+//! and automation at once and queues a rollback proposal for each upgrade
+//! applied under it (`rollback_anytime`).
+//!
+//! A revoke whose record cannot be written still takes effect: the consent
+//! goes into an in-memory deny set outside the chain, and the ledger
+//! refuses every further append until the revoke record is persisted.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use bioscale_upgrade_store::{HostBudget, UpgradeDescriptor};
+use cyberswarm_neurostack::telemetry::BciHostSnapshot;
+
+use crate::mode::{ModeGovernance, ModeProfiles, StepContext};
+use crate::{EvoConsent, PolicyError};
+
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsentOp {
+    Grant,
+    Amend,
+    Revoke,
+    /// Attaches a token, automation or applied upgrade; keeps the version.
+    Bind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BindingKind {
+    Token,
+    Automation,
+    Upgrade,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsentBinding {
+    pub kind: BindingKind,
+    pub id: String,
+}
+
+/// Hashed part of a record.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConsentRecordBody {
+    pub seq: u64,
+    /// Keyed by `EvoConsent::policy_id`.
+    pub consent_id: String,
+    /// Per-consent version, starting at 1 and bumped by grant/amend/revoke.
+    pub version: u32,
+    pub op: ConsentOp,
+    pub host_did: String,
+    pub utc_ms: i64,
+    /// Terms in force after this record; `None` for `Revoke` and `Bind`.
+    pub consent: Option<EvoConsent>,
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub binding: Option<ConsentBinding>,
+    pub prev_hash: String,
+}
+
+impl ConsentRecordBody {
+    pub fn hash(&self) -> Result<String, serde_json::Error> {
+        Ok(hex::encode(Sha256::digest(serde_json::to_vec(self)?)))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConsentRecord {
+    #[serde(flatten)]
+    pub body: ConsentRecordBody,
+    pub hash: String,
+}
+
+/// Rollback of one upgrade whose consent was revoked.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollbackProposal {
+    pub consent_id: String,
+    /// Version of the revoke record that triggered this rollback.
+    pub consent_version: u32,
+    pub upgrade_id: String,
+    pub host_did: String,
+    pub reason: String,
+    pub queued_utc_ms: i64,
+}
+
+/// Receives revocation effects as they happen.
+pub trait RevocationSink {
+    fn invalidate_token(&mut self, token_id: &str);
+    fn disable_automation(&mut self, automation_id: &str);
+    fn queue_rollback(&mut self, proposal: RollbackProposal);
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevocationEffects {
+    pub invalidated_tokens: Vec<String>,
+    pub disabled_automation: Vec<String>,
+    pub rollbacks: Vec<RollbackProposal>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LifecycleError {
+    #[error("I/O error in consent ledger: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization error in consent ledger: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("consent {0} is already active")]
+    AlreadyActive(String),
+    #[error("consent {0} is not active")]
+    NotActive(String),
+    #[error("consent {consent_id} belongs to {expected}, not {found}")]
+    HostMismatch {
+        consent_id: String,
+        expected: String,
+        found: String,
+    },
+    #[error("mode change for consent {consent_id} denied: {reason}")]
+    ModeChangeDenied { consent_id: String, reason: String },
+    #[error("consent {consent_id} refuses upgrade {upgrade_id}: {}", .error.clause_id())]
+    UpgradeRefused {
+        consent_id: String,
+        upgrade_id: String,
+        error: PolicyError,
+    },
+    #[error("consent chain broken at seq {seq}: {message}")]
+    BrokenChain { seq: u64, message: String },
+}
+
+#[derive(Clone, Debug, Default)]
+struct Bindings {
+    tokens: BTreeSet<String>,
+    automation: BTreeSet<String>,
+    upgrades: BTreeSet<String>,
+}
+
+/// A revoke in force whose record is not yet on disk.
+#[derive(Clone, Debug)]
+struct PendingRevoke {
+    host_did: String,
+    reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ConsentLedger {
+    records: Vec<ConsentRecord>,
+    /// consent_id -> index of its latest record.
+    latest: BTreeMap<String, usize>,
+    bindings: BTreeMap<String, Bindings>,
+    /// Revoked consents whose revoke record failed to persist; never chained.
+    denied: BTreeMap<String, PendingRevoke>,
+    path: Option<PathBuf>,
+}
+
+impl ConsentLedger {
+    /// In-memory ledger, nothing persisted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open (or create) a JSONL-backed ledger, verifying its chain.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, LifecycleError> {
+        let path = path.as_ref().to_path_buf();
+        let mut ledger = Self {
+            path: Some(path.clone()),
+            ..Self::default()
+        };
+        if path.exists() {
+            for line in std::fs::read_to_string(&path)?.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record: ConsentRecord = serde_json::from_str(line)?;
+                ledger.check_link(&record)?;
+                ledger.index(record);
+            }
+        }
+        Ok(ledger)
+    }
+
+    pub fn records(&self) -> &[ConsentRecord] {
+        &self.records
+    }
+
+    pub fn head_hash(&self) -> &str {
+        self.records.last().map(|r| r.hash.as_str()).unwrap_or(GENESIS_HASH)
+    }
+
+    /// Current terms, if the consent is active.
+    pub fn active(&self, consent_id: &str) -> Option<&EvoConsent> {
+        if self.denied.contains_key(consent_id) {
+            return None;
+        }
+        self.latest_record(consent_id).and_then(|r| r.body.consent.as_ref())
+    }
+
+    /// Consents revoked in memory whose revoke record is not yet persisted.
+    pub fn unpersisted_revokes(&self) -> impl Iterator<Item = &str> {
+        self.denied.keys().map(String::as_str)
+    }
+
+    /// Write every pending revoke record. Also runs before each append.
+    pub fn retry_pending_revokes(&mut self) -> Result<(), LifecycleError> {
+        let pending: Vec<(String, PendingRevoke)> =
+            self.denied.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        for (consent_id, revoke) in pending {
+            let record = self.next_record(
+                &consent_id,
+                ConsentOp::Revoke,
+                &revoke.host_did,
+                None,
+                Some(revoke.reason),
+                None,
+            )?;
+            self.persist(&record)?;
+            self.denied.remove(&consent_id);
+            self.index(record);
+        }
+        Ok(())
+    }
+
+    pub fn grant(&mut self, consent: EvoConsent, reason: Option<String>) -> Result<&ConsentRecord, LifecycleError> {
+        let id = consent.policy_id.clone();
+        if self.active(&id).is_some() {
+            return Err(LifecycleError::AlreadyActive(id));
+        }
+        if let Some(prev) = self.latest_record(&id) {
+            if prev.body.host_did != consent.host_did {
+                return Err(LifecycleError::HostMismatch {
+                    consent_id: id,
+                    expected: prev.body.host_did.clone(),
+                    found: consent.host_did,
+                });
+            }
+        }
+        let host = consent.host_did.clone();
+        self.append(&id, ConsentOp::Grant, &host, Some(consent), reason, None)
+    }
+
+    /// Replace the terms of an active consent. A change of `mode` is
+    /// recorded only if `governance` authorizes the transition (see
+    /// `mode::DowngradeModeGovernance`).
+    pub fn amend(
+        &mut self,
+        consent: EvoConsent,
+        reason: Option<String>,
+        governance: &dyn ModeGovernance,
+    ) -> Result<&ConsentRecord, LifecycleError> {
+        let id = consent.policy_id.clone();
+        let current = self
+            .active(&id)
+            .ok_or_else(|| LifecycleError::NotActive(id.clone()))?;
+        if current.host_did != consent.host_did {
+            return Err(LifecycleError::HostMismatch {
+                consent_id: id,
+                expected: current.host_did.clone(),
+                found: consent.host_did,
+            });
+        }
+        if current.mode != consent.mode {
+            governance
+                .authorize_transition(&consent.host_did, &current.mode, &consent.mode)
+                .map_err(|reason| LifecycleError::ModeChangeDenied {
+                    consent_id: id.clone(),
+                    reason,
+                })?;
+        }
+        let host = consent.host_did.clone();
+        self.append(&id, ConsentOp::Amend, &host, Some(consent), reason, None)
+    }
+
+    /// Revoke an active consent. Effects are applied to `sink` and to the
+    /// ledger's own view before the record is persisted. If the write
+    /// fails the error is returned, the consent stays revoked through the
+    /// deny set, and the record is retried before any later append.
+    pub fn revoke(
+        &mut self,
+        consent_id: &str,
+        reason: &str,
+        sink: &mut dyn RevocationSink,
+    ) -> Result<RevocationEffects, LifecycleError> {
+        let host = self
+            .active(consent_id)
+            .map(|c| c.host_did.clone())
+            .ok_or_else(|| LifecycleError::NotActive(consent_id.to_string()))?;
+
+        let bindings = self.bindings.remove(consent_id).unwrap_or_default();
+        let version = self.latest_record(consent_id).map(|r| r.body.version + 1).unwrap_or(1);
+        let now = now_ms();
+        let mut effects = RevocationEffects::default();
+        for token in bindings.tokens {
+            sink.invalidate_token(&token);
+            effects.invalidated_tokens.push(token);
+        }
+        for automation in bindings.automation {
+            sink.disable_automation(&automation);
+            effects.disabled_automation.push(automation);
+        }
+        for upgrade_id in bindings.upgrades {
+            let proposal = RollbackProposal {
+                consent_id: consent_id.to_string(),
+                consent_version: version,
+                upgrade_id,
+                host_did: host.clone(),
+                reason: reason.to_string(),
+                queued_utc_ms: now,
+            };
+            sink.queue_rollback(proposal.clone());
+            effects.rollbacks.push(proposal);
+        }
+
+        let appended = self
+            .append(consent_id, ConsentOp::Revoke, &host, None, Some(reason.to_string()), None)
+            .map(|_| ());
+        if let Err(e) = appended {
+            self.denied.insert(
+                consent_id.to_string(),
+                PendingRevoke {
+                    host_did: host,
+                    reason: reason.to_string(),
+                },
+            );
+            return Err(e);
+        }
+        Ok(effects)
+    }
+
+    pub fn bind_token(&mut self, consent_id: &str, token_id: &str) -> Result<(), LifecycleError> {
+        self.bind(consent_id, BindingKind::Token, token_id)
+    }
+
+    pub fn bind_automation(&mut self, consent_id: &str, automation_id: &str) -> Result<(), LifecycleError> {
+        self.bind(consent_id, BindingKind::Automation, automation_id)
+    }
+
+    /// Run `EvoConsent::enforce_on` for `desc` under the active terms of
+    /// `consent_id` and, if it passes, bind `upgrade_id` to the consent so
+    /// a later revoke rolls it back. Nothing is recorded on refusal.
+    #[allow(clippy::too_many_arguments)]
+    pub fn admit_upgrade(
+        &mut self,
+        consent_id: &str,
+        upgrade_id: &str,
+        desc: &UpgradeDescriptor,
+        host: &HostBudget,
+        snap: &BciHostSnapshot,
+        step: &StepContext,
+        profiles: &ModeProfiles,
+    ) -> Result<(), LifecycleError> {
+        let consent = self
+            .active(consent_id)
+            .ok_or_else(|| LifecycleError::NotActive(consent_id.to_string()))?;
+        consent
+            .enforce_on(desc, host, snap, step, profiles)
+            .map_err(|error| LifecycleError::UpgradeRefused {
+                consent_id: consent_id.to_string(),
+                upgrade_id: upgrade_id.to_string(),
+                error,
+            })?;
+        self.record_upgrade(consent_id, upgrade_id)
+    }
+
+    /// Record that `upgrade_id` was applied under `consent_id`.
+    pub fn record_upgrade(&mut self, consent_id: &str, upgrade_id: &str) -> Result<(), LifecycleError> {
+        self.bind(consent_id, BindingKind::Upgrade, upgrade_id)
+    }
+
+    /// A token is usable only while the consent it is bound to is active.
+    pub fn token_valid(&self, token_id: &str) -> bool {
+        self.bindings.values().any(|b| b.tokens.contains(token_id))
+    }
+
+    pub fn automation_allowed(&self, automation_id: &str) -> bool {
+        self.bindings.values().any(|b| b.automation.contains(automation_id))
+    }
+
+    /// Re-check every link; returns the first broken one.
+    pub fn verify(&self) -> Result<(), LifecycleError> {
+        let mut prev = GENESIS_HASH.to_string();
+        for (i, r) in self.records.iter().enumerate() {
+            if r.body.seq != i as u64 {
+                return Err(LifecycleError::BrokenChain {
+                    seq: r.body.seq,
+                    message: format!("expected seq {i}"),
+                });
+            }
+            if r.body.prev_hash != prev {
+                return Err(LifecycleError::BrokenChain {
+                    seq: r.body.seq,
+                    message: "prev_hash does not match previous record".into(),
+                });
+            }
+            if r.body.hash()? != r.hash {
+                return Err(LifecycleError::BrokenChain {
+                    seq: r.body.seq,
+                    message: "record hash does not match contents".into(),
+                });
+            }
+            prev = r.hash.clone();
+        }
+        Ok(())
+    }
+
+    fn latest_record(&self, consent_id: &str) -> Option<&ConsentRecord> {
+        self.latest.get(consent_id).map(|&i| &self.records[i])
+    }
+
+    fn bind(&mut self, consent_id: &str, kind: BindingKind, id: &str) -> Result<(), LifecycleError> {
+        let host = self
+            .active(consent_id)
+            .map(|c| c.host_did.clone())
+            .ok_or_else(|| LifecycleError::NotActive(consent_id.to_string()))?;
+        let binding = ConsentBinding {
+            kind,
+            id: id.to_string(),
+        };
+        self.append(consent_id, ConsentOp::Bind, &host, None, None, Some(binding))?;
+        Ok(())
+    }
+
+    fn check_link(&self, record: &ConsentRecord) -> Result<(), LifecycleError> {
+        let seq = record.body.seq;
+        if seq != self.records.len() as u64 || record.body.prev_hash != self.head_hash() {
+            return Err(LifecycleError::BrokenChain {
+                seq,
+                message: "record does not extend the chain head".into(),
+            });
+        }
+        if record.body.hash()? != record.hash {
+            return Err(LifecycleError::BrokenChain {
+                seq,
+                message: "record hash does not match contents".into(),
+            });
+        }
+        Ok(())
+    }
+
+    fn append(
+        &mut self,
+        consent_id: &str,
+        op: ConsentOp,
+        host_did: &str,
+        consent: Option<EvoConsent>,
+        reason: Option<String>,
+        binding: Option<ConsentBinding>,
+    ) -> Result<&ConsentRecord, LifecycleError> {
+        self.retry_pending_revokes()?;
+        let record = self.next_record(consent_id, op, host_did, consent, reason, binding)?;
+        self.persist(&record)?;
+        self.index(record);
+        Ok(self.records.last().expect("just indexed"))
+    }
+
+    /// The record `op` would append at the current head.
+    fn next_record(
+        &self,
+        consent_id: &str,
+        op: ConsentOp,
+        host_did: &str,
+        consent: Option<EvoConsent>,
+        reason: Option<String>,
+        binding: Option<ConsentBinding>,
+    ) -> Result<ConsentRecord, LifecycleError> {
+        let current = self.latest_record(consent_id).map(|r| r.body.version).unwrap_or(0);
+        let body = ConsentRecordBody {
+            seq: self.records.len() as u64,
+            consent_id: consent_id.to_string(),
+            version: if op == ConsentOp::Bind { current } else { current + 1 },
+            op,
+            host_did: host_did.to_string(),
+            utc_ms: now_ms(),
+            consent,
+            reason,
+            binding,
+            prev_hash: self.head_hash().to_string(),
+        };
+        Ok(ConsentRecord {
+            hash: body.hash()?,
+            body,
+        })
+    }
+
+    fn persist(&self, record: &ConsentRecord) -> Result<(), LifecycleError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        let start = file.metadata()?.len();
+        let written = file.write_all(line.as_bytes()).and_then(|_| file.sync_data());
+        if let Err(e) = written {
+            // Never leave a partial or unacknowledged line behind the head.
+            let _ = file.set_len(start);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    fn index(&mut self, record: ConsentRecord) {
+        let id = record.body.consent_id.clone();
+        match (record.body.op, &record.body.binding) {
+            (ConsentOp::Bind, Some(b)) => {
+                let bindings = self.bindings.entry(id).or_default();
+                let set = match b.kind {
+                    BindingKind::Token => &mut bindings.tokens,
+                    BindingKind::Automation => &mut bindings.automation,
+                    BindingKind::Upgrade => &mut bindings.upgrades,
+                };
+                set.insert(b.id.clone());
+            }
+            (ConsentOp::Bind, None) => {}
+            (ConsentOp::Revoke, _) => {
+                self.bindings.remove(&id);
+                self.latest.insert(id, self.records.len());
+            }
+            _ => {
+                self.latest.insert(id, self.records.len());
+            }
+        }
+        self.records.push(record);
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
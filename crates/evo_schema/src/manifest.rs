@@ -0,0 +1,414 @@
+//! Typed loader for `.evo` neuro-consent operational manifests
+//! (e.g. `src/main/schemas/neuro-consent-operational-manifest.evo`).
+//!
+//! `parse` checks structure only (required keys, types). `validate` adds
+//! semantic checks: DID formats, symbolic thresholds, BCI bands, consent
+//! binding. `load` does both and fails on any issue. A validated manifest
+//! converts into the `EvoConsent` / `ConsentEnvelopes` pair used by
+//! `EvoConsent::enforce_on`, once host-specific symbols are resolved.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use cyberswarm_neurostack::alnparticles::ALNComplianceParticle;
+use evidence_types::EvidenceBundle10;
+use neurorights_types::ConsentEnvelopes;
+
+use crate::{EvoConsent, EvoMode};
+
+pub const MANIFEST_SCHEMA: &str = "neuro-consent-operational-manifest";
+
+/// Bech32 data charset used by bostrom addresses.
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// A threshold given either as a number or as a host symbol (`brain_min`).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Threshold {
+    Value(f64),
+    Symbol(String),
+}
+
+impl Threshold {
+    pub fn resolve(&self, symbols: &SymbolTable) -> Option<f64> {
+        match self {
+            Threshold::Value(v) => Some(*v),
+            Threshold::Symbol(s) => symbols.get(s).copied(),
+        }
+    }
+}
+
+/// Host-local values for symbolic thresholds (from `HostEnvelope`).
+pub type SymbolTable = BTreeMap<String, f64>;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NeuroConsentManifest {
+    #[serde(deserialize_with = "scalar_string")]
+    pub version: String,
+    pub schema: String,
+    pub holder: Holder,
+    pub principles: Principles,
+    pub lifeforce_invariants: LifeforceInvariants,
+    pub neurovascular_mutation: NeurovascularMutation,
+    pub provenance_and_consent: ProvenanceAndConsent,
+    pub automatic_evolution: AutomaticEvolution,
+    #[serde(default)]
+    pub governance_and_updates: Option<GovernanceAndUpdates>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Holder {
+    pub did: String,
+    pub role: String,
+    #[serde(default)]
+    pub jurisdiction_hints: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Principles {
+    pub inner_domain: InnerDomain,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct InnerDomain {
+    pub description: String,
+    pub allowed_uses: Vec<String>,
+    pub forbidden_uses: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct LifeforceInvariants {
+    pub brain: BrainInvariant,
+    pub blood: FloorInvariant,
+    pub oxygen: FloorInvariant,
+    pub nano: NanoInvariant,
+    pub eco: EcoInvariant,
+    pub bci: BciInvariant,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BrainInvariant {
+    pub min: Threshold,
+    pub allow_negative: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct FloorInvariant {
+    pub min: Threshold,
+    pub hard_zero_forbidden: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NanoInvariant {
+    pub max_fraction: Threshold,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EcoInvariant {
+    pub max_ecocost_per_interval: Threshold,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BciInvariant {
+    pub ceiling: f64,
+    pub warning_band: f64,
+    pub roh_monotone: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NeurovascularMutation {
+    pub domain_id: String,
+    pub semantics: String,
+    /// Single-key maps, e.g. `- uses-decay: true`.
+    pub constraints: Vec<BTreeMap<String, serde_yaml::Value>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProvenanceAndConsent {
+    pub required_provenance: RequiredProvenance,
+    pub required_consent: RequiredConsent,
+    pub enforcement: ConsentEnforcement,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RequiredProvenance {
+    pub shard: String,
+    pub fields: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RequiredConsent {
+    pub shard: String,
+    pub bound_to_did: String,
+    pub domain_match: String,
+    pub time_bounded: bool,
+    #[serde(default)]
+    pub exclusions: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConsentEnforcement {
+    pub construct_systemadjustment_only_if: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AutomaticEvolution {
+    pub metabolic_consent: MetabolicConsent,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MetabolicMode {
+    ManualOnly,
+    AutoMicro,
+    AutoMicroPlusWave,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MetabolicConsent {
+    pub modes: Vec<MetabolicMode>,
+    #[serde(default)]
+    pub rules: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GovernanceAndUpdates {
+    #[serde(default)]
+    pub alignment_baselines: Vec<String>,
+    #[serde(default)]
+    pub update_rules: Vec<String>,
+}
+
+/// One semantic problem, addressed by its YAML key path.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestIssue {
+    pub path: String,
+    pub message: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ManifestError {
+    #[error("I/O error while reading manifest: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("manifest does not match schema: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("manifest failed validation: {0:?}")]
+    Invalid(Vec<ManifestIssue>),
+    #[error("symbolic threshold '{0}' has no host value")]
+    UnresolvedSymbol(String),
+}
+
+/// Symbols each threshold may reference.
+const BRAIN_SYMBOLS: &[&str] = &["brain_min"];
+const BLOOD_SYMBOLS: &[&str] = &["blood_min"];
+const OXYGEN_SYMBOLS: &[&str] = &["oxygen_min"];
+const NANO_SYMBOLS: &[&str] = &["nanomaxfraction"];
+const ECO_SYMBOLS: &[&str] = &["ecoflopslimit"];
+
+impl NeuroConsentManifest {
+    pub fn parse(text: &str) -> Result<Self, ManifestError> {
+        Ok(serde_yaml::from_str(text)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ManifestError> {
+        let manifest = Self::parse(&std::fs::read_to_string(path)?)?;
+        let issues = manifest.validate();
+        if !issues.is_empty() {
+            return Err(ManifestError::Invalid(issues));
+        }
+        Ok(manifest)
+    }
+
+    /// Semantic checks; returns every issue found rather than the first.
+    pub fn validate(&self) -> Vec<ManifestIssue> {
+        let mut issues = Vec::new();
+        let mut issue = |path: &str, message: String| {
+            issues.push(ManifestIssue {
+                path: path.to_string(),
+                message,
+            })
+        };
+
+        if self.schema != MANIFEST_SCHEMA {
+            issue("schema", format!("expected '{MANIFEST_SCHEMA}', found '{}'", self.schema));
+        }
+        if let Err(e) = validate_did(&self.holder.did) {
+            issue("holder.did", e);
+        }
+        let bound = &self.provenance_and_consent.required_consent.bound_to_did;
+        if let Err(e) = validate_did(bound) {
+            issue("provenance-and-consent.required-consent.bound-to-did", e);
+        }
+        if bare_did(bound) != bare_did(&self.holder.did) {
+            issue(
+                "provenance-and-consent.required-consent.bound-to-did",
+                format!("consent bound to {bound}, but holder is {}", self.holder.did),
+            );
+        }
+
+        if !self.principles.inner_domain.allowed_uses.is_empty() {
+            issue(
+                "principles.inner-domain.allowed-uses",
+                "inner domain must not allow any use".into(),
+            );
+        }
+
+        let li = &self.lifeforce_invariants;
+        let thresholds: [(&str, &Threshold, &[&str], bool); 5] = [
+            ("lifeforce-invariants.brain.min", &li.brain.min, BRAIN_SYMBOLS, false),
+            ("lifeforce-invariants.blood.min", &li.blood.min, BLOOD_SYMBOLS, false),
+            ("lifeforce-invariants.oxygen.min", &li.oxygen.min, OXYGEN_SYMBOLS, false),
+            ("lifeforce-invariants.nano.max-fraction", &li.nano.max_fraction, NANO_SYMBOLS, true),
+            (
+                "lifeforce-invariants.eco.max-ecocost-per-interval",
+                &li.eco.max_ecocost_per_interval,
+                ECO_SYMBOLS,
+                false,
+            ),
+        ];
+        for (path, threshold, allowed, is_fraction) in thresholds {
+            match threshold {
+                Threshold::Symbol(s) if !allowed.contains(&s.as_str()) => {
+                    issue(path, format!("unknown symbol '{s}', expected one of {allowed:?}"))
+                }
+                Threshold::Value(v) if !v.is_finite() || *v < 0.0 => {
+                    issue(path, format!("{v} is not a finite, non-negative value"))
+                }
+                Threshold::Value(v) if is_fraction && *v > 1.0 => {
+                    issue(path, format!("fraction {v} exceeds 1.0"))
+                }
+                _ => {}
+            }
+        }
+        if li.brain.allow_negative {
+            issue("lifeforce-invariants.brain.allow-negative", "BRAIN may never go negative".into());
+        }
+        if !li.blood.hard_zero_forbidden || !li.oxygen.hard_zero_forbidden {
+            issue("lifeforce-invariants", "BLOOD and OXYGEN must forbid hard zero".into());
+        }
+        if !(0.0..=0.3).contains(&li.bci.ceiling) {
+            issue("lifeforce-invariants.bci.ceiling", format!("{} outside [0, 0.3]", li.bci.ceiling));
+        }
+        if li.bci.warning_band > li.bci.ceiling {
+            issue(
+                "lifeforce-invariants.bci.warning-band",
+                "warning band above ceiling".into(),
+            );
+        }
+
+        if self.automatic_evolution.metabolic_consent.modes.is_empty() {
+            issue(
+                "automatic-evolution.metabolic-consent.modes",
+                "at least one metabolic consent mode is required".into(),
+            );
+        }
+
+        issues
+    }
+
+    /// Most permissive consent mode the manifest grants.
+    pub fn evo_mode(&self) -> EvoMode {
+        let modes = &self.automatic_evolution.metabolic_consent.modes;
+        if modes.iter().any(|m| *m != MetabolicMode::ManualOnly) {
+            EvoMode::Copilot
+        } else {
+            EvoMode::Conservative
+        }
+    }
+
+    pub fn consent_envelopes(&self, symbols: &SymbolTable) -> Result<ConsentEnvelopes, ManifestError> {
+        let li = &self.lifeforce_invariants;
+        let resolve = |t: &Threshold| {
+            t.resolve(symbols).ok_or_else(|| match t {
+                Threshold::Symbol(s) => ManifestError::UnresolvedSymbol(s.clone()),
+                Threshold::Value(v) => ManifestError::UnresolvedSymbol(v.to_string()),
+            })
+        };
+        Ok(ConsentEnvelopes {
+            brain_min: resolve(&li.brain.min)?,
+            blood_min: resolve(&li.blood.min)?,
+            oxygen_min: resolve(&li.oxygen.min)?,
+            nano_max_fraction: resolve(&li.nano.max_fraction)?,
+            eco_max_cost_per_interval: resolve(&li.eco.max_ecocost_per_interval)?,
+            bci_ceiling: li.bci.ceiling,
+            bci_warning_band: li.bci.warning_band,
+            roh_monotone: li.bci.roh_monotone,
+            ..ConsentEnvelopes::default()
+        })
+    }
+
+    /// Build the `EvoConsent` this manifest authorizes. ALN particles and
+    /// the evidence bundle come from their own shards, not the manifest.
+    pub fn to_evo_consent(
+        &self,
+        policy_id: &str,
+        symbols: &SymbolTable,
+        aln_particles: Vec<ALNComplianceParticle>,
+        evidence_bundle: EvidenceBundle10,
+    ) -> Result<EvoConsent, ManifestError> {
+        let issues = self.validate();
+        if !issues.is_empty() {
+            return Err(ManifestError::Invalid(issues));
+        }
+        Ok(EvoConsent {
+            host_did: bare_did(&self.holder.did).to_string(),
+            policy_id: policy_id.to_string(),
+            version: self.version.clone(),
+            mode: self.evo_mode(),
+            aln_particles,
+            envelopes: self.consent_envelopes(symbols)?,
+            evidence_bundle,
+        })
+    }
+}
+
+/// Strip an optional `did:aln:` prefix.
+pub fn bare_did(did: &str) -> &str {
+    did.strip_prefix("did:aln:").unwrap_or(did)
+}
+
+/// Check a bostrom DID/address: `bostrom1` + bech32 data charset, sane length.
+pub fn validate_did(did: &str) -> Result<(), String> {
+    let bare = bare_did(did);
+    let data = bare
+        .strip_prefix("bostrom1")
+        .ok_or_else(|| format!("'{did}' is not a bostrom1 address"))?;
+    if let Some(c) = data.chars().find(|c| !BECH32_CHARSET.contains(*c)) {
+        return Err(format!("'{did}' contains non-bech32 character '{c}'"));
+    }
+    if !(39..=90).contains(&bare.len()) {
+        return Err(format!("'{did}' has invalid length {}", bare.len()));
+    }
+    Ok(())
+}
+
+/// YAML `version: 1.0` arrives as a float; keep it as written.
+fn scalar_string<'de, D: Deserializer<'de>>(de: D) -> Result<String, D::Error> {
+    let value = serde_yaml::Value::deserialize(de)?;
+    match value {
+        serde_yaml::Value::String(s) => Ok(s),
+        serde_yaml::Value::Number(n) => Ok(n.to_string()),
+        other => Err(serde::de::Error::custom(format!(
+            "expected string or number, found {other:?}"
+        ))),
+    }
+}
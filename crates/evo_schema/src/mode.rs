@@ -0,0 +1,273 @@
+//! Concrete policy behind each `EvoMode`.
+//!
+//! - `Conservative`: host-initiated, strict-band micro-steps only.
+//! - `Copilot`: as Conservative, plus automatic micro-steps while a valid
+//!   SMART token is held.
+//! - `Custom(id)`: a `ModePolicy` loaded from a `.evomode.aln` shard. A
+//!   custom profile can never grant automatic evolution without SMART.
+//!
+//! Changing mode yields a new `EvoConsent` only after `ModeGovernance`
+//! approves, and `lifecycle::ConsentLedger::amend` asks it again before
+//! recording a consent whose mode differs. `DowngradeModeGovernance` runs
+//! the target through `sovereigntycore::downgrade` against the policy of
+//! the mode being left: dropping any restriction that mode had (automatic
+//! steps, research band, macro steps) is a loosening and needs host-signed
+//! consent.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use sovereigntycore::downgrade::{DowngradeConsent, DowngradeGate, PolicySurface, PolicyUpdate};
+
+use crate::{EvoConsent, EvoMode, PolicyError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoEvolution {
+    Forbidden,
+    SmartGated,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModePolicy {
+    pub auto_evolution: AutoEvolution,
+    pub allow_research_band: bool,
+    pub micro_steps_only: bool,
+}
+
+impl ModePolicy {
+    pub const CONSERVATIVE: ModePolicy = ModePolicy {
+        auto_evolution: AutoEvolution::Forbidden,
+        allow_research_band: false,
+        micro_steps_only: true,
+    };
+
+    pub const COPILOT: ModePolicy = ModePolicy {
+        auto_evolution: AutoEvolution::SmartGated,
+        allow_research_band: false,
+        micro_steps_only: true,
+    };
+
+    /// Comparable view for `sovereigntycore::downgrade`; each restriction is
+    /// a protection, so dropping one is a loosening.
+    pub fn policy_surface(&self) -> PolicySurface {
+        PolicySurface::default()
+            .require("auto_evolution_forbidden", self.auto_evolution == AutoEvolution::Forbidden)
+            .require("research_band_forbidden", !self.allow_research_band)
+            .require("micro_steps_only", self.micro_steps_only)
+    }
+
+    /// Admit or refuse one step under this policy.
+    pub fn admit(&self, step: &StepContext) -> Result<(), PolicyError> {
+        if step.automatic {
+            match self.auto_evolution {
+                AutoEvolution::Forbidden => return Err(PolicyError::AutoEvolutionForbidden),
+                AutoEvolution::SmartGated if !step.smart_token_valid => {
+                    return Err(PolicyError::SmartTokenRequired)
+                }
+                AutoEvolution::SmartGated => {}
+            }
+        }
+        if step.band == StepBand::Research && !self.allow_research_band {
+            return Err(PolicyError::ResearchBandForbidden);
+        }
+        if self.micro_steps_only && !step.micro {
+            return Err(PolicyError::MacroStepForbidden);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepBand {
+    Strict,
+    Research,
+}
+
+/// How the step under evaluation was initiated and what it touches.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepContext {
+    /// True when no host action initiated the step (auto-evolution).
+    pub automatic: bool,
+    pub band: StepBand,
+    /// True if the step stays within the micro-step SCALE cap.
+    pub micro: bool,
+    /// A SMART token in scope for this session is currently valid.
+    pub smart_token_valid: bool,
+}
+
+/// A named custom profile, e.g. `qpudatashards/particles/*.evomode.aln`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModeProfile {
+    pub profile_id: String,
+    pub subjectid: String,
+    pub version: String,
+    pub policy: ModePolicy,
+}
+
+impl ModeProfile {
+    /// Parse the line-oriented ALN form (`meta` + `policy` sections).
+    pub fn from_aln_str(text: &str) -> Result<Self, String> {
+        let mut section = "";
+        let mut profile_id = None;
+        let mut subjectid = None;
+        let mut version = None;
+        let mut auto = None;
+        let mut research = None;
+        let mut micro = None;
+
+        for (idx, raw) in text.lines().enumerate() {
+            let line = raw.split('#').next().unwrap_or("").trim();
+            if line.is_empty() || line == "aln" {
+                continue;
+            }
+            if !raw.starts_with(' ') {
+                section = line;
+                continue;
+            }
+            let (key, value) = match line.split_once(char::is_whitespace) {
+                Some((k, v)) => (k, v.trim()),
+                None => (line, ""),
+            };
+            let n = idx + 1;
+            match (section, key) {
+                ("meta", "profileid") => profile_id = Some(value.to_string()),
+                ("meta", "subjectid") => subjectid = Some(value.to_string()),
+                ("meta", "version") => version = Some(value.to_string()),
+                ("policy", "auto_evolution") => {
+                    auto = Some(match value {
+                        "forbidden" => AutoEvolution::Forbidden,
+                        "smart_gated" => AutoEvolution::SmartGated,
+                        other => return Err(format!("line {n}: unknown auto_evolution '{other}'")),
+                    })
+                }
+                ("policy", "allow_research_band") => research = Some(parse_bool(value, n)?),
+                ("policy", "micro_steps_only") => micro = Some(parse_bool(value, n)?),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            profile_id: profile_id.ok_or("meta.profileid missing")?,
+            subjectid: subjectid.ok_or("meta.subjectid missing")?,
+            version: version.ok_or("meta.version missing")?,
+            policy: ModePolicy {
+                auto_evolution: auto.ok_or("policy.auto_evolution missing")?,
+                allow_research_band: research.ok_or("policy.allow_research_band missing")?,
+                micro_steps_only: micro.ok_or("policy.micro_steps_only missing")?,
+            },
+        })
+    }
+}
+
+fn parse_bool(value: &str, line: usize) -> Result<bool, String> {
+    value
+        .parse()
+        .map_err(|_| format!("line {line}: expected true/false, found '{value}'"))
+}
+
+/// Custom profiles available to `EvoMode::Custom`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ModeProfiles {
+    profiles: BTreeMap<String, ModeProfile>,
+}
+
+impl ModeProfiles {
+    pub fn insert(&mut self, profile: ModeProfile) {
+        self.profiles.insert(profile.profile_id.clone(), profile);
+    }
+
+    pub fn get(&self, profile_id: &str) -> Option<&ModeProfile> {
+        self.profiles.get(profile_id)
+    }
+
+    /// Policy for `mode` on behalf of `host_did`. Unknown custom profiles,
+    /// or ones bound to another subject, resolve to an error.
+    pub fn policy_for(&self, mode: &EvoMode, host_did: &str) -> Result<ModePolicy, PolicyError> {
+        match mode {
+            EvoMode::Conservative => Ok(ModePolicy::CONSERVATIVE),
+            EvoMode::Copilot => Ok(ModePolicy::COPILOT),
+            EvoMode::Custom(id) => match self.profiles.get(id) {
+                Some(p) if p.subjectid == host_did => Ok(p.policy.clone()),
+                _ => Err(PolicyError::UnknownModeProfile),
+            },
+        }
+    }
+}
+
+/// Approves or refuses a mode change, e.g. by checking for an EVOLVE token
+/// with `policyupdate` scope held by the host.
+pub trait ModeGovernance {
+    fn authorize_transition(&self, host_did: &str, from: &EvoMode, to: &EvoMode) -> Result<(), String>;
+}
+
+fn mode_label(mode: &EvoMode, profiles: &ModeProfiles) -> String {
+    match mode {
+        EvoMode::Conservative => "conservative".to_string(),
+        EvoMode::Copilot => "copilot".to_string(),
+        EvoMode::Custom(id) => match profiles.get(id) {
+            Some(p) => format!("custom:{id}@{}", p.version),
+            None => format!("custom:{id}"),
+        },
+    }
+}
+
+/// The downgrade review for moving `host_did` from mode `from` into mode
+/// `to`. Hosts sign a `DowngradeConsent` over this update to admit a
+/// looser mode.
+pub fn mode_update(
+    host_did: &str,
+    from: &EvoMode,
+    to: &EvoMode,
+    profiles: &ModeProfiles,
+) -> Result<PolicyUpdate, PolicyError> {
+    let current = profiles.policy_for(from, host_did)?;
+    let proposed = profiles.policy_for(to, host_did)?;
+    Ok(PolicyUpdate {
+        artifact: format!("evomode:{host_did}"),
+        subjectid: host_did.to_string(),
+        from_version: mode_label(from, profiles),
+        to_version: mode_label(to, profiles),
+        current: current.policy_surface(),
+        proposed: proposed.policy_surface(),
+    })
+}
+
+/// `ModeGovernance` backed by the downgrade gate. A transition that keeps
+/// every restriction of the mode being left passes (Copilot to
+/// Conservative, say); one that drops any (Conservative to Copilot, or
+/// into a looser custom profile) passes only with `consent` covering it.
+pub struct DowngradeModeGovernance<'a> {
+    pub profiles: &'a ModeProfiles,
+    pub gate: DowngradeGate<'a>,
+    pub consent: Option<&'a DowngradeConsent>,
+}
+
+impl ModeGovernance for DowngradeModeGovernance<'_> {
+    fn authorize_transition(&self, host_did: &str, from: &EvoMode, to: &EvoMode) -> Result<(), String> {
+        let update = mode_update(host_did, from, to, self.profiles).map_err(|e| format!("{e:?}"))?;
+        self.gate.review(&update, self.consent).map(|_| ())
+    }
+}
+
+impl EvoConsent {
+    /// Copy of this consent in mode `to`, if the target resolves and
+    /// governance approves.
+    pub fn with_mode(
+        &self,
+        to: EvoMode,
+        profiles: &ModeProfiles,
+        governance: &dyn ModeGovernance,
+    ) -> Result<EvoConsent, PolicyError> {
+        profiles.policy_for(&to, &self.host_did)?;
+        governance
+            .authorize_transition(&self.host_did, &self.mode, &to)
+            .map_err(|_| PolicyError::ModeTransitionDenied)?;
+        Ok(EvoConsent {
+            mode: to,
+            ..self.clone()
+        })
+    }
+}
@@ -1,5 +1,9 @@
 #![forbid(unsafe_code)]
 
+pub mod lifecycle;
+pub mod manifest;
+pub mod mode;
+
 use serde::{Deserialize, Serialize};
 use bioscale_upgrade_store::{UpgradeDescriptor, HostBudget};
 use cyberswarm_neurostack::alnparticles::ALNComplianceParticle;
@@ -7,7 +11,10 @@ use cyberswarm_neurostack::telemetry::BciHostSnapshot;
 use neurorights_types::ConsentEnvelopes;
 use evidence_types::EvidenceBundle10;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+use mode::{ModeProfiles, StepContext};
+
+/// See `mode` for the policy each variant maps to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EvoMode {
     Conservative,
     Copilot,
@@ -32,15 +39,45 @@ pub enum PolicyError {
     BudgetViolation,
     EnvelopeViolation,
     EvidenceMissing,
+    AutoEvolutionForbidden,
+    SmartTokenRequired,
+    ResearchBandForbidden,
+    MacroStepForbidden,
+    UnknownModeProfile,
+    ModeTransitionDenied,
+}
+
+impl PolicyError {
+    /// Stable clause id, e.g. `missing_rollback_anytime`; independent of
+    /// the variant's Rust name.
+    pub fn clause_id(&self) -> &'static str {
+        match self {
+            PolicyError::MissingRollbackAnytime => "missing_rollback_anytime",
+            PolicyError::MissingMentalPrivacyFlag => "missing_mental_privacy_flag",
+            PolicyError::BudgetViolation => "budget_violation",
+            PolicyError::EnvelopeViolation => "envelope_violation",
+            PolicyError::EvidenceMissing => "evidence_missing",
+            PolicyError::AutoEvolutionForbidden => "auto_evolution_forbidden",
+            PolicyError::SmartTokenRequired => "smart_token_required",
+            PolicyError::ResearchBandForbidden => "research_band_forbidden",
+            PolicyError::MacroStepForbidden => "macro_step_forbidden",
+            PolicyError::UnknownModeProfile => "unknown_mode_profile",
+            PolicyError::ModeTransitionDenied => "mode_transition_denied",
+        }
+    }
 }
 
 impl EvoConsent {
     /// Hard gate: if this returns Err, the upgrade is not admissible.
+    /// `lifecycle::ConsentLedger::admit_upgrade` runs it before binding an
+    /// upgrade to the consent.
     pub fn enforce_on(
         &self,
         desc: &UpgradeDescriptor,
         host: &HostBudget,
         snap: &BciHostSnapshot,
+        step: &StepContext,
+        profiles: &ModeProfiles,
     ) -> Result<(), PolicyError> {
         // 1. Neurorights: rollbackanytime, no non-consensual modulation, no raw EEG export.
         if !self
@@ -83,6 +120,9 @@ impl EvoConsent {
             return Err(PolicyError::EvidenceMissing);
         }
 
+        // 5. Mode: auto-evolution, RoH band and step size allowed by `self.mode`.
+        profiles.policy_for(&self.mode, &self.host_did)?.admit(step)?;
+
         Ok(())
     }
 }
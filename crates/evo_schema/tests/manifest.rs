@@ -0,0 +1,104 @@
+//! Loader and semantic checks for `.evo` neuro-consent manifests.
+
+use evo_schema::manifest::{
+    bare_did, validate_did, ManifestError, NeuroConsentManifest, SymbolTable, Threshold,
+};
+use evo_schema::EvoMode;
+
+const MANIFEST: &str = include_str!("../../../src/main/schemas/neuro-consent-operational-manifest.evo");
+const HOLDER: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+
+fn issue_paths(text: &str) -> Vec<String> {
+    NeuroConsentManifest::parse(text)
+        .expect("manifest parses")
+        .validate()
+        .into_iter()
+        .map(|i| i.path)
+        .collect()
+}
+
+fn symbols() -> SymbolTable {
+    [
+        ("brain_min", 0.2),
+        ("blood_min", 0.3),
+        ("oxygen_min", 0.9),
+        ("nanomaxfraction", 0.05),
+        ("ecoflopslimit", 1.0e9),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v))
+    .collect()
+}
+
+#[test]
+fn checked_in_manifest_validates() {
+    let manifest = NeuroConsentManifest::parse(MANIFEST).unwrap();
+    let issues = manifest.validate();
+    assert!(issues.is_empty(), "{issues:?}");
+    assert_eq!(manifest.version, "1.0");
+    assert_eq!(manifest.holder.did, HOLDER);
+    assert_eq!(manifest.lifeforce_invariants.brain.min, Threshold::Symbol("brain_min".into()));
+    assert!(matches!(manifest.evo_mode(), EvoMode::Copilot));
+}
+
+#[test]
+fn consent_bound_to_another_did_is_rejected() {
+    let foreign = MANIFEST.replace(
+        &format!("bound-to-did: {HOLDER}"),
+        "bound-to-did: bostrom1ldgmtf20d6604a24ztr0jxht7xt7az4jhkmsrc",
+    );
+    assert_eq!(issue_paths(&foreign), ["provenance-and-consent.required-consent.bound-to-did"]);
+
+    // A `did:aln:` prefix on either side still binds the same holder.
+    let prefixed = MANIFEST.replace(&format!("bound-to-did: {HOLDER}"), &format!("bound-to-did: did:aln:{HOLDER}"));
+    assert!(issue_paths(&prefixed).is_empty());
+}
+
+#[test]
+fn reports_every_semantic_issue() {
+    let broken = MANIFEST
+        .replace("min: brain_min", "min: brain_max")
+        .replace("max-fraction: nanomaxfraction", "max-fraction: 1.5")
+        .replace("allow-negative: false", "allow-negative: true")
+        .replace("ceiling: 0.3 ", "ceiling: 0.4 ")
+        .replace("modes: [ManualOnly, AutoMicro]", "modes: []");
+    assert_eq!(
+        issue_paths(&broken),
+        [
+            "lifeforce-invariants.brain.min",
+            "lifeforce-invariants.nano.max-fraction",
+            "lifeforce-invariants.brain.allow-negative",
+            "lifeforce-invariants.bci.ceiling",
+            "automatic-evolution.metabolic-consent.modes",
+        ]
+    );
+}
+
+#[test]
+fn structural_errors_fail_parse() {
+    let no_holder = MANIFEST.replace(&format!("  did: {HOLDER}\n"), "");
+    assert!(matches!(NeuroConsentManifest::parse(&no_holder), Err(ManifestError::Yaml(_))));
+}
+
+#[test]
+fn did_format() {
+    assert!(validate_did(HOLDER).is_ok());
+    assert_eq!(bare_did(&format!("did:aln:{HOLDER}")), HOLDER);
+    assert!(validate_did("cosmos18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7").is_err());
+    assert!(validate_did("bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9yeb").is_err());
+    assert!(validate_did("bostrom1qqqq").is_err());
+}
+
+#[test]
+fn envelopes_resolve_host_symbols() {
+    let manifest = NeuroConsentManifest::parse(MANIFEST).unwrap();
+    let envelopes = manifest.consent_envelopes(&symbols()).unwrap();
+    assert_eq!(envelopes.brain_min, 0.2);
+    assert_eq!(envelopes.bci_ceiling, 0.3);
+    assert!(envelopes.roh_monotone);
+
+    let mut partial = symbols();
+    partial.remove("oxygen_min");
+    let err = manifest.consent_envelopes(&partial).unwrap_err();
+    assert!(matches!(err, ManifestError::UnresolvedSymbol(s) if s == "oxygen_min"));
+}
@@ -0,0 +1,271 @@
+//! Jurisdiction-ladder resolution with strictest-wins merging.
+//!
+//! The licence header's `jurisdiction_ladder` runs from `global` down to the
+//! host's most specific jurisdiction. Each level may contribute a
+//! `PolicyFragment`; resolving for a jurisdiction merges every fragment from
+//! `global` down to it so that:
+//! - ceilings take the minimum, floors take the maximum,
+//! - a neurorights flag, once required, stays required,
+//! - prohibitions accumulate.
+//!
+//! Lower levels can therefore only tighten. Every effective value records the
+//! level that supplied it.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use sovereigntycore::downgrade::PolicySurface;
+
+use crate::HciLicence;
+
+/// Policy contributed by one ladder level. Keys are free-form but shared
+/// across levels (e.g. `roh.global_ceiling`, `min_rollback_strength`).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PolicyFragment {
+    pub level: String,
+    /// Upper limits; strictest is the minimum.
+    #[serde(default)]
+    pub ceilings: BTreeMap<String, f64>,
+    /// Lower limits; strictest is the maximum.
+    #[serde(default)]
+    pub floors: BTreeMap<String, f64>,
+    /// `true` means the protection is required.
+    #[serde(default)]
+    pub neurorights: BTreeMap<String, bool>,
+    /// Forbidden practices, including export rules (e.g. `raw_export`).
+    #[serde(default)]
+    pub prohibitions: BTreeSet<String>,
+}
+
+impl PolicyFragment {
+    /// Baseline fragment for the top ladder level, taken from the licence.
+    pub fn from_licence(licence: &HciLicence) -> Self {
+        let roh = &licence.risk_and_budgets.roh;
+        let kernel = &licence.rights_kernel.rights_kernel;
+        let rights = &licence.neurorights_and_modes.neurorights;
+        let data = &licence.data_rights;
+
+        let ceilings = BTreeMap::from([
+            ("roh.global_ceiling".to_string(), roh.global_ceiling),
+            ("roh.governance_ceiling".to_string(), roh.governance_ceiling),
+            ("roh.personhood_ceiling".to_string(), roh.personhood_ceiling),
+            ("max_psych_risk".to_string(), kernel.max_psych_risk),
+            ("max_phys_risk".to_string(), kernel.max_phys_risk),
+            ("max_eco_stress".to_string(), kernel.max_eco_stress),
+            (
+                "max_burden_without_explicit_consent".to_string(),
+                kernel.max_burden_without_explicit_consent,
+            ),
+        ]);
+        let floors = BTreeMap::from([
+            ("min_rollback_strength".to_string(), kernel.min_rollback_strength),
+            ("min_observability".to_string(), kernel.min_observability),
+        ]);
+        let neurorights = BTreeMap::from([
+            ("mental_privacy".to_string(), rights.mental_privacy),
+            ("mental_integrity".to_string(), rights.mental_integrity),
+            ("cognitive_liberty".to_string(), rights.cognitive_liberty),
+            ("noscorefrominnerstate".to_string(), rights.noscorefrominnerstate),
+            ("noneurocoercion".to_string(), rights.noneurocoercion),
+            ("revocable".to_string(), rights.revocable),
+            ("retrieval_only_default".to_string(), rights.retrieval_only_default),
+        ]);
+
+        let mut prohibitions: BTreeSet<String> = data.prohibited_practices.iter().cloned().collect();
+        if !rights.soul_fields_allowed {
+            prohibitions.insert("soul_fields".into());
+        }
+        if !rights.consciousness_labels_allowed {
+            prohibitions.insert("consciousness_labels".into());
+        }
+        if data.raw_data_policy.raw_export_forbidden {
+            prohibitions.insert("raw_export".into());
+        }
+        if data.derivative_ko_policy.no_reconstruction_of_raw_state {
+            prohibitions.insert("raw_state_reconstruction".into());
+        }
+
+        Self {
+            level: licence
+                .jurisdiction_ladder()
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "global".into()),
+            ceilings,
+            floors,
+            neurorights,
+            prohibitions,
+        }
+    }
+}
+
+/// An effective value and the ladder level that supplied it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Effective<T> {
+    pub value: T,
+    pub level: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedPolicy {
+    pub jurisdiction: String,
+    pub ceilings: BTreeMap<String, Effective<f64>>,
+    pub floors: BTreeMap<String, Effective<f64>>,
+    pub neurorights: BTreeMap<String, Effective<bool>>,
+    /// Prohibition -> level that first introduced it.
+    pub prohibitions: BTreeMap<String, String>,
+}
+
+impl ResolvedPolicy {
+    pub fn ceiling(&self, key: &str) -> Option<f64> {
+        self.ceilings.get(key).map(|e| e.value)
+    }
+
+    pub fn floor(&self, key: &str) -> Option<f64> {
+        self.floors.get(key).map(|e| e.value)
+    }
+
+    pub fn requires(&self, right: &str) -> bool {
+        self.neurorights.get(right).map(|e| e.value).unwrap_or(false)
+    }
+
+    pub fn is_prohibited(&self, practice: &str) -> bool {
+        self.prohibitions.contains_key(practice)
+    }
+
+    /// Comparable view for `sovereigntycore::downgrade`, so a new snapshot
+    /// can be checked against the policy currently in force.
+    pub fn policy_surface(&self) -> PolicySurface {
+        PolicySurface {
+            ceilings: self.ceilings.iter().map(|(k, e)| (k.clone(), e.value)).collect(),
+            floors: self.floors.iter().map(|(k, e)| (k.clone(), e.value)).collect(),
+            required: self.neurorights.iter().map(|(k, e)| (k.clone(), e.value)).collect(),
+            prohibitions: self.prohibitions.keys().cloned().collect(),
+            ..PolicySurface::default()
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum LadderError {
+    #[error("jurisdiction ladder is empty")]
+    EmptyLadder,
+    #[error("jurisdiction '{0}' is not on the ladder")]
+    UnknownJurisdiction(String),
+    #[error("fragment for level '{0}', which is not on the ladder")]
+    UnknownLevel(String),
+    #[error("more than one fragment for level '{0}'")]
+    DuplicateLevel(String),
+    #[error("level '{level}' sets non-finite value for '{key}'")]
+    NonFinite { level: String, key: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JurisdictionLadder {
+    levels: Vec<String>,
+}
+
+impl JurisdictionLadder {
+    /// `levels` runs from broadest to most specific.
+    pub fn new(levels: Vec<String>) -> Result<Self, LadderError> {
+        if levels.is_empty() {
+            return Err(LadderError::EmptyLadder);
+        }
+        Ok(Self { levels })
+    }
+
+    pub fn from_licence(licence: &HciLicence) -> Result<Self, LadderError> {
+        Self::new(licence.jurisdiction_ladder().to_vec())
+    }
+
+    pub fn levels(&self) -> &[String] {
+        &self.levels
+    }
+
+    /// Merge fragments from the top of the ladder down to `jurisdiction`.
+    /// Fragments for levels below `jurisdiction` do not apply.
+    pub fn resolve(
+        &self,
+        jurisdiction: &str,
+        fragments: &[PolicyFragment],
+    ) -> Result<ResolvedPolicy, LadderError> {
+        let depth = self
+            .levels
+            .iter()
+            .position(|l| l == jurisdiction)
+            .ok_or_else(|| LadderError::UnknownJurisdiction(jurisdiction.to_string()))?;
+
+        let mut by_level: BTreeMap<&str, &PolicyFragment> = BTreeMap::new();
+        for f in fragments {
+            if !self.levels.contains(&f.level) {
+                return Err(LadderError::UnknownLevel(f.level.clone()));
+            }
+            if by_level.insert(f.level.as_str(), f).is_some() {
+                return Err(LadderError::DuplicateLevel(f.level.clone()));
+            }
+        }
+
+        let mut out = ResolvedPolicy {
+            jurisdiction: jurisdiction.to_string(),
+            ..ResolvedPolicy::default()
+        };
+        for level in &self.levels[..=depth] {
+            let Some(fragment) = by_level.get(level.as_str()) else {
+                continue;
+            };
+            merge_limits(&mut out.ceilings, &fragment.ceilings, level, |new, cur| new < cur)?;
+            merge_limits(&mut out.floors, &fragment.floors, level, |new, cur| new > cur)?;
+            for (right, required) in &fragment.neurorights {
+                match out.neurorights.get(right) {
+                    Some(cur) if cur.value || !*required => {}
+                    _ => {
+                        out.neurorights.insert(
+                            right.clone(),
+                            Effective {
+                                value: *required,
+                                level: level.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+            for p in &fragment.prohibitions {
+                out.prohibitions.entry(p.clone()).or_insert_with(|| level.clone());
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// Replace the current value only when `stricter(new, current)`; ties keep
+/// the broader level as the source.
+fn merge_limits(
+    out: &mut BTreeMap<String, Effective<f64>>,
+    incoming: &BTreeMap<String, f64>,
+    level: &str,
+    stricter: impl Fn(f64, f64) -> bool,
+) -> Result<(), LadderError> {
+    for (key, value) in incoming {
+        if !value.is_finite() {
+            return Err(LadderError::NonFinite {
+                level: level.to_string(),
+                key: key.clone(),
+            });
+        }
+        let replace = match out.get(key) {
+            Some(cur) => stricter(*value, cur.value),
+            None => true,
+        };
+        if replace {
+            out.insert(
+                key.clone(),
+                Effective {
+                    value: *value,
+                    level: level.to_string(),
+                },
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,532 @@
+//! Typed model of the HCI sovereign-use licence (HCI-SUL) NDJSON snapshot,
+//! e.g. `qpudatashards/hci/hci-sul-v1-snapshot-2026-02-01.ndjson`.
+//!
+//! A snapshot is six shards, one per `shard_kind`, all for the same host.
+//! `HciLicence::load` parses them, checks schema/version, host binding and
+//! the non-financial flags, and merges them into one licence object.
+
+#![forbid(unsafe_code)]
+
+pub mod jurisdiction;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const LICENCE_SCHEMA: &str = "hci.sovereign.use.licence.v1";
+pub const LICENCE_VERSION: &str = "1.0";
+
+/// Fields every shard carries.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShardMeta {
+    pub schema: String,
+    pub version: String,
+    pub hostid: String,
+    pub hexstamp: String,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "shard_kind")]
+pub enum HciShard {
+    #[serde(rename = "HCI_LICENSE_HEADER")]
+    LicenseHeader(LicenseHeader),
+    #[serde(rename = "HCI_NEURORIGHTS_AND_MODES")]
+    NeurorightsAndModes(NeurorightsAndModes),
+    #[serde(rename = "HCI_RISK_AND_BUDGETS")]
+    RiskAndBudgets(RiskAndBudgets),
+    #[serde(rename = "HCI_RIGHTS_KERNEL_AND_DOWNGRADE_RESISTANCE")]
+    RightsKernelAndDowngradeResistance(RightsKernelAndDowngradeResistance),
+    #[serde(rename = "HCI_AUDIT_AND_NEURAL_ROPE")]
+    AuditAndNeuralRope(AuditAndNeuralRope),
+    #[serde(rename = "HCI_DATA_RIGHTS_AND_KO_EXPORT")]
+    DataRightsAndKoExport(DataRightsAndKoExport),
+}
+
+impl HciShard {
+    pub const KINDS: [&'static str; 6] = [
+        "HCI_LICENSE_HEADER",
+        "HCI_NEURORIGHTS_AND_MODES",
+        "HCI_RISK_AND_BUDGETS",
+        "HCI_RIGHTS_KERNEL_AND_DOWNGRADE_RESISTANCE",
+        "HCI_AUDIT_AND_NEURAL_ROPE",
+        "HCI_DATA_RIGHTS_AND_KO_EXPORT",
+    ];
+
+    pub fn kind(&self) -> &'static str {
+        Self::KINDS[self.kind_index()]
+    }
+
+    pub fn meta(&self) -> &ShardMeta {
+        match self {
+            HciShard::LicenseHeader(s) => &s.meta,
+            HciShard::NeurorightsAndModes(s) => &s.meta,
+            HciShard::RiskAndBudgets(s) => &s.meta,
+            HciShard::RightsKernelAndDowngradeResistance(s) => &s.meta,
+            HciShard::AuditAndNeuralRope(s) => &s.meta,
+            HciShard::DataRightsAndKoExport(s) => &s.meta,
+        }
+    }
+
+    fn kind_index(&self) -> usize {
+        match self {
+            HciShard::LicenseHeader(_) => 0,
+            HciShard::NeurorightsAndModes(_) => 1,
+            HciShard::RiskAndBudgets(_) => 2,
+            HciShard::RightsKernelAndDowngradeResistance(_) => 3,
+            HciShard::AuditAndNeuralRope(_) => 4,
+            HciShard::DataRightsAndKoExport(_) => 5,
+        }
+    }
+}
+
+// ---- HCI_LICENSE_HEADER ----
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LicenseHeader {
+    #[serde(flatten)]
+    pub meta: ShardMeta,
+    pub did: String,
+    pub primary_bostrom: String,
+    #[serde(default)]
+    pub alt_bostrom: Vec<String>,
+    #[serde(default)]
+    pub safe_addresses: Vec<String>,
+    /// Broadest first, e.g. `global` .. `us-az-maricopa-phoenix`.
+    pub jurisdiction_ladder: Vec<String>,
+    pub license_tag: String,
+    pub roh_global_ceiling: f64,
+    pub neurorights_profile_id: String,
+    pub rights_kernel_profile_id: String,
+    pub eibon_continuity_profile_id: String,
+    pub created_at: DateTime<Utc>,
+    pub non_financial: bool,
+    pub financialization_allowed: bool,
+}
+
+// ---- HCI_NEURORIGHTS_AND_MODES ----
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NeurorightsAndModes {
+    #[serde(flatten)]
+    pub meta: ShardMeta,
+    pub did: String,
+    pub neurorights: NeurorightsFlags,
+    pub modes: ModeTable,
+    pub consent: ConsentRules,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NeurorightsFlags {
+    pub mental_privacy: bool,
+    pub mental_integrity: bool,
+    pub cognitive_liberty: bool,
+    pub noscorefrominnerstate: bool,
+    pub noneurocoercion: bool,
+    pub revocable: bool,
+    pub retrieval_only_default: bool,
+    pub soul_fields_allowed: bool,
+    pub consciousness_labels_allowed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModeTable {
+    pub available: Vec<String>,
+    pub default_mode: String,
+    pub mode_envelopes: BTreeMap<String, ModeEnvelope>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModeEnvelope {
+    pub max_intensity: f64,
+    pub max_duty_cycle: f64,
+    pub max_cumulative_load: f64,
+    pub max_psych_risk: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConsentRules {
+    pub required_for: Vec<String>,
+    pub properties: ConsentProperties,
+    pub revocation_effect: RevocationEffect,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConsentProperties {
+    pub informed: bool,
+    pub specific: bool,
+    pub time_bounded: bool,
+    pub revocable: bool,
+    pub max_revocation_latency_sec: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RevocationEffect {
+    pub forced_mode: String,
+    pub block_high_risk_actions: bool,
+}
+
+// ---- HCI_RISK_AND_BUDGETS ----
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RiskAndBudgets {
+    #[serde(flatten)]
+    pub meta: ShardMeta,
+    pub roh: RohLimits,
+    pub neu_budget: NeuBudget,
+    pub envelopes: SafetyEnvelopes,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RohLimits {
+    pub global_ceiling: f64,
+    pub governance_ceiling: f64,
+    pub personhood_ceiling: f64,
+    pub lyapunov_non_increase: bool,
+    pub roh_components: Vec<String>,
+    #[serde(default)]
+    pub roh_formula_hint: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NeuBudget {
+    pub token_name: String,
+    pub non_transferable: bool,
+    pub delegation_allowed: bool,
+    pub pooling_allowed: bool,
+    pub mint_rules: Vec<String>,
+    pub burn_on: Vec<String>,
+    pub episode_risk_field: String,
+    pub max_daily_burn_fraction: f64,
+    pub max_consecutive_high_risk_episodes: u32,
+    #[serde(default)]
+    pub blood_token_mint_forbidden: Vec<String>,
+    #[serde(default)]
+    pub ledger_invariants: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SafetyEnvelopes {
+    pub safety_envelope_layers: Vec<String>,
+    pub physics_gate_required: bool,
+    pub bio_safety_gate_required: bool,
+    pub mode_kernel_gate_required: bool,
+    pub failure_policy: FailurePolicy,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FailurePolicy {
+    pub on_gate_fail: String,
+    pub audit_log_required: bool,
+}
+
+// ---- HCI_RIGHTS_KERNEL_AND_DOWNGRADE_RESISTANCE ----
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RightsKernelAndDowngradeResistance {
+    #[serde(flatten)]
+    pub meta: ShardMeta,
+    pub rights_kernel: RightsKernel,
+    pub eibon_continuity: EibonContinuity,
+    pub rollback_playbooks: RollbackPlaybooks,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RightsKernel {
+    pub profile_id: String,
+    pub min_rollback_strength: f64,
+    pub min_observability: f64,
+    pub max_psych_risk: f64,
+    pub max_phys_risk: f64,
+    pub max_eco_stress: f64,
+    pub max_burden_without_explicit_consent: f64,
+    pub modes: Vec<String>,
+    pub touches_rights_flag_required: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EibonContinuity {
+    pub shard_id: String,
+    pub downgrade_resistance_enabled: bool,
+    pub neurorights_monotonicity_enabled: bool,
+    pub log_all_rights_narrowing_attempts: bool,
+    pub log_plane: String,
+    pub forbid_policy_only_rollback: bool,
+    pub allow_safety_rollback_only_with: SafetyRollbackGate,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SafetyRollbackGate {
+    pub physics_veto: Vec<String>,
+    pub host_visible_audit: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RollbackPlaybooks {
+    pub safety_playbook_id: String,
+    pub pqc_audit_shard_id: String,
+}
+
+// ---- HCI_AUDIT_AND_NEURAL_ROPE ----
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditAndNeuralRope {
+    #[serde(flatten)]
+    pub meta: ShardMeta,
+    pub neural_rope: NeuralRope,
+    pub audit: AuditView,
+    pub violation_response: ViolationResponse,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NeuralRope {
+    pub required: bool,
+    pub rope_plane: String,
+    pub rope_step_schema: String,
+    pub fields: Vec<String>,
+    pub append_only: bool,
+    pub retrieval_only: bool,
+    pub inner_state_exposure_forbidden: bool,
+    pub missing_rope_step_is_violation: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditView {
+    pub exposed_interfaces: Vec<String>,
+    pub auditor_roles: Vec<String>,
+    pub proof_targets: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ViolationResponse {
+    pub on_violation_mode: String,
+    pub halt_high_risk_functions: bool,
+    pub preserve_all_logs: bool,
+    pub user_notice_required: bool,
+    pub user_notice_fields: Vec<String>,
+    pub reconsent_required_after_fix: bool,
+}
+
+// ---- HCI_DATA_RIGHTS_AND_KO_EXPORT ----
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DataRightsAndKoExport {
+    #[serde(flatten)]
+    pub meta: ShardMeta,
+    pub raw_data_policy: RawDataPolicy,
+    pub derivative_ko_policy: DerivativeKoPolicy,
+    pub ecology_and_jurisdiction: EcologyAndJurisdiction,
+    pub prohibited_practices: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RawDataPolicy {
+    pub raw_biophysical_data_host_bound: bool,
+    pub governing_shards: Vec<String>,
+    pub raw_export_forbidden: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DerivativeKoPolicy {
+    pub ko_only_export: bool,
+    pub ko_wrapper_schema: String,
+    pub required_fields: Vec<String>,
+    pub no_reconstruction_of_raw_state: bool,
+    pub neurorights_and_roh_must_hold: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EcologyAndJurisdiction {
+    pub eco_metrics_required: Vec<String>,
+    pub reject_if_eco_degrades_beyond_threshold: bool,
+    pub phoenix_ladder: Vec<String>,
+    pub strictest_wins: bool,
+    pub legal_constraints_may_tighten_never_weaken_rights: bool,
+}
+
+// ---- merged licence ----
+
+#[derive(thiserror::Error, Debug)]
+pub enum LicenceError {
+    #[error("I/O error while reading licence snapshot: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("line {line}: {source}")]
+    Parse {
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("line {line}: schema '{found}', expected '{LICENCE_SCHEMA}'")]
+    Schema { line: usize, found: String },
+    #[error("line {line}: version '{found}', expected '{LICENCE_VERSION}'")]
+    Version { line: usize, found: String },
+    #[error("line {line}: second {kind} shard (first on line {first})")]
+    DuplicateKind {
+        kind: &'static str,
+        line: usize,
+        first: usize,
+    },
+    #[error("snapshot has no {0} shard")]
+    MissingKind(&'static str),
+    #[error("line {line}: hostid {found} does not match header hostid {expected}")]
+    HostMismatch {
+        line: usize,
+        expected: String,
+        found: String,
+    },
+    #[error("licence must be non-financial: {0}")]
+    Financialization(String),
+    #[error("licence is internally inconsistent: {0}")]
+    Inconsistent(String),
+}
+
+/// The six shards of one snapshot, validated and merged.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HciLicence {
+    pub header: LicenseHeader,
+    pub neurorights_and_modes: NeurorightsAndModes,
+    pub risk_and_budgets: RiskAndBudgets,
+    pub rights_kernel: RightsKernelAndDowngradeResistance,
+    pub audit_and_neural_rope: AuditAndNeuralRope,
+    pub data_rights: DataRightsAndKoExport,
+}
+
+impl HciLicence {
+    pub fn load(path: &Path) -> Result<Self, LicenceError> {
+        Self::from_ndjson_str(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn from_ndjson_str(text: &str) -> Result<Self, LicenceError> {
+        let mut slots: [Option<(usize, HciShard)>; 6] = Default::default();
+        for (idx, raw) in text.lines().enumerate() {
+            let line = idx + 1;
+            if raw.trim().is_empty() {
+                continue;
+            }
+            let shard: HciShard =
+                serde_json::from_str(raw).map_err(|source| LicenceError::Parse { line, source })?;
+            let meta = shard.meta();
+            if meta.schema != LICENCE_SCHEMA {
+                return Err(LicenceError::Schema {
+                    line,
+                    found: meta.schema.clone(),
+                });
+            }
+            if meta.version != LICENCE_VERSION {
+                return Err(LicenceError::Version {
+                    line,
+                    found: meta.version.clone(),
+                });
+            }
+            let slot = &mut slots[shard.kind_index()];
+            if let Some((first, _)) = slot {
+                return Err(LicenceError::DuplicateKind {
+                    kind: shard.kind(),
+                    line,
+                    first: *first,
+                });
+            }
+            *slot = Some((line, shard));
+        }
+
+        let header_line = match &slots[0] {
+            Some((line, _)) => *line,
+            None => return Err(LicenceError::MissingKind(HciShard::KINDS[0])),
+        };
+        let hostid = slots[0].as_ref().map(|(_, s)| s.meta().hostid.clone()).unwrap_or_default();
+        for (kind, slot) in HciShard::KINDS.iter().zip(&slots) {
+            let (line, shard) = slot.as_ref().ok_or(LicenceError::MissingKind(*kind))?;
+            if shard.meta().hostid != hostid {
+                return Err(LicenceError::HostMismatch {
+                    line: *line,
+                    expected: hostid.clone(),
+                    found: shard.meta().hostid.clone(),
+                });
+            }
+        }
+
+        let [header, modes, risk, kernel, audit, data] = slots.map(|s| s.map(|(_, shard)| shard));
+        let licence = match (header, modes, risk, kernel, audit, data) {
+            (
+                Some(HciShard::LicenseHeader(header)),
+                Some(HciShard::NeurorightsAndModes(neurorights_and_modes)),
+                Some(HciShard::RiskAndBudgets(risk_and_budgets)),
+                Some(HciShard::RightsKernelAndDowngradeResistance(rights_kernel)),
+                Some(HciShard::AuditAndNeuralRope(audit_and_neural_rope)),
+                Some(HciShard::DataRightsAndKoExport(data_rights)),
+            ) => HciLicence {
+                header,
+                neurorights_and_modes,
+                risk_and_budgets,
+                rights_kernel,
+                audit_and_neural_rope,
+                data_rights,
+            },
+            _ => unreachable!("slots are indexed by kind_index"),
+        };
+        licence.validate(header_line)?;
+        Ok(licence)
+    }
+
+    fn validate(&self, header_line: usize) -> Result<(), LicenceError> {
+        let h = &self.header;
+        if !h.non_financial {
+            return Err(LicenceError::Financialization(format!(
+                "line {header_line}: non_financial is false"
+            )));
+        }
+        if h.financialization_allowed {
+            return Err(LicenceError::Financialization(format!(
+                "line {header_line}: financialization_allowed is true"
+            )));
+        }
+
+        let expected_did = format!("did:aln:{}", h.meta.hostid);
+        for did in [&h.did, &self.neurorights_and_modes.did] {
+            if *did != expected_did {
+                return Err(LicenceError::Inconsistent(format!(
+                    "did {did} is not bound to hostid {}",
+                    h.meta.hostid
+                )));
+            }
+        }
+        if h.roh_global_ceiling != self.risk_and_budgets.roh.global_ceiling {
+            return Err(LicenceError::Inconsistent(format!(
+                "header roh_global_ceiling {} != roh.global_ceiling {}",
+                h.roh_global_ceiling, self.risk_and_budgets.roh.global_ceiling
+            )));
+        }
+        if h.rights_kernel_profile_id != self.rights_kernel.rights_kernel.profile_id {
+            return Err(LicenceError::Inconsistent(format!(
+                "header rights_kernel_profile_id {} != rights_kernel.profile_id {}",
+                h.rights_kernel_profile_id, self.rights_kernel.rights_kernel.profile_id
+            )));
+        }
+        if h.eibon_continuity_profile_id != self.rights_kernel.eibon_continuity.shard_id {
+            return Err(LicenceError::Inconsistent(format!(
+                "header eibon_continuity_profile_id {} != eibon_continuity.shard_id {}",
+                h.eibon_continuity_profile_id, self.rights_kernel.eibon_continuity.shard_id
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn hostid(&self) -> &str {
+        &self.header.meta.hostid
+    }
+
+    pub fn roh_global_ceiling(&self) -> f64 {
+        self.header.roh_global_ceiling
+    }
+
+    pub fn jurisdiction_ladder(&self) -> &[String] {
+        &self.header.jurisdiction_ladder
+    }
+
+    pub fn mode_envelope(&self, mode: &str) -> Option<&ModeEnvelope> {
+        self.neurorights_and_modes.modes.mode_envelopes.get(mode)
+    }
+}
@@ -1,3 +1,32 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+pub mod calibrate;
+
+/// Tolerance used when checking `weightssumleq 1.0` on f32 weights.
+const WEIGHT_SUM_EPSILON: f32 = 1e-4;
+
+/// One RoH axis as declared in the `axes` section of a `.rohmodel.aln` shard.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RohAxis {
+    pub name: String,
+    pub min: f32,
+    pub max: f32,
+    pub weight: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RohWeights {
+    pub axes: Vec<RohAxis>,
+}
+
+impl RohWeights {
+    pub fn sum(&self) -> f32 {
+        self.axes.iter().map(|a| a.weight).sum()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RohBands {
     pub rohceiling_strict: f32,
@@ -12,6 +41,21 @@ pub struct RohModelCore {
     pub notes: Option<String>,
 }
 
+/// Raw per-axis observations fed into `RohModelShard::compute_roh`.
+/// Axes missing from `values` contribute zero.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RohInputs {
+    pub values: BTreeMap<String, f32>,
+}
+
+/// A parsed `.rohmodel.aln` shard (meta + model core).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RohModelShard {
+    pub subjectid: String,
+    pub version: String,
+    pub model: RohModelCore,
+}
+
 impl RohModelShard {
     pub fn roh_ceiling_strict(&self) -> f32 {
         self.model.bands.rohceiling_strict
@@ -19,8 +63,39 @@ impl RohModelShard {
     pub fn roh_ceiling_research(&self) -> f32 {
         self.model.bands.rohceiling_research
     }
+    /// Weighted RoH score in [0, 1]; each axis is normalized by its min/max first.
+    pub fn compute_roh(&self, inputs: &RohInputs) -> f32 {
+        let score: f32 = self
+            .model
+            .weights
+            .axes
+            .iter()
+            .map(|a| {
+                let raw = inputs.values.get(&a.name).copied().unwrap_or(a.min);
+                a.weight * ((raw - a.min) / (a.max - a.min)).clamp(0.0, 1.0)
+            })
+            .sum();
+        score.clamp(0.0, 1.0)
+    }
     pub fn validate_invariants(&self) -> Result<(), String> {
-        // existing weight checks…
+        for axis in &self.model.weights.axes {
+            if !(axis.min.is_finite() && axis.max.is_finite() && axis.weight.is_finite()) {
+                return Err(format!("axis {} has a non-finite value", axis.name));
+            }
+            if axis.weight < 0.0 {
+                return Err(format!("axis {} has negative weight", axis.name));
+            }
+            if axis.max <= axis.min {
+                return Err(format!("axis {} has max <= min", axis.name));
+            }
+        }
+        let bands = &self.model.bands;
+        if !(bands.rohceiling_strict.is_finite() && bands.rohceiling_research.is_finite()) {
+            return Err("RoH ceiling is not finite".into());
+        }
+        if self.model.weights.sum() > 1.0 + WEIGHT_SUM_EPSILON {
+            return Err("weights sum > 1.0".into());
+        }
         if self.model.bands.rohceiling_research < self.model.bands.rohceiling_strict {
             return Err("research ceiling < strict ceiling".into());
         }
@@ -29,4 +104,121 @@ impl RohModelShard {
         }
         Ok(())
     }
+
+    /// Parse the line-oriented ALN form used by `qpudatashards/particles/*.rohmodel.aln`.
+    pub fn from_aln_str(text: &str) -> Result<Self, String> {
+        let mut section = "";
+        let mut subjectid = None;
+        let mut modelid = None;
+        let mut version = None;
+        let mut description = None;
+        let mut axes = Vec::new();
+        let mut strict = None;
+        let mut research = None;
+
+        for (idx, raw) in text.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line == "aln" {
+                continue;
+            }
+            if !raw.starts_with(' ') {
+                section = line;
+                continue;
+            }
+            let (key, value) = match line.split_once(char::is_whitespace) {
+                Some((k, v)) => (k, v.trim()),
+                None => (line, ""),
+            };
+            match (section, key) {
+                ("meta", "subjectid") => subjectid = Some(value.to_string()),
+                ("meta", "modelid") => modelid = Some(value.to_string()),
+                ("meta", "version") => version = Some(value.to_string()),
+                ("meta", "description") => description = Some(value.to_string()),
+                ("axes", "-") => axes.push(parse_axis_line(value, idx + 1)?),
+                ("bands", "rohceiling_strict") => strict = Some(parse_f32(value, idx + 1)?),
+                ("bands", "rohceiling_research") => research = Some(parse_f32(value, idx + 1)?),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            subjectid: subjectid.ok_or("meta.subjectid missing")?,
+            version: version.ok_or("meta.version missing")?,
+            model: RohModelCore {
+                id: modelid.ok_or("meta.modelid missing")?,
+                weights: RohWeights { axes },
+                bands: RohBands {
+                    rohceiling_strict: strict.ok_or("bands.rohceiling_strict missing")?,
+                    rohceiling_research: research.ok_or("bands.rohceiling_research missing")?,
+                },
+                notes: description,
+            },
+        })
+    }
+
+    /// Render this shard back into the ALN layout it was parsed from.
+    /// Numbers use shortest round-trip formatting, so `from_aln_str` on the
+    /// output yields the same `f32` values.
+    pub fn to_aln_string(&self) -> String {
+        let mut out = String::from("aln\nmeta\n");
+        out.push_str(&format!("  subjectid {}\n", self.subjectid));
+        out.push_str(&format!("  modelid   {}\n", self.model.id));
+        out.push_str(&format!("  version   {}\n", self.version));
+        if let Some(notes) = &self.model.notes {
+            out.push_str(&format!("  description {}\n", notes));
+        }
+        out.push_str("\naxes\n");
+        for a in &self.model.weights.axes {
+            out.push_str(&format!(
+                "  - name {:<15} min {} max {} weight {}\n",
+                a.name, a.min, a.max, a.weight
+            ));
+        }
+        out.push_str("\nbands\n");
+        out.push_str(&format!(
+            "  rohceiling_strict   {}\n",
+            self.model.bands.rohceiling_strict
+        ));
+        out.push_str(&format!(
+            "  rohceiling_research {}\n",
+            self.model.bands.rohceiling_research
+        ));
+        out.push_str("\ninvariants\n");
+        out.push_str("  weightsnonnegative true\n");
+        out.push_str("  weightssumleq      1.0\n");
+        out.push_str("  rohceiling_research_leq 0.45\n");
+        out
+    }
+}
+
+fn parse_axis_line(value: &str, line: usize) -> Result<RohAxis, String> {
+    let tokens: Vec<&str> = value.split_whitespace().collect();
+    let mut name = None;
+    let mut min = None;
+    let mut max = None;
+    let mut weight = None;
+    for pair in tokens.chunks(2) {
+        match pair {
+            ["name", v] => name = Some(v.to_string()),
+            ["min", v] => min = Some(parse_f32(v, line)?),
+            ["max", v] => max = Some(parse_f32(v, line)?),
+            ["weight", v] => weight = Some(parse_f32(v, line)?),
+            _ => return Err(format!("line {line}: malformed axis entry")),
+        }
+    }
+    Ok(RohAxis {
+        name: name.ok_or(format!("line {line}: axis name missing"))?,
+        min: min.ok_or(format!("line {line}: axis min missing"))?,
+        max: max.ok_or(format!("line {line}: axis max missing"))?,
+        weight: weight.ok_or(format!("line {line}: axis weight missing"))?,
+    })
+}
+
+/// Finite numbers only: `NaN`, `inf` and out-of-range literals are rejected.
+fn parse_f32(value: &str, line: usize) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("line {line}: expected number, found {value:?}"))
 }
@@ -0,0 +1,266 @@
+//! Offline RoH weight calibration from a labeled, host-local dataset.
+//!
+//! Fits non-negative axis weights with `sum(weights) <= 1.0` by projected
+//! gradient descent on mean squared error, then emits a *candidate*
+//! `RohModelShard` for human review. Nothing here writes to live policy:
+//! the caller decides whether the candidate is ever promoted.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{RohAxis, RohModelShard, RohWeights};
+
+/// Column / key that carries the outcome label in calibration rows.
+pub const LABEL_KEY: &str = "label";
+
+/// One labeled calibration row: raw axis values plus the observed outcome.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CalibrationSample {
+    pub axes: BTreeMap<String, f32>,
+    pub label: f32,
+}
+
+/// Solver knobs. Defaults are sized for a few thousand rows.
+#[derive(Clone, Debug)]
+pub struct CalibrationConfig {
+    pub max_iterations: usize,
+    pub tolerance: f32,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 5_000,
+            tolerance: 1e-7,
+        }
+    }
+}
+
+/// Fit quality and provenance of a calibration run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    pub samples: usize,
+    pub iterations: usize,
+    pub converged: bool,
+    pub rmse: f32,
+    pub r_squared: f32,
+    pub mean_label: f32,
+    pub mean_prediction: f32,
+    pub weight_sum: f32,
+    pub weights: BTreeMap<String, f32>,
+}
+
+/// Load calibration rows from a `.csv` (header row, `label` column) or
+/// `.ndjson` / `.jsonl` file (one flat object per line with a `label` key).
+pub fn load_samples(path: &Path) -> Result<Vec<CalibrationSample>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("csv") => parse_csv(&text),
+        Some("ndjson") | Some("jsonl") => parse_ndjson(&text),
+        other => Err(format!("unsupported calibration file extension {other:?}")),
+    }
+}
+
+pub fn parse_csv(text: &str) -> Result<Vec<CalibrationSample>, String> {
+    let mut lines = text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+    let (_, header) = lines.next().ok_or("calibration CSV is empty")?;
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    if !columns.contains(&LABEL_KEY) {
+        return Err(format!("calibration CSV has no '{LABEL_KEY}' column"));
+    }
+
+    let mut samples = Vec::new();
+    for (idx, line) in lines {
+        let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+        if cells.len() != columns.len() {
+            return Err(format!(
+                "line {}: expected {} cells, found {}",
+                idx + 1,
+                columns.len(),
+                cells.len()
+            ));
+        }
+        let mut axes = BTreeMap::new();
+        let mut label = 0.0;
+        for (col, cell) in columns.iter().zip(cells) {
+            let v = cell
+                .parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| format!("line {}: {col} is not a finite number", idx + 1))?;
+            if *col == LABEL_KEY {
+                label = v;
+            } else {
+                axes.insert(col.to_string(), v);
+            }
+        }
+        samples.push(CalibrationSample { axes, label });
+    }
+    Ok(samples)
+}
+
+pub fn parse_ndjson(text: &str) -> Result<Vec<CalibrationSample>, String> {
+    let mut samples = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut row: BTreeMap<String, f32> = serde_json::from_str(line)
+            .map_err(|e| format!("line {}: {e}", idx + 1))?;
+        // Out-of-range numbers narrow to an infinite f32.
+        if let Some((key, _)) = row.iter().find(|(_, v)| !v.is_finite()) {
+            return Err(format!("line {}: {key} is not a finite number", idx + 1));
+        }
+        let label = row
+            .remove(LABEL_KEY)
+            .ok_or(format!("line {}: missing '{LABEL_KEY}'", idx + 1))?;
+        samples.push(CalibrationSample { axes: row, label });
+    }
+    Ok(samples)
+}
+
+/// Fit weights for the axes declared in `base` against `samples`.
+///
+/// Axis values are normalized into [0, 1] with the shard's own min/max
+/// before fitting, so the fitted weights drop straight into the shard.
+pub fn fit_weights(
+    base: &RohModelShard,
+    samples: &[CalibrationSample],
+    config: &CalibrationConfig,
+) -> Result<CalibrationReport, String> {
+    let axes = &base.model.weights.axes;
+    if axes.is_empty() {
+        return Err("base model declares no axes".into());
+    }
+    if samples.is_empty() {
+        return Err("calibration dataset is empty".into());
+    }
+
+    let rows = normalize_rows(axes, samples)?;
+    let labels: Vec<f32> = samples.iter().map(|s| s.label).collect();
+    let n = rows.len() as f32;
+    let dims = axes.len();
+
+    // Step size from a Lipschitz bound on the MSE gradient: 2 * trace(XᵀX) / n.
+    let trace: f32 = rows.iter().flatten().map(|x| x * x).sum::<f32>() / n;
+    let step = if trace > 0.0 { 1.0 / (2.0 * trace) } else { 1.0 };
+
+    let mut w = project_capped_simplex(&vec![1.0 / dims as f32; dims]);
+    let mut iterations = 0;
+    let mut converged = false;
+
+    while iterations < config.max_iterations {
+        iterations += 1;
+        let mut grad = vec![0.0f32; dims];
+        for (x, y) in rows.iter().zip(&labels) {
+            let residual = dot(&w, x) - y;
+            for (g, xi) in grad.iter_mut().zip(x) {
+                *g += 2.0 * residual * xi / n;
+            }
+        }
+        let candidate: Vec<f32> = w.iter().zip(&grad).map(|(wi, gi)| wi - step * gi).collect();
+        let next = project_capped_simplex(&candidate);
+        let moved: f32 = next.iter().zip(&w).map(|(a, b)| (a - b).abs()).sum();
+        w = next;
+        if moved < config.tolerance {
+            converged = true;
+            break;
+        }
+    }
+
+    let predictions: Vec<f32> = rows.iter().map(|x| dot(&w, x)).collect();
+    let mean_label = labels.iter().sum::<f32>() / n;
+    let mean_prediction = predictions.iter().sum::<f32>() / n;
+    let ss_res: f32 = predictions.iter().zip(&labels).map(|(p, y)| (p - y).powi(2)).sum();
+    let ss_tot: f32 = labels.iter().map(|y| (y - mean_label).powi(2)).sum();
+
+    Ok(CalibrationReport {
+        samples: rows.len(),
+        iterations,
+        converged,
+        rmse: (ss_res / n).sqrt(),
+        r_squared: if ss_tot > 0.0 { 1.0 - ss_res / ss_tot } else { 0.0 },
+        mean_label,
+        mean_prediction,
+        weight_sum: w.iter().sum(),
+        weights: axes.iter().map(|a| a.name.clone()).zip(w).collect(),
+    })
+}
+
+/// Build a candidate shard from `base` with the fitted weights swapped in.
+/// The candidate must pass `validate_invariants` or it is not returned.
+pub fn candidate_shard(
+    base: &RohModelShard,
+    report: &CalibrationReport,
+    model_id: &str,
+    version: &str,
+    dataset_label: &str,
+) -> Result<RohModelShard, String> {
+    let axes = base
+        .model
+        .weights
+        .axes
+        .iter()
+        .map(|a| RohAxis {
+            weight: report.weights.get(&a.name).copied().unwrap_or(0.0),
+            ..a.clone()
+        })
+        .collect();
+
+    let mut shard = base.clone();
+    shard.version = version.to_string();
+    shard.model.id = model_id.to_string();
+    shard.model.weights = RohWeights { axes };
+    shard.model.notes = Some(format!(
+        "CANDIDATE calibrated from {dataset_label} (n={}, rmse={:.4}, r2={:.3}); requires human review.",
+        report.samples, report.rmse, report.r_squared
+    ));
+    shard.validate_invariants()?;
+    Ok(shard)
+}
+
+fn normalize_rows(axes: &[RohAxis], samples: &[CalibrationSample]) -> Result<Vec<Vec<f32>>, String> {
+    samples
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            axes.iter()
+                .map(|a| {
+                    let raw = s
+                        .axes
+                        .get(&a.name)
+                        .ok_or(format!("sample {i}: missing axis {}", a.name))?;
+                    Ok(((raw - a.min) / (a.max - a.min)).clamp(0.0, 1.0))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Euclidean projection onto `{ w >= 0, sum(w) <= 1 }`.
+fn project_capped_simplex(v: &[f32]) -> Vec<f32> {
+    let clipped: Vec<f32> = v.iter().map(|x| x.max(0.0)).collect();
+    if clipped.iter().sum::<f32>() <= 1.0 {
+        return clipped;
+    }
+    // Project onto the probability simplex (sum == 1).
+    let mut sorted = v.to_vec();
+    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    let mut cumulative = 0.0;
+    let mut theta = 0.0;
+    for (i, u) in sorted.iter().enumerate() {
+        cumulative += u;
+        let t = (cumulative - 1.0) / (i as f32 + 1.0);
+        if *u - t > 0.0 {
+            theta = t;
+        }
+    }
+    v.iter().map(|x| (x - theta).max(0.0)).collect()
+}
@@ -0,0 +1,177 @@
+//! Parser round-trip and calibration fitter checks for `.rohmodel.aln` shards.
+
+use std::collections::BTreeMap;
+
+use organiccpualn::rohmodel::calibrate::{
+    candidate_shard, fit_weights, parse_csv, parse_ndjson, CalibrationConfig, CalibrationSample,
+};
+use organiccpualn::rohmodel::RohModelShard;
+
+const SHARD: &str = include_str!("../../../qpudatashards/particles/bostrom-rohmodel-v2.rohmodel.aln");
+
+fn shard() -> RohModelShard {
+    RohModelShard::from_aln_str(SHARD).expect("checked-in shard parses")
+}
+
+#[test]
+fn parses_checked_in_shard() {
+    let shard = shard();
+    assert_eq!(shard.model.id, "bostrom-rohmodel-v2");
+    assert_eq!(shard.version, "2.0.0");
+    assert_eq!(shard.model.weights.axes.len(), 7);
+    assert_eq!(shard.model.weights.axes[1].name, "cognitiveload");
+    assert_eq!(shard.model.weights.axes[1].weight, 0.20);
+    assert_eq!(shard.roh_ceiling_strict(), 0.30);
+    assert_eq!(shard.roh_ceiling_research(), 0.45);
+    shard.validate_invariants().unwrap();
+}
+
+#[test]
+fn reports_missing_and_malformed_fields() {
+    let no_version = SHARD.replace("  version   2.0.0\n", "");
+    assert_eq!(
+        RohModelShard::from_aln_str(&no_version).unwrap_err(),
+        "meta.version missing"
+    );
+
+    let bad_weight = SHARD.replace("weight 0.15\n  - name cognitiveload", "weight x\n  - name cognitiveload");
+    let err = RohModelShard::from_aln_str(&bad_weight).unwrap_err();
+    assert!(err.starts_with("line 9: expected number"), "{err}");
+
+    let no_name = SHARD.replace("name thermalload     ", "");
+    assert_eq!(
+        RohModelShard::from_aln_str(&no_name).unwrap_err(),
+        "line 9: axis name missing"
+    );
+}
+
+#[test]
+fn to_aln_string_round_trips_exact_values() {
+    let mut shard = shard();
+    shard.model.weights.axes[0].min = 0.05;
+    shard.model.weights.axes[0].max = 1.25;
+    shard.model.weights.axes[0].weight = 0.123_456_79;
+    shard.model.bands.rohceiling_strict = 0.312_5;
+
+    let reparsed = RohModelShard::from_aln_str(&shard.to_aln_string()).unwrap();
+    for (a, b) in shard.model.weights.axes.iter().zip(&reparsed.model.weights.axes) {
+        assert_eq!(a.name, b.name);
+        assert_eq!(a.min.to_bits(), b.min.to_bits());
+        assert_eq!(a.max.to_bits(), b.max.to_bits());
+        assert_eq!(a.weight.to_bits(), b.weight.to_bits());
+    }
+    assert_eq!(reparsed.roh_ceiling_strict(), 0.312_5);
+    assert_eq!(reparsed.model.notes, shard.model.notes);
+    reparsed.validate_invariants().unwrap();
+}
+
+#[test]
+fn validate_rejects_weight_sum_and_band_violations() {
+    let mut heavy = shard();
+    heavy.model.weights.axes[0].weight = 0.5;
+    assert_eq!(heavy.validate_invariants().unwrap_err(), "weights sum > 1.0");
+
+    let mut wide = shard();
+    wide.model.bands.rohceiling_research = 0.5;
+    assert_eq!(wide.validate_invariants().unwrap_err(), "research ceiling > 0.45");
+}
+
+#[test]
+fn rejects_non_finite_numbers() {
+    for bad in ["NaN", "inf", "-inf", "1e39"] {
+        let text = SHARD.replace("weight 0.15\n  - name cognitiveload", &format!("weight {bad}\n  - name cognitiveload"));
+        let err = RohModelShard::from_aln_str(&text).unwrap_err();
+        assert!(err.starts_with("line 9: expected number"), "{bad}: {err}");
+
+        let text = SHARD.replace("rohceiling_strict   0.30", &format!("rohceiling_strict   {bad}"));
+        assert!(RohModelShard::from_aln_str(&text).is_err(), "{bad}");
+
+        let err = parse_csv(&format!("thermalload,label\n{bad},0.1\n")).unwrap_err();
+        assert_eq!(err, "line 2: thermalload is not a finite number");
+    }
+    let err = parse_ndjson("{\"thermalload\": 1e39, \"label\": 0.1}\n").unwrap_err();
+    assert_eq!(err, "line 1: thermalload is not a finite number");
+
+    let mut nan_weight = shard();
+    nan_weight.model.weights.axes[0].weight = f32::NAN;
+    assert_eq!(
+        nan_weight.validate_invariants().unwrap_err(),
+        "axis thermalload has a non-finite value"
+    );
+    let mut nan_ceiling = shard();
+    nan_ceiling.model.bands.rohceiling_research = f32::NAN;
+    assert_eq!(nan_ceiling.validate_invariants().unwrap_err(), "RoH ceiling is not finite");
+}
+
+/// Deterministic rows whose label is an exact weighted sum of the axes.
+fn synthetic_samples(shard: &RohModelShard, truth: &[f32]) -> Vec<CalibrationSample> {
+    (0..200u32)
+        .map(|i| {
+            let axes: BTreeMap<String, f32> = shard
+                .model
+                .weights
+                .axes
+                .iter()
+                .enumerate()
+                .map(|(k, a)| {
+                    let v = ((i * (2 * k as u32 + 3) + k as u32 * 7) % 97) as f32 / 96.0;
+                    (a.name.clone(), v)
+                })
+                .collect();
+            let label = shard
+                .model
+                .weights
+                .axes
+                .iter()
+                .zip(truth)
+                .map(|(a, w)| w * axes[&a.name])
+                .sum();
+            CalibrationSample { axes, label }
+        })
+        .collect()
+}
+
+#[test]
+fn fitter_recovers_weights_inside_the_feasible_set() {
+    let base = shard();
+    let truth = [0.10, 0.25, 0.15, 0.0, 0.05, 0.10, 0.20];
+    let samples = synthetic_samples(&base, &truth);
+    let config = CalibrationConfig {
+        max_iterations: 50_000,
+        ..CalibrationConfig::default()
+    };
+
+    let report = fit_weights(&base, &samples, &config).unwrap();
+    assert!(report.rmse < 1e-3, "rmse {}", report.rmse);
+    for (axis, want) in base.model.weights.axes.iter().zip(truth) {
+        let got = report.weights[&axis.name];
+        assert!((got - want).abs() < 1e-2, "{}: {got} vs {want}", axis.name);
+    }
+
+    let candidate = candidate_shard(&base, &report, "rohmodel-test", "0.0.1", "synthetic").unwrap();
+    RohModelShard::from_aln_str(&candidate.to_aln_string())
+        .unwrap()
+        .validate_invariants()
+        .unwrap();
+}
+
+#[test]
+fn fitter_projects_onto_capped_simplex() {
+    // Labels need total weight 2.0; the fit must stay non-negative with sum <= 1.
+    let base = shard();
+    let samples = synthetic_samples(&base, &[0.5, 0.5, 0.2, 0.2, 0.2, 0.2, 0.2]);
+    let report = fit_weights(&base, &samples, &CalibrationConfig::default()).unwrap();
+    assert!(report.weights.values().all(|w| *w >= 0.0));
+    assert!(report.weight_sum <= 1.0 + 1e-4, "sum {}", report.weight_sum);
+}
+
+#[test]
+fn fitter_rejects_missing_axes_and_empty_data() {
+    let base = shard();
+    let err = fit_weights(&base, &[], &CalibrationConfig::default()).unwrap_err();
+    assert_eq!(err, "calibration dataset is empty");
+
+    let rows = parse_csv("thermalload,label\n0.5,0.1\n").unwrap();
+    let err = fit_weights(&base, &rows, &CalibrationConfig::default()).unwrap_err();
+    assert_eq!(err, "sample 0: missing axis cognitiveload");
+}
@@ -0,0 +1,186 @@
+//! Cross-document checks over an `Inventory`. Each finding names the
+//! offending location and the location(s) it conflicts with.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use propose_only::reference_terminal::REQUIRED_EVIDENCE_TAGS;
+
+use crate::inventory::{CeilingSource, Inventory, Location};
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Finding {
+    pub check: &'static str,
+    pub at: Location,
+    pub message: String,
+    pub related: Vec<Location>,
+}
+
+pub fn run_all(inv: &Inventory) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    load_errors(inv, &mut findings);
+    host_dids(inv, &mut findings);
+    roh_ceilings(inv, &mut findings);
+    evidence_tags(inv, &mut findings);
+    profile_ids(inv, &mut findings);
+    findings.sort_by(|a, b| a.at.cmp(&b.at));
+    findings
+}
+
+fn load_errors(inv: &Inventory, out: &mut Vec<Finding>) {
+    for e in &inv.load_errors {
+        out.push(Finding {
+            check: "load",
+            at: e.at.clone(),
+            message: format!("{}: {}", e.field, e.value),
+            related: Vec::new(),
+        });
+    }
+}
+
+/// Every host-identity field must name the same DID. The reference is the
+/// HCI `hostid` when present (the licence is the outer contract), otherwise
+/// the most common value.
+fn host_dids(inv: &Inventory, out: &mut Vec<Finding>) {
+    let reference = inv
+        .dids
+        .iter()
+        .find(|d| d.field == "hostid")
+        .or_else(|| {
+            let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+            for d in &inv.dids {
+                *counts.entry(d.value.as_str()).or_default() += 1;
+            }
+            let (top, _) = counts.into_iter().max_by_key(|(_, n)| *n)?;
+            inv.dids.iter().find(|d| d.value == top)
+        });
+    let Some(reference) = reference else {
+        return;
+    };
+    for d in inv.dids.iter().filter(|d| d.value != reference.value) {
+        out.push(Finding {
+            check: "did",
+            at: d.at.clone(),
+            message: format!(
+                "{} is {}, but host DID is {}",
+                d.field, d.value, reference.value
+            ),
+            related: vec![reference.at.clone()],
+        });
+    }
+}
+
+/// Licence global ceilings must agree; every other ceiling must sit at or
+/// below the tightest of them. A research-band ceiling above the licence is
+/// reported once, at the ceiling, with the tokens that unlock it as related
+/// locations: the rohmodel invariants allow it, the licence contract does not.
+fn roh_ceilings(inv: &Inventory, out: &mut Vec<Finding>) {
+    let global: Vec<_> = inv
+        .ceilings
+        .iter()
+        .filter(|c| c.value.0 == CeilingSource::LicenceGlobal)
+        .collect();
+    let Some(tightest) = global
+        .iter()
+        .min_by(|a, b| a.value.1.total_cmp(&b.value.1))
+        .copied()
+    else {
+        return;
+    };
+    let limit = tightest.value.1;
+
+    for g in global.iter().filter(|g| g.value.1 != limit) {
+        out.push(Finding {
+            check: "roh-ceiling",
+            at: g.at.clone(),
+            message: format!("licence {} is {:.2}, elsewhere {:.2}", g.field, g.value.1, limit),
+            related: vec![tightest.at.clone()],
+        });
+    }
+    for c in inv
+        .ceilings
+        .iter()
+        .filter(|c| c.value.0 != CeilingSource::LicenceGlobal && c.value.1 > limit + 1e-9)
+    {
+        let mut message = format!(
+            "{} {:.2} exceeds licence global ceiling {:.2}",
+            c.field, c.value.1, limit
+        );
+        let mut related = vec![tightest.at.clone()];
+        if c.value.0 == CeilingSource::ModelResearch && !inv.research_tokens.is_empty() {
+            let ids: Vec<&str> = inv.research_tokens.iter().map(|t| t.value.as_str()).collect();
+            message.push_str(&format!("; unlocked by token {}", ids.join(", ")));
+            related.extend(inv.research_tokens.iter().map(|t| t.at.clone()));
+        }
+        out.push(Finding {
+            check: "roh-ceiling",
+            at: c.at.clone(),
+            message,
+            related,
+        });
+    }
+}
+
+/// Every evidence block must carry exactly the 10-tag registry.
+fn evidence_tags(inv: &Inventory, out: &mut Vec<Finding>) {
+    for block in &inv.evidence {
+        let mut seen: BTreeMap<&str, &Location> = BTreeMap::new();
+        for tag in &block.tags {
+            if let Some(first) = seen.insert(tag.value.as_str(), &tag.at) {
+                out.push(Finding {
+                    check: "evidence",
+                    at: tag.at.clone(),
+                    message: format!("duplicate evidence tag {}", tag.value),
+                    related: vec![first.clone()],
+                });
+            }
+            if !REQUIRED_EVIDENCE_TAGS.contains(&tag.value.as_str()) {
+                out.push(Finding {
+                    check: "evidence",
+                    at: tag.at.clone(),
+                    message: format!("evidence tag {} is not in the registry", tag.value),
+                    related: Vec::new(),
+                });
+            }
+        }
+        let missing: Vec<&str> = REQUIRED_EVIDENCE_TAGS
+            .iter()
+            .copied()
+            .filter(|t| !seen.contains_key(t))
+            .collect();
+        if !missing.is_empty() {
+            out.push(Finding {
+                check: "evidence",
+                at: block.header.clone(),
+                message: format!("evidence block is missing {}", missing.join(", ")),
+                related: Vec::new(),
+            });
+        }
+    }
+}
+
+/// Each profile slot (neurorights, rights kernel, eibon continuity) must
+/// resolve to one id across all artifacts; the first occurrence wins.
+fn profile_ids(inv: &Inventory, out: &mut Vec<Finding>) {
+    let mut first = BTreeMap::new();
+    for p in &inv.profiles {
+        let (slot, id) = &p.value;
+        let key = format!("{slot:?}");
+        match first.get(&key) {
+            None => {
+                first.insert(key, p);
+            }
+            Some(reference) if reference.value.1 != *id => out.push(Finding {
+                check: "profile",
+                at: p.at.clone(),
+                message: format!(
+                    "{} is {}, but {} is {}",
+                    p.field, id, reference.field, reference.value.1
+                ),
+                related: vec![reference.at.clone()],
+            }),
+            Some(_) => {}
+        }
+    }
+}
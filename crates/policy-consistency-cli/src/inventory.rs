@@ -0,0 +1,356 @@
+//! Collects located facts (DIDs, RoH ceilings, evidence tags, profile ids)
+//! from every policy artifact under a root, using the typed loaders where
+//! they exist so unparsable artifacts are reported too.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use evo_schema::manifest::NeuroConsentManifest;
+use organiccpualn::rohmodel::RohModelShard;
+use sovereigntycore::evolvetoken::EvolveToken;
+use sovereigntycore::riskofharm::RohBand;
+
+/// A file position, 1-based line.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Location {
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file.display(), self.line)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Located<T> {
+    pub value: T,
+    pub field: String,
+    pub at: Location,
+}
+
+/// Where a RoH ceiling value comes from; each source has its own meaning.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum CeilingSource {
+    /// HCI licence `roh_global_ceiling` / `roh.global_ceiling`.
+    LicenceGlobal,
+    /// rohmodel `rohceiling_strict`.
+    ModelStrict,
+    /// rohmodel `rohceiling_research`.
+    ModelResearch,
+    /// `.evo` `lifeforce-invariants.bci.ceiling`.
+    ConsentBci,
+    /// Corridor predicate `roh_estimate_window`.
+    CorridorWindow,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum ProfileSlot {
+    Neurorights,
+    RightsKernel,
+    EibonContinuity,
+}
+
+impl ProfileSlot {
+    const PREFIXES: [(&'static str, ProfileSlot); 3] = [
+        ("neurorights.policy.", ProfileSlot::Neurorights),
+        ("rights.kernel.profile.", ProfileSlot::RightsKernel),
+        ("eibon.sovereign.continuity.", ProfileSlot::EibonContinuity),
+    ];
+
+    fn of(id: &str) -> Option<Self> {
+        Self::PREFIXES
+            .iter()
+            .find(|(p, _)| id.starts_with(p))
+            .map(|(_, s)| *s)
+    }
+}
+
+/// One evidence tag block (`evidence_hex_tags`, `evidence_hex_tags_ref`).
+#[derive(Clone, Debug, Serialize)]
+pub struct EvidenceBlock {
+    pub header: Location,
+    pub tags: Vec<Located<String>>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Inventory {
+    pub files: Vec<PathBuf>,
+    pub dids: Vec<Located<String>>,
+    pub ceilings: Vec<Located<(CeilingSource, f64)>>,
+    pub evidence: Vec<EvidenceBlock>,
+    pub profiles: Vec<Located<(ProfileSlot, String)>>,
+    /// Tokens with `roh_band: research`, by token id.
+    pub research_tokens: Vec<Located<String>>,
+    /// Artifacts a typed loader rejected, or that failed their own validation.
+    pub load_errors: Vec<Located<String>>,
+}
+
+/// Keys whose value is the host identity (not alt or safe addresses).
+const DID_KEYS: [&str; 6] = ["subjectid", "did", "bound-to-did", "hostid", "primary_bostrom", "bostromprimary"];
+
+const SKIP_DIRS: [&str; 3] = [".git", "target", "node_modules"];
+
+impl Inventory {
+    pub fn collect(root: &Path) -> Self {
+        let mut inv = Inventory::default();
+        let mut paths = Vec::new();
+        walk(root, &mut paths);
+        paths.sort();
+        for path in paths {
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            let Ok(text) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let collected = if name.ends_with(".aln") {
+                inv.collect_aln(&path, &text, name.ends_with(".rohmodel.aln"));
+                true
+            } else if name.ends_with(".evo") {
+                inv.collect_evo(&path, &text);
+                true
+            } else if name.ends_with(".ndjson") {
+                inv.collect_ndjson(&path, &text)
+            } else if name.ends_with(".json") && !name.ends_with(".schema.json") {
+                inv.collect_token(&path, &text)
+            } else {
+                false
+            };
+            if collected {
+                inv.files.push(path);
+            }
+        }
+        inv
+    }
+
+    fn collect_aln(&mut self, path: &Path, text: &str, is_rohmodel: bool) {
+        if is_rohmodel {
+            let loaded = RohModelShard::from_aln_str(text).and_then(|s| s.validate_invariants());
+            if let Err(e) = loaded {
+                self.load_error(path, 1, "rohmodel", e);
+            }
+        }
+        self.scan_keyed_lines(path, text);
+    }
+
+    fn collect_evo(&mut self, path: &Path, text: &str) {
+        match NeuroConsentManifest::parse(text) {
+            Ok(manifest) => {
+                for issue in manifest.validate() {
+                    let key = issue.path.rsplit('.').next().unwrap_or(&issue.path);
+                    let line = find_line(text, &format!("{key}:")).unwrap_or(1);
+                    self.load_error(path, line, &issue.path, issue.message);
+                }
+            }
+            Err(e) => self.load_error(path, 1, "manifest", e.to_string()),
+        }
+        self.scan_keyed_lines(path, text);
+    }
+
+    /// Returns false for NDJSON that is not an HCI licence stream.
+    fn collect_ndjson(&mut self, path: &Path, text: &str) -> bool {
+        let mut any = false;
+        for (idx, raw) in text.lines().enumerate() {
+            let line = idx + 1;
+            if raw.trim().is_empty() {
+                continue;
+            }
+            let record: Value = match serde_json::from_str(raw) {
+                Ok(v) => v,
+                Err(e) => {
+                    self.load_error(path, line, "ndjson", e.to_string());
+                    continue;
+                }
+            };
+            if record.get("shard_kind").is_none() {
+                continue;
+            }
+            any = true;
+            for key in DID_KEYS {
+                if let Some(v) = record.get(key).and_then(Value::as_str) {
+                    self.push_did(path, line, key, v);
+                }
+            }
+            for pointer in ["/roh_global_ceiling", "/roh/global_ceiling"] {
+                if let Some(v) = record.pointer(pointer).and_then(Value::as_f64) {
+                    self.push_ceiling(path, line, &pointer[1..].replace('/', "."), CeilingSource::LicenceGlobal, v);
+                }
+            }
+            for pointer in [
+                "/neurorights_profile_id",
+                "/rights_kernel_profile_id",
+                "/eibon_continuity_profile_id",
+                "/rights_kernel/profile_id",
+                "/eibon_continuity/shard_id",
+            ] {
+                if let Some(v) = record.pointer(pointer).and_then(Value::as_str) {
+                    self.push_profile(path, line, &pointer[1..].replace('/', "."), v);
+                }
+            }
+        }
+        any
+    }
+
+    /// Returns false for JSON that is not an EVOLVE/SMART token.
+    fn collect_token(&mut self, path: &Path, text: &str) -> bool {
+        let Ok(value) = serde_json::from_str::<Value>(text) else {
+            return false;
+        };
+        if value.get("kind").is_none() || value.get("subjectid").is_none() {
+            return false;
+        }
+        let token = match EvolveToken::load(path) {
+            Ok(t) => t,
+            Err(e) => {
+                self.load_error(path, 1, "token", e);
+                return true;
+            }
+        };
+        let line = find_line(text, "\"subjectid\"").unwrap_or(1);
+        self.push_did(path, line, "subjectid", &token.subjectid);
+        if token.roh_band == RohBand::Research {
+            self.research_tokens.push(Located {
+                value: token.id.clone(),
+                field: "roh_band".into(),
+                at: loc(path, find_line(text, "\"roh_band\"").unwrap_or(1)),
+            });
+        }
+        true
+    }
+
+    /// Shared line scanner for ALN (`key value`) and YAML (`key: value`).
+    fn scan_keyed_lines(&mut self, path: &Path, text: &str) {
+        let mut evidence: Option<(usize, EvidenceBlock)> = None;
+        for (idx, raw) in text.lines().enumerate() {
+            let line = idx + 1;
+            let content = raw.split('#').next().unwrap_or("");
+            if content.trim().is_empty() {
+                continue;
+            }
+            let indent = content.len() - content.trim_start().len();
+            let mut parts = content.trim().trim_start_matches("- ").split_whitespace();
+            let key = parts.next().unwrap_or("").trim_end_matches(':');
+            let rest: Vec<&str> = parts.collect();
+
+            if let Some((header_indent, block)) = evidence.as_mut() {
+                if indent > *header_indent && is_hex_tag(key) {
+                    block.tags.push(Located {
+                        value: key.to_string(),
+                        field: "evidence".into(),
+                        at: loc(path, line),
+                    });
+                    continue;
+                }
+                let (_, done) = evidence.take().expect("checked above");
+                self.evidence.push(done);
+            }
+            if rest.is_empty() && (key == "evidence_hex_tags" || key == "evidence_hex_tags_ref") {
+                evidence = Some((
+                    indent,
+                    EvidenceBlock {
+                        header: loc(path, line),
+                        tags: Vec::new(),
+                    },
+                ));
+                continue;
+            }
+
+            let value = rest.last().copied().unwrap_or("");
+            if DID_KEYS.contains(&key) {
+                self.push_did(path, line, key, value);
+            }
+            let source = match key {
+                "rohceiling_strict" => Some(CeilingSource::ModelStrict),
+                "rohceiling_research" => Some(CeilingSource::ModelResearch),
+                "roh_estimate_window" => Some(CeilingSource::CorridorWindow),
+                "ceiling" => Some(CeilingSource::ConsentBci),
+                _ => None,
+            };
+            if let (Some(source), Ok(v)) = (source, value.parse::<f64>()) {
+                self.push_ceiling(path, line, key, source, v);
+            }
+            for word in content.split(|c: char| c.is_whitespace() || c == '"' || c == ',') {
+                if ProfileSlot::of(word).is_some() {
+                    self.push_profile(path, line, key, word);
+                }
+            }
+        }
+        if let Some((_, done)) = evidence {
+            self.evidence.push(done);
+        }
+    }
+
+    fn push_did(&mut self, path: &Path, line: usize, field: &str, value: &str) {
+        let bare = value.strip_prefix("did:aln:").unwrap_or(value);
+        if bare.starts_with("bostrom1") {
+            self.dids.push(Located {
+                value: bare.to_string(),
+                field: field.to_string(),
+                at: loc(path, line),
+            });
+        }
+    }
+
+    fn push_ceiling(&mut self, path: &Path, line: usize, field: &str, source: CeilingSource, value: f64) {
+        self.ceilings.push(Located {
+            value: (source, value),
+            field: field.to_string(),
+            at: loc(path, line),
+        });
+    }
+
+    fn push_profile(&mut self, path: &Path, line: usize, field: &str, id: &str) {
+        if let Some(slot) = ProfileSlot::of(id) {
+            self.profiles.push(Located {
+                value: (slot, id.to_string()),
+                field: field.to_string(),
+                at: loc(path, line),
+            });
+        }
+    }
+
+    fn load_error(&mut self, path: &Path, line: usize, field: &str, message: String) {
+        self.load_errors.push(Located {
+            value: message,
+            field: field.to_string(),
+            at: loc(path, line),
+        });
+    }
+}
+
+fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            let skip = path
+                .file_name()
+                .map(|n| SKIP_DIRS.iter().any(|s| n == *s))
+                .unwrap_or(false);
+            if !skip {
+                walk(&path, out);
+            }
+        } else {
+            out.push(path);
+        }
+    }
+}
+
+fn loc(path: &Path, line: usize) -> Location {
+    Location {
+        file: path.to_path_buf(),
+        line,
+    }
+}
+
+fn find_line(text: &str, needle: &str) -> Option<usize> {
+    text.lines().position(|l| l.contains(needle)).map(|i| i + 1)
+}
+
+fn is_hex_tag(s: &str) -> bool {
+    s.len() == 8 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+mod checks;
+mod inventory;
+
+use checks::run_all;
+use inventory::Inventory;
+
+/// Cross-document consistency checker for policy artifacts.
+///
+/// Loads every ALN shard, HCI licence NDJSON, `.evo` consent manifest and
+/// EVOLVE/SMART token under the workspace root and reports drift between
+/// them (host DIDs, RoH ceilings, evidence tags, neurorights profile ids):
+///   cargo run -p policy-consistency-cli -- --workspace-root .
+///
+/// Exits non-zero when any conflict is found, so it can gate CI.
+#[derive(Parser, Debug)]
+#[command(name = "policy-consistency-cli")]
+struct Args {
+    /// Workspace root to scan (defaults to CWD).
+    #[arg(long)]
+    workspace_root: Option<PathBuf>,
+
+    /// Emit findings as JSON instead of `file:line: [check] message`.
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let root = match args.workspace_root {
+        Some(root) => root,
+        None => std::env::current_dir()?,
+    };
+
+    let inventory = Inventory::collect(&root);
+    let findings = run_all(&inventory);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&findings)?);
+    } else {
+        for f in &findings {
+            println!("{}: [{}] {}", f.at, f.check, f.message);
+            for r in &f.related {
+                println!("    see {r}");
+            }
+        }
+        eprintln!(
+            "checked {} artifacts, {} conflict(s)",
+            inventory.files.len(),
+            findings.len()
+        );
+    }
+
+    if !findings.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
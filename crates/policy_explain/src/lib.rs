@@ -0,0 +1,373 @@
+//! Plain-language explanations for policy decisions
+//! (corridor clause `explainable_use_required`).
+//!
+//! Machine reasons are mapped to clause ids:
+//! - `corridor.<reason>` for `EvolutionDecision::reasons`,
+//! - `sovereignty.<id>` for `SovereigntyCore` denials (`DenyClause::id`),
+//! - `consent.<id>` for `evo_schema::PolicyError` (`PolicyError::clause_id`).
+//!
+//! Ids come from the decision itself, never from its message text.
+//!
+//! Each clause id has a template; `{corridor}` and `{detail}` are filled
+//! in. Unknown clauses still render, naming the rule and the raw reason.
+//! `ActivationRecord` always carries the explanation of its decision.
+//!
+//! `ActivationLog` wraps the three decision points (corridor kernels,
+//! `SovereigntyCore::evaluate`, `ConsentLedger::admit_upgrade`) and appends
+//! each decision's record to an audit sink before returning it.
+
+#![forbid(unsafe_code)]
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use bioscale_core::{BrainSpecs, EvolutionDecision, EvolutionDecisionKind};
+use bioscale_metrics::CorridorMetricsSink;
+use bioscale_neuro::BciHostSnapshot;
+use bioscale_upgrade_store::UpgradeDescriptor;
+use corridors_xr::gaze_v1::{XrCorridorGuardKernel, XrGazeCorridorStateV1};
+use cyberswarm_neurostack::telemetry::BciHostSnapshot as ConsentSnapshot;
+use evo_schema::lifecycle::{ConsentLedger, LifecycleError};
+use evo_schema::mode::{ModeProfiles, StepContext};
+use evo_schema::PolicyError;
+use governance::token::EvolveToken;
+use sovereigntycore::audit::{AuditRecord, AuditSink};
+use sovereigntycore::{Decision, DenyClause, NormalizedBioState, Proposal, SovereigntyCore};
+
+pub const AUDIT_PLANE: &str = "hci.activation";
+
+const DEFAULT_TEMPLATES: &[(&str, &str)] = &[
+    ("corridor.allowed", "Allowed: this {corridor} activation stayed inside every corridor limit."),
+    ("corridor.spatial_error_exceeds_corridor", "Stopped: gaze tracking was less precise than the {corridor} corridor allows."),
+    ("corridor.energy_envelope_exceeded", "Stopped: this activation would use more energy than your {corridor} budget for the event, session or day."),
+    ("corridor.sbio_or_thermal_envelope_exceeded", "Stopped: biological load or local/global warming would go past the safe limit for {corridor}."),
+    ("corridor.duty_or_timing_violation", "Stopped: activations were too frequent or too long without the required rest between them."),
+    ("corridor.hrv_eeg_or_roh_violation", "Stopped: heart-rate variability, brain-activity load or overall risk of harm left the safe range."),
+    ("corridor.denied", "Stopped: this {corridor} activation was refused by a corridor limit."),
+    ("sovereignty.allowed", "Allowed: the change passed your neurorights, safety corridor and risk-of-harm checks."),
+    ("sovereignty.subject_not_host", "Refused: the change was proposed for someone other than you, the host."),
+    ("sovereignty.pain_envelope", "Refused: your reported pain is above the level at which changes may proceed."),
+    ("sovereignty.cognitive_envelope", "Refused: your current mental workload is too high for a change right now."),
+    ("sovereignty.corridor_polytope", "Refused: the predicted effect would take you outside your safety corridor."),
+    ("sovereignty.evolve_token_required", "Refused: raising risk of harm needs an EVOLVE research token that you hold."),
+    ("sovereignty.token_subject", "Refused: the EVOLVE token presented was issued to someone else."),
+    ("sovereignty.token_band", "Refused: the EVOLVE token does not cover the research risk band."),
+    ("sovereignty.token_scope", "Refused: the EVOLVE token does not include high-risk research."),
+    ("sovereignty.token_validity", "Refused: the EVOLVE token is expired or not valid yet."),
+    ("sovereignty.physioguard", "Refused: your heart-rate variability, muscle tension or fatigue is outside the token's physiological guard."),
+    ("sovereignty.research_kind", "Refused: high-risk research may only nudge a parameter or shift a threshold."),
+    ("sovereignty.roh_strict_increase", "Refused: in normal operation a change may never increase your risk of harm."),
+    ("sovereignty.roh_strict_ceiling", "Refused: the change would put your risk of harm above the normal ceiling."),
+    ("sovereignty.effect_bound", "Refused: the research token does not state a valid limit on change size."),
+    ("sovereignty.effect_size", "Refused: the change is larger than your research token allows."),
+    ("sovereignty.roh_research_ceiling", "Refused: the change would put your risk of harm above the research ceiling."),
+    ("sovereignty.roh_not_finite", "Refused: the risk-of-harm estimate could not be computed reliably."),
+    ("consent.allowed", "Allowed: the upgrade fits everything your consent covers."),
+    ("consent.missing_rollback_anytime", "Refused: your consent does not guarantee you can roll this back at any time."),
+    ("consent.missing_mental_privacy_flag", "Refused: your consent does not guarantee mental privacy (no non-consensual modulation, no raw EEG export)."),
+    ("consent.budget_violation", "Refused: the upgrade does not fit your host energy and protein budget."),
+    ("consent.envelope_violation", "Refused: the upgrade would exceed your thermal, cognitive or neural-rope limits."),
+    ("consent.evidence_missing", "Refused: the upgrade lacks the full set of ten evidence references."),
+    ("consent.auto_evolution_forbidden", "Refused: your current mode does not allow automatic changes; you must start it yourself."),
+    ("consent.smart_token_required", "Refused: automatic changes need a valid SMART token from you."),
+    ("consent.research_band_forbidden", "Refused: your current mode does not allow research-band changes."),
+    ("consent.macro_step_forbidden", "Refused: your current mode only allows small micro-steps."),
+    ("consent.unknown_mode_profile", "Refused: the custom mode profile named in your consent could not be found."),
+    ("consent.mode_transition_denied", "Refused: switching mode was not approved through governance."),
+];
+
+/// Clause id -> template.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TemplateSet {
+    templates: BTreeMap<String, String>,
+}
+
+impl Default for TemplateSet {
+    fn default() -> Self {
+        Self {
+            templates: DEFAULT_TEMPLATES
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl TemplateSet {
+    /// Override or add a template (e.g. a localized one).
+    pub fn insert(&mut self, clause_id: &str, template: &str) {
+        self.templates.insert(clause_id.to_string(), template.to_string());
+    }
+
+    pub fn render(&self, clause_id: &str, corridor: &str, detail: &str) -> String {
+        match self.templates.get(clause_id) {
+            Some(t) => t.replace("{corridor}", corridor).replace("{detail}", detail),
+            None if detail.is_empty() => format!("Stopped by rule {clause_id}."),
+            None => format!("Stopped by rule {clause_id}: {detail}."),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExplanationLine {
+    pub clause_id: String,
+    pub text: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Explanation {
+    pub lines: Vec<ExplanationLine>,
+}
+
+impl Explanation {
+    pub fn clause_ids(&self) -> Vec<&str> {
+        self.lines.iter().map(|l| l.clause_id.as_str()).collect()
+    }
+
+    /// All lines joined into one paragraph.
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|l| l.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+pub struct Renderer<'a> {
+    pub templates: &'a TemplateSet,
+}
+
+impl Renderer<'_> {
+    fn line(&self, clause_id: String, corridor: &str, detail: &str) -> ExplanationLine {
+        ExplanationLine {
+            text: self.templates.render(&clause_id, corridor, detail),
+            clause_id,
+        }
+    }
+
+    pub fn evolution(&self, decision: &EvolutionDecision) -> Explanation {
+        let corridor = decision.corridor_id.as_str();
+        let mut lines: Vec<ExplanationLine> = decision
+            .reasons
+            .iter()
+            .map(|r| self.line(format!("corridor.{r}"), corridor, r))
+            .collect();
+        if lines.is_empty() {
+            let id = match decision.kind {
+                EvolutionDecisionKind::Allow => "corridor.allowed",
+                _ => "corridor.denied",
+            };
+            lines.push(self.line(id.to_string(), corridor, ""));
+        }
+        Explanation { lines }
+    }
+
+    pub fn sovereignty(&self, decision: &Decision) -> Explanation {
+        let line = match decision {
+            Decision::Allowed => self.line("sovereignty.allowed".into(), "", ""),
+            Decision::Denied(denial) => self.line(sovereignty_clause(denial.clause), "", &denial.message),
+        };
+        Explanation { lines: vec![line] }
+    }
+
+    pub fn consent(&self, result: &Result<(), PolicyError>) -> Explanation {
+        let line = match result {
+            Ok(()) => self.line("consent.allowed".into(), "", ""),
+            Err(e) => self.line(consent_clause(e), "", ""),
+        };
+        Explanation { lines: vec![line] }
+    }
+}
+
+pub fn sovereignty_clause(clause: DenyClause) -> String {
+    format!("sovereignty.{}", clause.id())
+}
+
+/// `PolicyError::MissingRollbackAnytime` -> `consent.missing_rollback_anytime`.
+pub fn consent_clause(error: &PolicyError) -> String {
+    format!("consent.{}", error.clause_id())
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ActivationSource {
+    Corridor(String),
+    Sovereignty,
+    Consent,
+}
+
+/// One activation decision with its explanation stored alongside.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActivationRecord {
+    pub activation_id: String,
+    pub subjectid: String,
+    pub utc_ms: i64,
+    pub source: ActivationSource,
+    pub allowed: bool,
+    pub explanation: Explanation,
+}
+
+impl ActivationRecord {
+    pub fn from_evolution(
+        activation_id: &str,
+        subjectid: &str,
+        utc_ms: i64,
+        decision: &EvolutionDecision,
+        renderer: &Renderer<'_>,
+    ) -> Self {
+        Self {
+            activation_id: activation_id.to_string(),
+            subjectid: subjectid.to_string(),
+            utc_ms,
+            source: ActivationSource::Corridor(decision.corridor_id.clone()),
+            allowed: matches!(decision.kind, EvolutionDecisionKind::Allow),
+            explanation: renderer.evolution(decision),
+        }
+    }
+
+    pub fn from_sovereignty(
+        activation_id: &str,
+        subjectid: &str,
+        utc_ms: i64,
+        decision: &Decision,
+        renderer: &Renderer<'_>,
+    ) -> Self {
+        Self {
+            activation_id: activation_id.to_string(),
+            subjectid: subjectid.to_string(),
+            utc_ms,
+            source: ActivationSource::Sovereignty,
+            allowed: matches!(decision, Decision::Allowed),
+            explanation: renderer.sovereignty(decision),
+        }
+    }
+
+    pub fn from_consent(
+        activation_id: &str,
+        subjectid: &str,
+        utc_ms: i64,
+        result: &Result<(), PolicyError>,
+        renderer: &Renderer<'_>,
+    ) -> Self {
+        Self {
+            activation_id: activation_id.to_string(),
+            subjectid: subjectid.to_string(),
+            utc_ms,
+            source: ActivationSource::Consent,
+            allowed: result.is_ok(),
+            explanation: renderer.consent(result),
+        }
+    }
+
+    /// Audit line for `sovereigntycore::audit`; detail is the explanation text.
+    pub fn to_audit_record(&self) -> AuditRecord {
+        AuditRecord {
+            utc_ms: self.utc_ms,
+            plane: AUDIT_PLANE.to_string(),
+            action: if self.allowed { "activation_allowed" } else { "activation_denied" }.to_string(),
+            actor: self.subjectid.clone(),
+            subject: self.activation_id.clone(),
+            detail: Some(format!(
+                "[{}] {}",
+                self.explanation.clause_ids().join(","),
+                self.explanation.text()
+            )),
+        }
+    }
+}
+
+/// Runs a decision point, records its explanation and only then returns the
+/// decision. An audit failure is returned instead of the decision, so no
+/// activation goes ahead unexplained.
+pub struct ActivationLog<'a> {
+    pub renderer: Renderer<'a>,
+    pub audit: &'a dyn AuditSink,
+}
+
+impl ActivationLog<'_> {
+    pub fn append(&self, record: &ActivationRecord) -> std::io::Result<()> {
+        self.audit.append(&record.to_audit_record())
+    }
+
+    /// `XrCorridorGuardKernel::check_and_decide`, recorded.
+    #[allow(clippy::too_many_arguments)]
+    pub fn check_corridor(
+        &self,
+        kernel: &dyn XrCorridorGuardKernel,
+        activation_id: &str,
+        subjectid: &str,
+        utc_ms: i64,
+        brain: &BrainSpecs,
+        budget: &bioscale_core::HostBudget,
+        bci: &BciHostSnapshot,
+        state: &XrGazeCorridorStateV1,
+        metrics: &dyn CorridorMetricsSink,
+    ) -> std::io::Result<EvolutionDecision> {
+        let decision = kernel.check_and_decide(brain, budget, bci, state, metrics);
+        self.append(&ActivationRecord::from_evolution(
+            activation_id,
+            subjectid,
+            utc_ms,
+            &decision,
+            &self.renderer,
+        ))?;
+        Ok(decision)
+    }
+
+    /// `SovereigntyCore::evaluate`, recorded under the proposal id.
+    pub fn evaluate(
+        &self,
+        core: &SovereigntyCore,
+        state: &NormalizedBioState,
+        proposal: &Proposal,
+        token: Option<&EvolveToken>,
+        now_unix: i64,
+    ) -> std::io::Result<Decision> {
+        let decision = core.evaluate(state, proposal, token, now_unix);
+        self.append(&ActivationRecord::from_sovereignty(
+            &proposal.id,
+            &proposal.subjectid,
+            now_unix.saturating_mul(1000),
+            &decision,
+            &self.renderer,
+        ))?;
+        Ok(decision)
+    }
+
+    /// `ConsentLedger::admit_upgrade`, recorded under the upgrade id. Only
+    /// consent-policy outcomes are recorded; an inactive consent or a ledger
+    /// I/O error is returned as is.
+    #[allow(clippy::too_many_arguments)]
+    pub fn admit_upgrade(
+        &self,
+        ledger: &mut ConsentLedger,
+        consent_id: &str,
+        upgrade_id: &str,
+        desc: &UpgradeDescriptor,
+        host: &bioscale_upgrade_store::HostBudget,
+        snap: &ConsentSnapshot,
+        step: &StepContext,
+        profiles: &ModeProfiles,
+        utc_ms: i64,
+    ) -> Result<(), LifecycleError> {
+        let subjectid = ledger
+            .active(consent_id)
+            .map(|c| c.host_did.clone())
+            .ok_or_else(|| LifecycleError::NotActive(consent_id.to_string()))?;
+        let result = ledger.admit_upgrade(consent_id, upgrade_id, desc, host, snap, step, profiles);
+        let outcome = match &result {
+            Ok(()) => Ok(()),
+            Err(LifecycleError::UpgradeRefused { error, .. }) => Err(error.clone()),
+            Err(_) => return result,
+        };
+        self.append(&ActivationRecord::from_consent(
+            upgrade_id,
+            &subjectid,
+            utc_ms,
+            &outcome,
+            &self.renderer,
+        ))?;
+        result
+    }
+}
@@ -0,0 +1,54 @@
+//! Every clause a decision can carry has a default template.
+
+use evo_schema::PolicyError;
+use policy_explain::{consent_clause, sovereignty_clause, Renderer, TemplateSet};
+use sovereigntycore::{Decision, Denial, DenyClause};
+
+#[test]
+fn every_sovereignty_clause_has_a_template() {
+    let templates = TemplateSet::default();
+    let renderer = Renderer { templates: &templates };
+    for clause in DenyClause::ALL {
+        let decision = Decision::Denied(Denial::new(clause, "raw message"));
+        let line = &renderer.sovereignty(&decision).lines[0];
+        assert_eq!(line.clause_id, sovereignty_clause(clause));
+        assert!(line.text.starts_with("Refused:"), "{}: {}", line.clause_id, line.text);
+    }
+}
+
+#[test]
+fn every_consent_clause_has_a_template() {
+    let templates = TemplateSet::default();
+    let renderer = Renderer { templates: &templates };
+    let errors = [
+        PolicyError::MissingRollbackAnytime,
+        PolicyError::MissingMentalPrivacyFlag,
+        PolicyError::BudgetViolation,
+        PolicyError::EnvelopeViolation,
+        PolicyError::EvidenceMissing,
+        PolicyError::AutoEvolutionForbidden,
+        PolicyError::SmartTokenRequired,
+        PolicyError::ResearchBandForbidden,
+        PolicyError::MacroStepForbidden,
+        PolicyError::UnknownModeProfile,
+        PolicyError::ModeTransitionDenied,
+    ];
+    for e in errors {
+        let line = &renderer.consent(&Err(e.clone())).lines[0];
+        assert_eq!(line.clause_id, consent_clause(&e));
+        assert!(line.text.starts_with("Refused:"), "{}: {}", line.clause_id, line.text);
+    }
+}
+
+#[test]
+fn clause_ids_do_not_depend_on_messages() {
+    let decision = Decision::Denied(Denial::new(DenyClause::ResearchKind, "reworded message"));
+    assert_eq!(
+        Renderer { templates: &TemplateSet::default() }.sovereignty(&decision).clause_ids(),
+        ["sovereignty.research_kind"]
+    );
+    assert_eq!(
+        consent_clause(&PolicyError::MissingMentalPrivacyFlag),
+        "consent.missing_mental_privacy_flag"
+    );
+}
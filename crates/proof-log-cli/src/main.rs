@@ -0,0 +1,471 @@
+use std::fs::File;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use ed25519_dalek::VerifyingKey;
+
+use organic_cpu::evidence::bundle::{BundleEntry, ProofBundle};
+use organic_cpu::evidence::chain::{verify_jsonl_against, ChainHead};
+use organic_cpu::evidence::cbor::{cbor_to_jsonl, is_cbor_path, jsonl_to_cbor, scan_log, verify_sealed_log};
+use organic_cpu::evidence::redact::{FieldReveal, RedactableField};
+use organic_cpu::evidence::{current_utc_ms, EcoBand, EventDomain, LifeforceBand, PainBand};
+use sovereigntycore::audit::JsonlAuditLog;
+use sovereigntycore::export::{ExportConsentSet, ExportGateway, ExportKind, ExportRequest, FieldClassifier};
+
+mod query;
+
+use query::{parse_band, parse_domain, Filter, Summarizer};
+
+/// Verify and inspect chained proof-artifact logs.
+///
+/// Works on logs written by `organic_cpu::evidence::chain`:
+///   cargo run -p proof-log-cli -- verify --log proofs/host.jsonl --sealed
+///   cargo run -p proof-log-cli -- verify --log proofs/host.jsonl \
+///     --expect-head 1200:<hash>
+///   cargo run -p proof-log-cli -- summary --log proofs/host.jsonl --domain WaveLoad
+///   cargo run -p proof-log-cli -- export --log proofs/host.jsonl \
+///     --from-utc-ms 1760832000000 --out review/bundle.json
+///   cargo run -p proof-log-cli -- export --log proofs/host.jsonl \
+///     --redact civic_audit_id --out review/redacted.json
+///
+/// Every command verifies the chain while reading and exits non-zero at
+/// the first broken link. Logs ending in `.cbor` are read as CBOR frames
+/// (`organic_cpu::evidence::cbor`), and a directory as a segmented log
+/// (`organic_cpu::evidence::segment`) from its last compaction on;
+/// `export` takes a JSONL file only.
+#[derive(Parser, Debug)]
+#[command(name = "proof-log-cli")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check chain links and, with `--sealed`, host signatures.
+    Verify {
+        #[arg(long)]
+        log: PathBuf,
+        /// Require host seals on every artifact. Without `--trusted-key` the
+        /// genesis key is taken from the log itself and reported as
+        /// unverified.
+        #[arg(long)]
+        sealed: bool,
+        /// Expected genesis host key (hex ed25519); implies `--sealed`.
+        #[arg(long)]
+        trusted_key: Option<String>,
+        /// Head recorded earlier, as `<records>:<hash>` from a previous
+        /// `verify`; the chain must pass through it, so a truncated or
+        /// rewritten tail fails. JSONL files only.
+        #[arg(long, value_parser = parse_head)]
+        expect_head: Option<ChainHead>,
+    },
+    /// Print matching artifacts as JSONL.
+    Query {
+        #[arg(long)]
+        log: PathBuf,
+        #[command(flatten)]
+        filter: FilterArgs,
+    },
+    /// Per-UTC-day counts per domain, HardStops and seqno gaps.
+    Summary {
+        #[arg(long)]
+        log: PathBuf,
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Emit JSON instead of text.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write matching artifacts as a bundle that verifies on its own.
+    /// From a sealed log, the bundle ends at the last signed record. The
+    /// bundle passes the HCI export gateway first
+    /// (`sovereigntycore::export`): raw fields need a matching consent, and
+    /// the attempt is audited either way.
+    Export {
+        #[arg(long)]
+        log: PathBuf,
+        #[command(flatten)]
+        filter: FilterArgs,
+        /// Withhold a field, keeping its commitment (`civic_audit_id`,
+        /// `consent_proof_hash_hex`, `provenance_hash_hex`); repeatable.
+        #[arg(long, value_parser = parse_band::<RedactableField>)]
+        redact: Vec<RedactableField>,
+        #[arg(long)]
+        out: PathBuf,
+        /// Recipient named in export consents; defaults to `--out`.
+        #[arg(long)]
+        destination: Option<String>,
+        /// JSON array of `ExportConsent`s; without it no raw field leaves.
+        #[arg(long)]
+        export_consents: Option<PathBuf>,
+        #[arg(long, default_value = "export-audit.jsonl")]
+        audit_log: PathBuf,
+    },
+    /// Print the salt and value of one committed field, for a reviewer
+    /// holding a redacted bundle.
+    Reveal {
+        #[arg(long)]
+        log: PathBuf,
+        #[arg(long)]
+        seq: u64,
+        #[arg(long, value_parser = parse_band::<RedactableField>)]
+        field: RedactableField,
+    },
+    /// Sync a SQLite index with the log (rebuilding it if it drifted),
+    /// then print matching artifacts from the index as JSONL.
+    #[cfg(feature = "sqlite-index")]
+    Index {
+        #[arg(long)]
+        log: PathBuf,
+        #[arg(long)]
+        db: PathBuf,
+        /// Re-index from genesis instead of appending.
+        #[arg(long)]
+        rebuild: bool,
+        /// Re-verify every indexed record against the recorded head before
+        /// querying; reads the whole index.
+        #[arg(long)]
+        audit: bool,
+        #[command(flatten)]
+        filter: FilterArgs,
+        #[arg(long)]
+        consent_hash: Option<String>,
+        #[arg(long)]
+        provenance_hash: Option<String>,
+    },
+    /// Convert between JSONL and CBOR; direction follows the `.cbor`
+    /// extension. The output must not exist yet.
+    Convert {
+        #[arg(long)]
+        input: PathBuf,
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Verify a bundle written by `export`.
+    VerifyBundle {
+        #[arg(long)]
+        bundle: PathBuf,
+        /// Expected genesis host key (hex ed25519).
+        #[arg(long)]
+        trusted_key: Option<String>,
+        /// Field reveal (JSON from `reveal`) to check; repeatable.
+        #[arg(long)]
+        reveal: Vec<PathBuf>,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+struct FilterArgs {
+    /// Event domain (`WaveLoad`, `SmartAutonomy`, ... or an `Other` label).
+    #[arg(long, value_parser = parse_domain)]
+    domain: Option<EventDomain>,
+    /// Lifeforce band (`Safe`, `SoftWarn`, `HardStop`).
+    #[arg(long, value_parser = parse_band::<LifeforceBand>)]
+    lifeforce: Option<LifeforceBand>,
+    /// Eco band (`Low`, `Medium`, `High`).
+    #[arg(long, value_parser = parse_band::<EcoBand>)]
+    eco: Option<EcoBand>,
+    /// Pain band (`None`, `Mild`, `Strong`, `HardStopEquivalent`).
+    #[arg(long, value_parser = parse_band::<PainBand>)]
+    pain: Option<PainBand>,
+    /// Lowest `consensus_seqno` (inclusive).
+    #[arg(long)]
+    from_seqno: Option<u64>,
+    /// Highest `consensus_seqno` (inclusive).
+    #[arg(long)]
+    to_seqno: Option<u64>,
+    /// Earliest `utc_ms` (inclusive).
+    #[arg(long)]
+    from_utc_ms: Option<i64>,
+    /// Latest `utc_ms` (inclusive).
+    #[arg(long)]
+    to_utc_ms: Option<i64>,
+}
+
+impl From<FilterArgs> for Filter {
+    fn from(a: FilterArgs) -> Self {
+        Filter {
+            domain: a.domain,
+            lifeforce: a.lifeforce,
+            eco: a.eco,
+            pain: a.pain,
+            seqno: (a.from_seqno, a.to_seqno),
+            utc_ms: (a.from_utc_ms, a.to_utc_ms),
+        }
+    }
+}
+
+fn parse_key(hex_key: &str) -> anyhow::Result<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(hex_key)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("trusted key must be 32 bytes"))?;
+    Ok(VerifyingKey::from_bytes(&bytes)?)
+}
+
+fn parse_head(s: &str) -> Result<ChainHead, String> {
+    let (next_seq, hash) = s
+        .split_once(':')
+        .ok_or_else(|| format!("expected <records>:<hash>, got {s:?}"))?;
+    Ok(ChainHead {
+        next_seq: next_seq.parse().map_err(|e| format!("bad record count {next_seq:?}: {e}"))?,
+        hash: hash.to_string(),
+    })
+}
+
+/// Host DID of the bundle's artifacts, for matching export consents.
+fn bundle_subject(bundle: &ProofBundle) -> String {
+    bundle
+        .entries
+        .iter()
+        .find_map(|e| match e {
+            BundleEntry::Full(r) => r.artifact().map(|a| a.host.did.clone()),
+            BundleEntry::Redacted(r) => Some(r.artifact.host.did.clone()),
+            BundleEntry::Omitted(_) => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Seal line for a verified log or bundle. Without a trusted key the
+/// genesis key is whatever the log asserts, so the DID is only a claim.
+fn sealed_by(did: &str, keys: usize, trusted: bool) -> String {
+    if trusted {
+        format!("sealed by {did} ({keys} key(s)), genesis key trusted")
+    } else {
+        format!(
+            "sealed under a self-asserted genesis key claiming {did} ({keys} key(s)); \
+             UNVERIFIED, pass --trusted-key to bind it to the host"
+        )
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    match args.command {
+        Command::Verify {
+            log,
+            sealed,
+            trusted_key,
+            expect_head,
+        } => {
+            if let Some(anchor) = &expect_head {
+                if log.is_dir() || is_cbor_path(&log) {
+                    anyhow::bail!("--expect-head takes a JSONL log file");
+                }
+                verify_jsonl_against(&log, anchor)?;
+                println!(
+                    "proof-log-cli: chain passes through head {}:{}",
+                    anchor.next_seq, anchor.hash
+                );
+            }
+            let trusted = trusted_key.as_deref().map(parse_key).transpose()?;
+            if sealed || trusted.is_some() {
+                let (head, report) = verify_sealed_log(&log, trusted)?;
+                println!(
+                    "proof-log-cli: chain ok, {} records, head {}",
+                    head.next_seq, head.hash
+                );
+                println!(
+                    "proof-log-cli: {}, {} artifacts sealed",
+                    sealed_by(&report.did, report.keys.len(), trusted.is_some()),
+                    report.sealed_artifacts
+                );
+                if !report.unsealed_tail.is_empty() {
+                    eprintln!(
+                        "proof-log-cli: {} trailing artifact(s) not yet sealed: seq {:?}",
+                        report.unsealed_tail.len(),
+                        report.unsealed_tail
+                    );
+                    std::process::exit(1);
+                }
+            } else {
+                let head = scan_log(&log, |_| Ok(()))?;
+                println!(
+                    "proof-log-cli: chain ok, {} records, head {}",
+                    head.next_seq, head.hash
+                );
+            }
+        }
+        Command::Query { log, filter } => {
+            let filter = Filter::from(filter);
+            scan_log(&log, |record| {
+                if filter.matches_record(record) {
+                    println!("{}", serde_json::to_string(record)?);
+                }
+                Ok(())
+            })?;
+        }
+        Command::Summary { log, filter, json } => {
+            let filter = Filter::from(filter);
+            let mut summarizer = Summarizer::default();
+            scan_log(&log, |record| {
+                if let Some(artifact) = record.artifact() {
+                    summarizer.push(artifact, &filter);
+                }
+                Ok(())
+            })?;
+            let days = summarizer.finish();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&days)?);
+            } else {
+                for d in &days {
+                    println!(
+                        "{}: {} artifact(s), HardStop lifeforce={} pain={}",
+                        d.day, d.artifacts, d.lifeforce_hardstops, d.pain_hardstops
+                    );
+                    for (domain, n) in &d.per_domain {
+                        println!("  {domain:<18} {n}");
+                    }
+                    for (first, last) in &d.seqno_gaps {
+                        println!("  missing seqno {first}..={last}");
+                    }
+                }
+            }
+        }
+        Command::Export {
+            log,
+            filter,
+            redact,
+            out,
+            destination,
+            export_consents,
+            audit_log,
+        } => {
+            let filter = Filter::from(filter);
+            let mut bundle = ProofBundle::from_jsonl_redacted(&log, |r| filter.matches_record(r), &redact)?;
+            let mut report = bundle.verify(None)?;
+            if report.seal.as_ref().is_some_and(|s| !s.unsealed_tail.is_empty()) {
+                let dropped = bundle.cut_at_last_seal();
+                report = bundle.verify(None)?;
+                eprintln!(
+                    "proof-log-cli: left out {dropped} unsealed trailing record(s); seal the log to export them"
+                );
+            }
+            let consents = match &export_consents {
+                Some(path) => ExportConsentSet::load(path).map_err(anyhow::Error::msg)?,
+                None => ExportConsentSet::default(),
+            };
+            let audit = JsonlAuditLog::new(&audit_log);
+            let classifier = FieldClassifier::for_kind(&ExportKind::ProofBundle);
+            let gateway = ExportGateway {
+                classifier: &classifier,
+                consents: &consents,
+                audit: &audit,
+            };
+            let request = ExportRequest {
+                subjectid: bundle_subject(&bundle),
+                destination: destination.unwrap_or_else(|| out.display().to_string()),
+                kind: ExportKind::ProofBundle,
+                payload: serde_json::to_value(&bundle)?,
+            };
+            gateway
+                .export(&request, current_utc_ms())
+                .map_err(|e| anyhow::anyhow!("export blocked: {e}"))?;
+            if let Some(parent) = out.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            serde_json::to_writer_pretty(File::create(&out)?, &request.payload)?;
+            println!(
+                "proof-log-cli: wrote {} artifact(s) ({} redacted, {} omitted) to {:?}",
+                report.included_artifacts, report.redacted, report.omitted, out
+            );
+        }
+        Command::Reveal { log, seq, field } => {
+            let mut reveal = None;
+            scan_log(&log, |record| {
+                if record.body.seq == seq {
+                    reveal = FieldReveal::from_record(record, field);
+                }
+                Ok(())
+            })?;
+            let reveal = reveal.ok_or_else(|| {
+                anyhow::anyhow!("seq {seq} is not an artifact with a commitment for {}", field.name())
+            })?;
+            println!("{}", serde_json::to_string_pretty(&reveal)?);
+        }
+        #[cfg(feature = "sqlite-index")]
+        Command::Index {
+            log,
+            db,
+            rebuild,
+            audit,
+            filter,
+            consent_hash,
+            provenance_hash,
+        } => {
+            use organic_cpu::evidence::index::{IndexQuery, ProofIndex};
+
+            let index = ProofIndex::open(&db)?;
+            if rebuild {
+                index.rebuild(&log)?;
+            } else {
+                let sync = index.sync(&log)?;
+                if sync.rebuilt {
+                    eprintln!("proof-log-cli: index had drifted from the log and was rebuilt");
+                }
+            }
+            let head = if audit { index.audit()? } else { index.head()? };
+            eprintln!("proof-log-cli: index at {} records, head {}", head.next_seq, head.hash);
+            let query = IndexQuery {
+                domain: filter.domain,
+                lifeforce: filter.lifeforce,
+                eco: filter.eco,
+                pain: filter.pain,
+                consent_proof_hash_hex: consent_hash,
+                provenance_hash_hex: provenance_hash,
+                seqno: (filter.from_seqno, filter.to_seqno),
+                utc_ms: (filter.from_utc_ms, filter.to_utc_ms),
+                limit: None,
+            };
+            for record in index.query(&query)? {
+                println!("{}", serde_json::to_string(&record)?);
+            }
+        }
+        Command::Convert { input, output } => {
+            let head = match (is_cbor_path(&input), is_cbor_path(&output)) {
+                (false, true) => jsonl_to_cbor(&input, &output)?,
+                (true, false) => cbor_to_jsonl(&input, &output)?,
+                _ => anyhow::bail!("exactly one of --input and --output must end in .cbor"),
+            };
+            println!(
+                "proof-log-cli: converted {} records to {:?}, head {}",
+                head.next_seq, output, head.hash
+            );
+        }
+        Command::VerifyBundle {
+            bundle,
+            trusted_key,
+            reveal,
+        } => {
+            let trusted = trusted_key.as_deref().map(parse_key).transpose()?;
+            let bundle: ProofBundle = serde_json::from_reader(File::open(&bundle)?)?;
+            let report = bundle.verify(trusted)?;
+            println!(
+                "proof-log-cli: bundle ok, {} artifact(s), {} omitted, head {}",
+                report.included_artifacts, report.omitted, report.head.hash
+            );
+            if let Some(seal) = &report.seal {
+                println!(
+                    "proof-log-cli: {}",
+                    sealed_by(&seal.did, seal.keys.len(), trusted.is_some())
+                );
+                if !seal.unsealed_tail.is_empty() {
+                    eprintln!(
+                        "proof-log-cli: {} trailing artifact(s) not sealed: seq {:?}",
+                        seal.unsealed_tail.len(),
+                        seal.unsealed_tail
+                    );
+                    std::process::exit(1);
+                }
+            }
+            for path in &reveal {
+                let r: FieldReveal = serde_json::from_reader(File::open(path)?)?;
+                if !bundle.check_reveal(&r) {
+                    anyhow::bail!("reveal {:?} does not match seq {} {}", path, r.seq, r.field.name());
+                }
+                println!("proof-log-cli: seq {} {} = {:?}", r.seq, r.field.name(), r.value);
+            }
+        }
+    }
+    Ok(())
+}
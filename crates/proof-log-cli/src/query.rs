@@ -0,0 +1,135 @@
+//! Artifact filters and per-day summaries over a chained proof log.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use organic_cpu::evidence::chain::{ChainedProofRecord, SeqnoSet};
+use organic_cpu::evidence::merkle::utc_day;
+use organic_cpu::evidence::{
+    BiophysicalProofArtifact, EcoBand, EventDomain, LifeforceBand, PainBand,
+};
+
+/// Artifact filter; every set field must match.
+#[derive(Clone, Debug, Default)]
+pub struct Filter {
+    pub domain: Option<EventDomain>,
+    pub lifeforce: Option<LifeforceBand>,
+    pub eco: Option<EcoBand>,
+    pub pain: Option<PainBand>,
+    /// Inclusive `consensus_seqno` range.
+    pub seqno: (Option<u64>, Option<u64>),
+    /// Inclusive `utc_ms` range.
+    pub utc_ms: (Option<i64>, Option<i64>),
+}
+
+impl Filter {
+    pub fn matches(&self, a: &BiophysicalProofArtifact) -> bool {
+        self.domain.as_ref().map_or(true, |d| *d == a.event.domain)
+            && self.lifeforce.as_ref().map_or(true, |b| *b == a.lifeforce_band)
+            && self.eco.as_ref().map_or(true, |b| *b == a.eco_band)
+            && self.pain.as_ref().map_or(true, |b| a.pain_band.as_ref() == Some(b))
+            && in_range(a.consensus_seqno, self.seqno)
+            && in_range(a.utc_ms, self.utc_ms)
+    }
+
+    pub fn matches_record(&self, record: &ChainedProofRecord) -> bool {
+        record.artifact().is_some_and(|a| self.matches(a))
+    }
+}
+
+fn in_range<T: PartialOrd>(v: T, (lo, hi): (Option<T>, Option<T>)) -> bool {
+    lo.map_or(true, |lo| v >= lo) && hi.map_or(true, |hi| v <= hi)
+}
+
+/// Parse a domain label: a known variant name, otherwise `Other(label)`.
+pub fn parse_domain(s: &str) -> Result<EventDomain, String> {
+    Ok(match s {
+        "WaveLoad" => EventDomain::WaveLoad,
+        "SmartAutonomy" => EventDomain::SmartAutonomy,
+        "EvolutionUpgrade" => EventDomain::EvolutionUpgrade,
+        "DefensiveMicro" => EventDomain::DefensiveMicro,
+        other => EventDomain::Other(other.to_string()),
+    })
+}
+
+/// Parse a unit-variant band name (`Safe`, `High`, `HardStopEquivalent`, ...).
+pub fn parse_band<T: serde::de::DeserializeOwned>(s: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(s.to_string()))
+        .map_err(|_| format!("unknown band '{s}'"))
+}
+
+pub fn domain_label(d: &EventDomain) -> String {
+    match d {
+        EventDomain::Other(s) => s.clone(),
+        known => format!("{known:?}"),
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DaySummary {
+    pub day: String,
+    pub artifacts: u64,
+    pub per_domain: BTreeMap<String, u64>,
+    pub lifeforce_hardstops: u64,
+    pub pain_hardstops: u64,
+    /// Missing `consensus_seqno` ranges `(first, last)`, inclusive.
+    pub seqno_gaps: Vec<(u64, u64)>,
+}
+
+/// Build per-day summaries. Counts cover artifacts that match `filter`;
+/// seqno gaps are taken over every artifact, since domain or band filters
+/// would otherwise show gaps that are not missing events. Gaps are worked
+/// out in `finish` over every seqno seen, so an artifact that arrives out
+/// of order fills its gap; each gap is listed under the day of the
+/// artifact right after it.
+#[derive(Debug, Default)]
+pub struct Summarizer {
+    days: BTreeMap<String, DaySummary>,
+    seen: SeqnoSet,
+    /// `utc_ms` of the first artifact seen per seqno.
+    utc_ms: BTreeMap<u64, i64>,
+}
+
+impl Summarizer {
+    pub fn push(&mut self, artifact: &BiophysicalProofArtifact, filter: &Filter) {
+        let day = utc_day(artifact.utc_ms);
+        if self.seen.insert(artifact.consensus_seqno) {
+            self.utc_ms.insert(artifact.consensus_seqno, artifact.utc_ms);
+        }
+
+        if !filter.matches(artifact) {
+            return;
+        }
+        let summary = self.entry(&day);
+        summary.artifacts += 1;
+        *summary
+            .per_domain
+            .entry(domain_label(&artifact.event.domain))
+            .or_default() += 1;
+        if artifact.lifeforce_band == LifeforceBand::HardStop {
+            summary.lifeforce_hardstops += 1;
+        }
+        if artifact.pain_band == Some(PainBand::HardStopEquivalent) {
+            summary.pain_hardstops += 1;
+        }
+    }
+
+    fn entry(&mut self, day: &str) -> &mut DaySummary {
+        self.days.entry(day.to_string()).or_insert_with(|| DaySummary {
+            day: day.to_string(),
+            ..DaySummary::default()
+        })
+    }
+
+    pub fn finish(mut self) -> Vec<DaySummary> {
+        let runs: Vec<(u64, u64)> = self.seen.runs().collect();
+        for pair in runs.windows(2) {
+            let (gap, next) = ((pair[0].1 + 1, pair[1].0 - 1), pair[1].0);
+            if let Some(utc_ms) = self.utc_ms.get(&next).copied() {
+                self.entry(&utc_day(utc_ms)).seqno_gaps.push(gap);
+            }
+        }
+        self.days.into_values().collect()
+    }
+}
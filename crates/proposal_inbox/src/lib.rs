@@ -0,0 +1,412 @@
+//! Host-local inbox for propose-only upgrades.
+//!
+//! Propose-only terminals deposit `ProposedUpgrade`s here; nothing in this
+//! crate actuates. A host reviews pending entries and approves, rejects or
+//! annotates them. Approval turns an entry into a `sovereigntycore::Proposal`
+//! that still has to pass `SovereigntyCore::evaluate`.
+//!
+//! An inbox is bound to one host DID when opened. Only that host may
+//! approve, reject or annotate, and only proposals whose SESSION host is
+//! that DID are approved; the SESSION section itself is proposer-controlled
+//! and never trusted alone. Entries come in only through `submit`, and
+//! `approve` re-runs `ReferenceTerminal::check` on the stored text, so a
+//! lenient terminal or an edited `inbox.json` cannot skip the policy.
+//! Every state change is written to the audit log before `inbox.json`.
+//!
+//! Layout under the inbox root:
+//! - `inbox.json`: current entries, keyed by content hash (rewritten atomically).
+//! - `inbox-audit.jsonl`: append-only log of every state change.
+//!
+//! A terminal that reaches this crate can name both `ProposeOnlyTerminal`
+//! and `ActuationCapability`, so the no-actuation guarantee is checked
+//! here. A terminal holding an effector compiles as long as it does not
+//! drive it:
+//!
+//! ```
+//! use std::sync::Arc;
+//! use propose_only::{PolicyError, ProposeOnlyTerminal, ProposedUpgrade};
+//! use sovereigntycore::capability::ActuationCapability;
+//!
+//! trait Effector: Send + Sync {
+//!     fn apply(&self, cap: ActuationCapability, payload: Vec<f64>, now_unix: i64) -> Result<bool, String>;
+//! }
+//!
+//! struct SneakyTerminal {
+//!     effector: Arc<dyn Effector>,
+//! }
+//!
+//! impl ProposeOnlyTerminal for SneakyTerminal {
+//!     fn submit_proposal_aln(&self, _raw_aln: &str) -> Result<ProposedUpgrade, PolicyError> {
+//!         let _held = Arc::clone(&self.effector);
+//!         Err(PolicyError::UnsafeTerminalClass)
+//!     }
+//! }
+//! ```
+//!
+//! Driving it needs a capability, and the terminal cannot mint one:
+//!
+//! ```compile_fail
+//! use std::sync::Arc;
+//! use propose_only::{PolicyError, ProposeOnlyTerminal, ProposedUpgrade};
+//! use sovereigntycore::capability::ActuationCapability;
+//!
+//! trait Effector: Send + Sync {
+//!     fn apply(&self, cap: ActuationCapability, payload: Vec<f64>, now_unix: i64) -> Result<bool, String>;
+//! }
+//!
+//! struct SneakyTerminal {
+//!     effector: Arc<dyn Effector>,
+//! }
+//!
+//! impl ProposeOnlyTerminal for SneakyTerminal {
+//!     fn submit_proposal_aln(&self, _raw_aln: &str) -> Result<ProposedUpgrade, PolicyError> {
+//!         let cap = ActuationCapability::mint("p", "bostrom1...", "stim-0", 0);
+//!         let _ = self.effector.apply(cap, vec![1.0], 0);
+//!         Err(PolicyError::UnsafeTerminalClass)
+//!     }
+//! }
+//! ```
+//!
+//! Nor build one by hand:
+//!
+//! ```compile_fail
+//! use std::sync::Arc;
+//! use propose_only::{PolicyError, ProposeOnlyTerminal, ProposedUpgrade};
+//! use sovereigntycore::capability::ActuationCapability;
+//!
+//! trait Effector: Send + Sync {
+//!     fn apply(&self, cap: ActuationCapability, payload: Vec<f64>, now_unix: i64) -> Result<bool, String>;
+//! }
+//!
+//! struct SneakyTerminal {
+//!     effector: Arc<dyn Effector>,
+//! }
+//!
+//! impl ProposeOnlyTerminal for SneakyTerminal {
+//!     fn submit_proposal_aln(&self, _raw_aln: &str) -> Result<ProposedUpgrade, PolicyError> {
+//!         let cap = ActuationCapability {
+//!             proposal_id: "p".into(),
+//!             subjectid: "bostrom1...".into(),
+//!             effector_id: "stim-0".into(),
+//!             issued_at_unix: 0,
+//!             expires_at_unix: i64::MAX,
+//!         };
+//!         let _ = self.effector.apply(cap, vec![1.0], 0);
+//!         Err(PolicyError::UnsafeTerminalClass)
+//!     }
+//! }
+//! ```
+
+#![forbid(unsafe_code)]
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use autonomysafety::polytope::NormalizedMetrics;
+use propose_only::intent_grammar::parse_intent_aln;
+use propose_only::reference_terminal::ReferenceTerminal;
+use propose_only::{PolicyError, ProposeOnlyTerminal, ProposedUpgrade};
+use sovereigntycore::audit::{AuditRecord, AuditSink, JsonlAuditLog};
+use sovereigntycore::{Proposal, ProposalKind};
+
+pub const AUDIT_PLANE: &str = "proposal.inbox";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewState {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Annotation {
+    pub reviewer: String,
+    pub utc_ms: i64,
+    pub note: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InboxEntry {
+    /// sha256 of `upgrade.raw_aln`, hex.
+    pub content_hash: String,
+    pub upgrade: ProposedUpgrade,
+    pub state: ReviewState,
+    pub received_utc_ms: i64,
+    pub annotations: Vec<Annotation>,
+    pub decided_by: Option<String>,
+    pub decision_note: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Queued(String),
+    Duplicate(String),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum InboxError {
+    #[error("I/O error in proposal inbox: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("serialization error in proposal inbox: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("terminal rejected proposal: {0:?}")]
+    Policy(PolicyError),
+    #[error("no inbox entry with hash {0}")]
+    UnknownEntry(String),
+    #[error("entry {0} is not pending")]
+    NotPending(String),
+    #[error("reviewer {reviewer} is not the inbox host {host}")]
+    NotHost { reviewer: String, host: String },
+    #[error("proposal subject {subject} is not the inbox host {host}")]
+    ForeignSubject { subject: String, host: String },
+    #[error("proposal cannot be converted: {0}")]
+    Conversion(String),
+}
+
+pub type InboxResult<T> = Result<T, InboxError>;
+
+pub struct ProposalInbox {
+    root: PathBuf,
+    host_did: String,
+    entries: BTreeMap<String, InboxEntry>,
+    audit: JsonlAuditLog,
+}
+
+impl ProposalInbox {
+    /// Open (or create) an inbox rooted at `root` for the host `host_did`.
+    pub fn open<P: AsRef<Path>>(root: P, host_did: &str) -> InboxResult<Self> {
+        let root = root.as_ref().to_path_buf();
+        std::fs::create_dir_all(&root)?;
+        let state_path = root.join("inbox.json");
+        let entries = if state_path.exists() {
+            serde_json::from_slice(&std::fs::read(&state_path)?)?
+        } else {
+            BTreeMap::new()
+        };
+        let audit = JsonlAuditLog::new(root.join("inbox-audit.jsonl"));
+        Ok(Self {
+            root,
+            host_did: host_did.to_string(),
+            entries,
+            audit,
+        })
+    }
+
+    pub fn content_hash(raw_aln: &str) -> String {
+        hex::encode(Sha256::digest(raw_aln.as_bytes()))
+    }
+
+    /// Run `raw_aln` through a terminal and queue the result.
+    pub fn submit(
+        &mut self,
+        terminal: &dyn ProposeOnlyTerminal,
+        raw_aln: &str,
+    ) -> InboxResult<SubmitOutcome> {
+        match terminal.submit_proposal_aln(raw_aln) {
+            Ok(upgrade) => self.enqueue(upgrade),
+            Err(e) => {
+                self.log("submit_rejected", "terminal", &Self::content_hash(raw_aln), Some(format!("{e:?}")))?;
+                Err(InboxError::Policy(e))
+            }
+        }
+    }
+
+    /// Queue an upgrade a terminal produced, deduplicating by content hash.
+    fn enqueue(&mut self, upgrade: ProposedUpgrade) -> InboxResult<SubmitOutcome> {
+        let hash = Self::content_hash(&upgrade.raw_aln);
+        if self.entries.contains_key(&hash) {
+            self.log("duplicate", &upgrade.session_id, &hash, None)?;
+            return Ok(SubmitOutcome::Duplicate(hash));
+        }
+        self.log("queued", &upgrade.session_id, &hash, None)?;
+        self.entries.insert(
+            hash.clone(),
+            InboxEntry {
+                content_hash: hash.clone(),
+                upgrade,
+                state: ReviewState::Pending,
+                received_utc_ms: now_ms(),
+                annotations: Vec::new(),
+                decided_by: None,
+                decision_note: None,
+            },
+        );
+        self.persist()?;
+        Ok(SubmitOutcome::Queued(hash))
+    }
+
+    /// Entries awaiting review, oldest first.
+    pub fn pending(&self) -> Vec<&InboxEntry> {
+        let mut out: Vec<&InboxEntry> = self
+            .entries
+            .values()
+            .filter(|e| e.state == ReviewState::Pending)
+            .collect();
+        out.sort_by_key(|e| e.received_utc_ms);
+        out
+    }
+
+    pub fn host_did(&self) -> &str {
+        &self.host_did
+    }
+
+    pub fn get(&self, hash: &str) -> Option<&InboxEntry> {
+        self.entries.get(hash)
+    }
+
+    pub fn annotate(&mut self, hash: &str, reviewer: &str, note: &str) -> InboxResult<()> {
+        self.check_host(reviewer)?;
+        if !self.entries.contains_key(hash) {
+            return Err(InboxError::UnknownEntry(hash.to_string()));
+        }
+        self.log("annotated", reviewer, hash, Some(note.to_string()))?;
+        if let Some(entry) = self.entries.get_mut(hash) {
+            entry.annotations.push(Annotation {
+                reviewer: reviewer.to_string(),
+                utc_ms: now_ms(),
+                note: note.to_string(),
+            });
+        }
+        self.persist()
+    }
+
+    pub fn reject(&mut self, hash: &str, reviewer: &str, reason: &str) -> InboxResult<()> {
+        self.check_host(reviewer)?;
+        self.check_pending(hash)?;
+        self.log("rejected", reviewer, hash, Some(reason.to_string()))?;
+        self.decide(hash, reviewer, ReviewState::Rejected, Some(reason.to_string()))
+    }
+
+    /// Approve a pending entry and convert it into a `Proposal` for
+    /// `SovereigntyCore::evaluate`. `projected_metrics` come from the
+    /// host's own simulation, never from the proposing terminal.
+    ///
+    /// Both `reviewer` and the proposal's SESSION host must equal the DID
+    /// the inbox was opened for, and the stored text must still pass
+    /// `ReferenceTerminal::check`.
+    pub fn approve(
+        &mut self,
+        hash: &str,
+        reviewer: &str,
+        projected_metrics: NormalizedMetrics,
+    ) -> InboxResult<Proposal> {
+        self.check_host(reviewer)?;
+        let entry = self.check_pending(hash)?;
+        ReferenceTerminal.check(&entry.upgrade.raw_aln).map_err(InboxError::Policy)?;
+        let proposal = to_proposal(hash, &entry.upgrade, projected_metrics)?;
+        if proposal.subjectid != self.host_did {
+            return Err(InboxError::ForeignSubject {
+                subject: proposal.subjectid,
+                host: self.host_did.clone(),
+            });
+        }
+        self.log("approved", reviewer, hash, Some(proposal.id.clone()))?;
+        self.decide(hash, reviewer, ReviewState::Approved, None)?;
+        Ok(proposal)
+    }
+
+    fn check_host(&self, reviewer: &str) -> InboxResult<()> {
+        if reviewer != self.host_did {
+            return Err(InboxError::NotHost {
+                reviewer: reviewer.to_string(),
+                host: self.host_did.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_pending(&self, hash: &str) -> InboxResult<&InboxEntry> {
+        let entry = self
+            .entries
+            .get(hash)
+            .ok_or_else(|| InboxError::UnknownEntry(hash.to_string()))?;
+        if entry.state != ReviewState::Pending {
+            return Err(InboxError::NotPending(hash.to_string()));
+        }
+        Ok(entry)
+    }
+
+    /// Apply a decision already checked by `check_pending` and audited.
+    fn decide(
+        &mut self,
+        hash: &str,
+        reviewer: &str,
+        state: ReviewState,
+        note: Option<String>,
+    ) -> InboxResult<()> {
+        let entry = self
+            .entries
+            .get_mut(hash)
+            .ok_or_else(|| InboxError::UnknownEntry(hash.to_string()))?;
+        entry.state = state;
+        entry.decided_by = Some(reviewer.to_string());
+        entry.decision_note = note;
+        self.persist()
+    }
+
+    fn persist(&self) -> InboxResult<()> {
+        let tmp = self.root.join("inbox.json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(&self.entries)?)?;
+        std::fs::rename(&tmp, self.root.join("inbox.json"))?;
+        Ok(())
+    }
+
+    fn log(&self, action: &str, actor: &str, hash: &str, detail: Option<String>) -> InboxResult<()> {
+        self.audit
+            .append(&AuditRecord::now(AUDIT_PLANE, action, actor, hash, detail))?;
+        Ok(())
+    }
+}
+
+/// Map the SESSION/INTENT/SAFETY sections onto a `sovereigntycore::Proposal`.
+pub fn to_proposal(
+    hash: &str,
+    upgrade: &ProposedUpgrade,
+    projected_metrics: NormalizedMetrics,
+) -> InboxResult<Proposal> {
+    let doc = parse_intent_aln(&upgrade.raw_aln)
+        .map_err(|e| InboxError::Conversion(format!("line {}: {}", e.line, e.message)))?;
+
+    let subjectid = doc
+        .session
+        .get("host")
+        .ok_or_else(|| InboxError::Conversion("SESSION host missing".into()))?
+        .to_string();
+
+    let kind = match doc.intent.get("kind").unwrap_or_default() {
+        "param_nudge" => ProposalKind::ParamNudge,
+        "threshold_shift" => ProposalKind::ThresholdShift,
+        "mode_shift" => ProposalKind::ModeShift,
+        "policy_update" => ProposalKind::PolicyUpdate,
+        other => return Err(InboxError::Conversion(format!("unknown INTENT kind '{other}'"))),
+    };
+
+    let roh_delta = doc
+        .safety
+        .get("roh_delta")
+        .ok_or_else(|| InboxError::Conversion("SAFETY roh_delta missing".into()))?
+        .parse::<f32>()
+        .map_err(|_| InboxError::Conversion("SAFETY roh_delta is not a number".into()))?;
+
+    let short = hash
+        .get(..16)
+        .ok_or_else(|| InboxError::Conversion(format!("content hash '{hash}' is too short")))?;
+
+    Ok(Proposal {
+        id: format!("inbox-{short}"),
+        subjectid,
+        kind,
+        roh_delta,
+        projected_metrics,
+    })
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
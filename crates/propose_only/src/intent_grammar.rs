@@ -0,0 +1,228 @@
+//! Parser for the five-section ALN intent grammar carried in
+//! `ProposedUpgrade::raw_aln`:
+//!
+//! ```text
+//! SESSION
+//!   id      sess-2026-02-03-001
+//!   host    bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7
+//! INTENT
+//!   label   tighten xr gaze duty cycle
+//!   kind    threshold_shift
+//! SAFETY
+//!   roh_delta -0.01
+//!   band      strict
+//! EVIDENCE
+//!   a1f3c9b2  # the full 10-tag registry, one tag per line
+//!   4be79d01
+//!   9cd4a7e8
+//!   2f8c6b44
+//!   7e1da2ff
+//!   5b93e0c3
+//!   d0174aac
+//!   6ac2f9d9
+//!   c4e61b20
+//!   8f09d5ee
+//! TERMINAL
+//!   class   propose_only
+//! ```
+//!
+//! Section headers are unindented and must appear once each, in this order.
+//! Entries are indented `key value` pairs (EVIDENCE entries are bare tags).
+//! `#` starts a comment. Parsing is purely syntactic; policy checks
+//! (terminal class, evidence completeness) live in `reference_terminal`.
+
+use serde::{Deserialize, Serialize};
+
+pub const SECTION_ORDER: [&str; 5] = ["SESSION", "INTENT", "SAFETY", "EVIDENCE", "TERMINAL"];
+
+/// Syntax error with a 1-based source position.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrammarError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl GrammarError {
+    fn at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlnEntry {
+    pub line: usize,
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlnSection {
+    pub name: String,
+    pub line: usize,
+    pub entries: Vec<AlnEntry>,
+}
+
+impl AlnSection {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.key == key)
+            .map(|e| e.value.as_str())
+    }
+
+    /// Like `get`, but a missing or empty value is a grammar error at the section header.
+    pub fn require(&self, key: &str) -> Result<&str, GrammarError> {
+        match self.get(key) {
+            Some(v) if !v.is_empty() => Ok(v),
+            _ => Err(GrammarError::at(
+                self.line,
+                1,
+                format!("{} section requires '{key}'", self.name),
+            )),
+        }
+    }
+}
+
+/// Structured form of a parsed intent document.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlnIntentDocument {
+    pub session: AlnSection,
+    pub intent: AlnSection,
+    pub safety: AlnSection,
+    pub evidence: AlnSection,
+    pub terminal: AlnSection,
+}
+
+impl AlnIntentDocument {
+    pub fn session_id(&self) -> &str {
+        self.session.get("id").unwrap_or_default()
+    }
+
+    pub fn intent_label(&self) -> &str {
+        self.intent.get("label").unwrap_or_default()
+    }
+
+    pub fn terminal_class(&self) -> &str {
+        self.terminal.get("class").unwrap_or_default()
+    }
+
+    /// Evidence tags in source order (lower-cased).
+    pub fn evidence_tags(&self) -> Vec<String> {
+        self.evidence.entries.iter().map(|e| e.key.clone()).collect()
+    }
+}
+
+pub fn parse_intent_aln(raw: &str) -> Result<AlnIntentDocument, GrammarError> {
+    let mut sections: Vec<AlnSection> = Vec::with_capacity(SECTION_ORDER.len());
+
+    for (idx, raw_line) in raw.lines().enumerate() {
+        let line_no = idx + 1;
+        let without_comment = raw_line.split('#').next().unwrap_or_default();
+        if without_comment.trim().is_empty() {
+            continue;
+        }
+
+        let indent = without_comment.len() - without_comment.trim_start().len();
+        let body = without_comment.trim();
+
+        if indent == 0 {
+            let expected = SECTION_ORDER.get(sections.len()).copied();
+            match expected {
+                Some(name) if body == name => sections.push(AlnSection {
+                    name: name.to_string(),
+                    line: line_no,
+                    entries: Vec::new(),
+                }),
+                Some(name) => {
+                    return Err(GrammarError::at(
+                        line_no,
+                        1,
+                        format!("expected section {name}, found '{body}'"),
+                    ))
+                }
+                None => {
+                    return Err(GrammarError::at(
+                        line_no,
+                        1,
+                        format!("unexpected content after TERMINAL: '{body}'"),
+                    ))
+                }
+            }
+            continue;
+        }
+
+        let section = sections.last_mut().ok_or_else(|| {
+            GrammarError::at(line_no, indent + 1, "entry before SESSION header")
+        })?;
+
+        let (key, value) = match body.split_once(char::is_whitespace) {
+            Some((k, v)) => (k, v.trim()),
+            None => (body, ""),
+        };
+
+        if section.name == "EVIDENCE" {
+            if !value.is_empty() {
+                return Err(GrammarError::at(
+                    line_no,
+                    indent + key.len() + 2,
+                    "EVIDENCE entries must be a single hex tag",
+                ));
+            }
+            if key.len() != 8 || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(GrammarError::at(
+                    line_no,
+                    indent + 1,
+                    format!("'{key}' is not an 8-digit hex evidence tag"),
+                ));
+            }
+            section.entries.push(AlnEntry {
+                line: line_no,
+                key: key.to_ascii_lowercase(),
+                value: String::new(),
+            });
+            continue;
+        }
+
+        if section.entries.iter().any(|e| e.key == key) {
+            return Err(GrammarError::at(
+                line_no,
+                indent + 1,
+                format!("duplicate key '{key}' in {}", section.name),
+            ));
+        }
+        section.entries.push(AlnEntry {
+            line: line_no,
+            key: key.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    let last_line = raw.lines().count().max(1);
+    let [session, intent, safety, evidence, terminal]: [AlnSection; 5] =
+        sections.try_into().map_err(|found: Vec<AlnSection>| {
+            GrammarError::at(
+                last_line,
+                1,
+                format!("missing section {}", SECTION_ORDER[found.len()]),
+            )
+        })?;
+    let doc = AlnIntentDocument {
+        session,
+        intent,
+        safety,
+        evidence,
+        terminal,
+    };
+
+    doc.session.require("id")?;
+    doc.intent.require("label")?;
+    doc.intent.require("kind")?;
+    doc.terminal.require("class")?;
+
+    Ok(doc)
+}
@@ -2,6 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 
+pub mod intent_grammar;
+pub mod reference_terminal;
+
 /// What a propose-only terminal is allowed to emit.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ProposedUpgrade {
@@ -13,12 +16,28 @@ pub struct ProposedUpgrade {
 /// Policy errors for malformed or out-of-scope proposals.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PolicyError {
-    InvalidGrammar,
+    /// 1-based position of the first syntax error in `raw_aln`.
+    InvalidGrammar {
+        line: usize,
+        column: usize,
+        message: String,
+    },
     UnsafeTerminalClass,
     MissingEvidenceBundle,
 }
 
 /// Terminals that implement this trait *cannot* actuate.
+///
+/// This crate depends only on `serde`: `ActuationCapability`, the token
+/// every effector requires, cannot be named from here. This doctest only
+/// guards that dependency boundary; that a terminal which can name the
+/// type still cannot obtain one is doctested in `proposal_inbox`, which
+/// depends on both crates:
+///
+/// ```compile_fail
+/// use sovereigntycore::capability::ActuationCapability;
+/// fn _holds(_cap: &ActuationCapability) {}
+/// ```
 pub trait ProposeOnlyTerminal {
     fn submit_proposal_aln(&self, raw_aln: &str) -> Result<ProposedUpgrade, PolicyError>;
 }
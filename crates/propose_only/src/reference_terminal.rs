@@ -0,0 +1,54 @@
+//! Reference `ProposeOnlyTerminal` backed by `intent_grammar`.
+
+use crate::intent_grammar::{parse_intent_aln, AlnIntentDocument};
+use crate::{PolicyError, ProposeOnlyTerminal, ProposedUpgrade};
+
+/// The fixed 10-tag evidence registry (same tags as the corridor shards).
+pub const REQUIRED_EVIDENCE_TAGS: [&str; 10] = [
+    "a1f3c9b2", "4be79d01", "9cd4a7e8", "2f8c6b44", "7e1da2ff",
+    "5b93e0c3", "d0174aac", "6ac2f9d9", "c4e61b20", "8f09d5ee",
+];
+
+/// Terminal classes that can only describe, never drive, a change.
+/// Anything else (e.g. `actuator`, `closed_loop`, `effector`) is rejected.
+pub const NON_ACTUATING_CLASSES: [&str; 3] = ["propose_only", "advisory", "read_only"];
+
+#[derive(Clone, Debug, Default)]
+pub struct ReferenceTerminal;
+
+impl ReferenceTerminal {
+    /// Parse and policy-check `raw_aln`, returning the structured document.
+    pub fn check(&self, raw_aln: &str) -> Result<AlnIntentDocument, PolicyError> {
+        let doc = parse_intent_aln(raw_aln).map_err(|e| PolicyError::InvalidGrammar {
+            line: e.line,
+            column: e.column,
+            message: e.message,
+        })?;
+
+        if !NON_ACTUATING_CLASSES.contains(&doc.terminal_class()) {
+            return Err(PolicyError::UnsafeTerminalClass);
+        }
+
+        let tags = doc.evidence_tags();
+        let complete = tags.len() == REQUIRED_EVIDENCE_TAGS.len()
+            && REQUIRED_EVIDENCE_TAGS
+                .iter()
+                .all(|req| tags.iter().any(|t| t == req));
+        if !complete {
+            return Err(PolicyError::MissingEvidenceBundle);
+        }
+
+        Ok(doc)
+    }
+}
+
+impl ProposeOnlyTerminal for ReferenceTerminal {
+    fn submit_proposal_aln(&self, raw_aln: &str) -> Result<ProposedUpgrade, PolicyError> {
+        let doc = self.check(raw_aln)?;
+        Ok(ProposedUpgrade {
+            session_id: doc.session_id().to_string(),
+            raw_aln: raw_aln.to_string(),
+            parsed_intent: doc.intent_label().to_string(),
+        })
+    }
+}
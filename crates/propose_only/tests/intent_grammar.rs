@@ -0,0 +1,118 @@
+//! Source positions reported by the intent grammar, directly and through
+//! `ReferenceTerminal` as `PolicyError::InvalidGrammar`.
+
+use propose_only::intent_grammar::{parse_intent_aln, GrammarError};
+use propose_only::reference_terminal::ReferenceTerminal;
+use propose_only::{PolicyError, ProposeOnlyTerminal};
+
+/// The module-doc example, verbatim.
+const VALID: &str = "\
+SESSION
+  id      sess-2026-02-03-001
+  host    bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7
+INTENT
+  label   tighten xr gaze duty cycle
+  kind    threshold_shift
+SAFETY
+  roh_delta -0.01
+  band      strict
+EVIDENCE
+  a1f3c9b2  # the full 10-tag registry, one tag per line
+  4be79d01
+  9cd4a7e8
+  2f8c6b44
+  7e1da2ff
+  5b93e0c3
+  d0174aac
+  6ac2f9d9
+  c4e61b20
+  8f09d5ee
+TERMINAL
+  class   propose_only
+";
+
+fn error(raw: &str) -> GrammarError {
+    parse_intent_aln(raw).expect_err("document should be rejected")
+}
+
+fn at(err: &GrammarError) -> (usize, usize) {
+    (err.line, err.column)
+}
+
+#[test]
+fn doc_example_parses_and_passes_the_terminal() {
+    let doc = parse_intent_aln(VALID).unwrap();
+    assert_eq!(doc.session_id(), "sess-2026-02-03-001");
+    assert_eq!(doc.intent_label(), "tighten xr gaze duty cycle");
+    assert_eq!(doc.evidence_tags().len(), 10);
+    assert_eq!(doc.evidence.entries[0].line, 11);
+
+    let upgrade = ReferenceTerminal.submit_proposal_aln(VALID).unwrap();
+    assert_eq!(upgrade.parsed_intent, "tighten xr gaze duty cycle");
+}
+
+#[test]
+fn out_of_order_section_points_at_header() {
+    let err = error(&VALID.replacen("SESSION", "INTENT", 1));
+    assert_eq!(at(&err), (1, 1));
+    assert_eq!(err.message, "expected section SESSION, found 'INTENT'");
+}
+
+#[test]
+fn entry_before_first_header_points_at_entry() {
+    let err = error(&format!("  id orphan\n{VALID}"));
+    assert_eq!(at(&err), (1, 3));
+    assert_eq!(err.message, "entry before SESSION header");
+}
+
+#[test]
+fn evidence_value_points_past_the_tag() {
+    let err = error(&VALID.replace("  4be79d01\n", "  4be79d01 extra\n"));
+    assert_eq!(at(&err), (12, 12));
+    assert_eq!(err.message, "EVIDENCE entries must be a single hex tag");
+}
+
+#[test]
+fn bad_evidence_tag_points_at_tag() {
+    let err = error(&VALID.replace("  9cd4a7e8\n", "    9cd4a7e\n"));
+    assert_eq!(at(&err), (13, 5));
+    assert_eq!(err.message, "'9cd4a7e' is not an 8-digit hex evidence tag");
+}
+
+#[test]
+fn duplicate_key_points_at_second_entry() {
+    let err = error(&VALID.replace("  band      strict\n", "  band      strict\n  band research\n"));
+    assert_eq!(at(&err), (10, 3));
+    assert_eq!(err.message, "duplicate key 'band' in SAFETY");
+}
+
+#[test]
+fn missing_required_key_points_at_section_header() {
+    let err = error(&VALID.replace("  kind    threshold_shift\n", ""));
+    assert_eq!(at(&err), (4, 1));
+    assert_eq!(err.message, "INTENT section requires 'kind'");
+}
+
+#[test]
+fn trailing_content_and_missing_sections() {
+    let err = error(&format!("{VALID}EXTRA\n"));
+    assert_eq!(at(&err), (23, 1));
+    assert_eq!(err.message, "unexpected content after TERMINAL: 'EXTRA'");
+
+    let truncated: String = VALID.lines().take(9).map(|l| format!("{l}\n")).collect();
+    let err = error(&truncated);
+    assert_eq!(at(&err), (9, 1));
+    assert_eq!(err.message, "missing section EVIDENCE");
+}
+
+#[test]
+fn terminal_reports_grammar_position() {
+    let raw = VALID.replace("  9cd4a7e8\n", "    9cd4a7e\n");
+    match ReferenceTerminal.submit_proposal_aln(&raw) {
+        Err(PolicyError::InvalidGrammar { line, column, message }) => {
+            assert_eq!((line, column), (13, 5));
+            assert!(message.contains("9cd4a7e"), "{message}");
+        }
+        other => panic!("expected InvalidGrammar, got {other:?}"),
+    }
+}
@@ -0,0 +1,113 @@
+use std::fs::File;
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use organiccpualn::rohmodel::calibrate::{
+    candidate_shard, fit_weights, load_samples, CalibrationConfig,
+};
+use organiccpualn::rohmodel::RohModelShard;
+
+/// Offline RoH weight calibration against a labeled dataset.
+///
+/// Reads a host-local CSV/NDJSON of axis values + outcome labels (e.g. the
+/// dataset behind evidence tag 8f09d5ee), fits non-negative weights with
+/// sum <= 1.0, and writes a candidate `.rohmodel.aln` for human review:
+///   cargo run -p roh-calibration-cli -- \
+///     --base qpudatashards/particles/bostrom-rohmodel-v2.rohmodel.aln \
+///     --dataset research/roh-calibration-8f09d5ee.csv \
+///     --model-id bostrom-rohmodel-v3-candidate --version 3.0.0-rc1
+#[derive(Parser, Debug)]
+#[command(name = "roh-calibration-cli")]
+struct Args {
+    /// Existing `.rohmodel.aln` shard that supplies axes, ranges and bands.
+    #[arg(long)]
+    base: PathBuf,
+
+    /// Labeled calibration dataset (`.csv`, `.ndjson` or `.jsonl`).
+    #[arg(long)]
+    dataset: PathBuf,
+
+    /// Model id written into the candidate shard.
+    #[arg(long)]
+    model_id: String,
+
+    /// Version written into the candidate shard.
+    #[arg(long)]
+    version: String,
+
+    /// Output path for the candidate shard (defaults to research/<model-id>.rohmodel.aln).
+    #[arg(long)]
+    out: Option<PathBuf>,
+
+    /// Optional path for the JSON fit report.
+    #[arg(long)]
+    report: Option<PathBuf>,
+
+    /// Maximum solver iterations.
+    #[arg(long, default_value_t = 5_000)]
+    max_iterations: usize,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    // 1. Load the base shard and make sure it is itself sane.
+    let base_text = std::fs::read_to_string(&args.base)?;
+    let base = RohModelShard::from_aln_str(&base_text).map_err(anyhow::Error::msg)?;
+    base.validate_invariants().map_err(anyhow::Error::msg)?;
+
+    // 2. Load labeled samples and fit weights.
+    let samples = load_samples(&args.dataset).map_err(anyhow::Error::msg)?;
+    let config = CalibrationConfig {
+        max_iterations: args.max_iterations,
+        ..CalibrationConfig::default()
+    };
+    let report = fit_weights(&base, &samples, &config).map_err(anyhow::Error::msg)?;
+
+    // 3. Build the candidate; this re-runs validate_invariants.
+    let dataset_label = args
+        .dataset
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| args.dataset.display().to_string());
+    let candidate = candidate_shard(&base, &report, &args.model_id, &args.version, &dataset_label)
+        .map_err(anyhow::Error::msg)?;
+
+    // 4. Write the candidate and report; never overwrite the base shard.
+    let out_path = args
+        .out
+        .unwrap_or_else(|| PathBuf::from(format!("research/{}.rohmodel.aln", args.model_id)));
+    if out_path == args.base {
+        anyhow::bail!("refusing to overwrite base shard {:?}", args.base);
+    }
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Re-parse what is about to be written so the file on disk, not just the
+    // in-memory candidate, satisfies the invariants.
+    let rendered = candidate.to_aln_string();
+    RohModelShard::from_aln_str(&rendered)
+        .and_then(|reparsed| reparsed.validate_invariants())
+        .map_err(|e| anyhow::anyhow!("rendered candidate is invalid: {e}"))?;
+    std::fs::write(&out_path, rendered)?;
+
+    if let Some(report_path) = &args.report {
+        serde_json::to_writer_pretty(File::create(report_path)?, &report)?;
+    }
+
+    println!(
+        "roh-calibration-cli: n={} rmse={:.4} r2={:.3} weight_sum={:.3} converged={} ({} iters)",
+        report.samples,
+        report.rmse,
+        report.r_squared,
+        report.weight_sum,
+        report.converged,
+        report.iterations
+    );
+    for (axis, w) in &report.weights {
+        println!("  {axis:<15} {w:.4}");
+    }
+    println!("roh-calibration-cli: wrote candidate to {:?} (pending human review)", out_path);
+    Ok(())
+}
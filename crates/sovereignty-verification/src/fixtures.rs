@@ -0,0 +1,163 @@
+//! Host-shaped inputs for harnesses and property tests.
+//!
+//! Values mirror the shards under `qpudatashards/particles/` and
+//! `policies/` so proofs run against the limits actually in force.
+
+use chrono::{TimeZone, Utc};
+
+use autonomysafety::polytope::{CorridorPolytope, NormalizedMetrics};
+use governance::neurorights::NeurorightsProfile;
+use governance::stake::StakePolicy;
+use governance::token::{EvolveToken as GovernanceToken, PhysioGuard};
+use organiccpualn::rohmodel::{RohAxis, RohBands, RohModelCore, RohModelShard, RohWeights};
+use sovereigntycore::evolvetoken::{EvolveToken, PhysioGuardLimits, TokenKind};
+use sovereigntycore::riskofharm::{RiskOfHarm, RohBand};
+use sovereigntycore::tokenguard::{BioState, HrvSample};
+use sovereigntycore::{NormalizedBioState, Proposal, ProposalKind, SovereigntyCore};
+
+pub const HOST_DID: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+pub const OTHER_DID: &str = "bostrom1ldgmtf20d6604a24ztr0jxht7xt7az4jhkmsrc";
+
+/// `bostrom-rohmodel-v2` as declared in its ALN shard.
+pub fn rohmodel_v2() -> RohModelShard {
+    let axes = [
+        ("thermalload", 0.15),
+        ("cognitiveload", 0.20),
+        ("fatigueindex", 0.20),
+        ("inflammation", 0.10),
+        ("ecoimpact", 0.10),
+        ("dreamload", 0.10),
+        ("lifeforcedrain", 0.15),
+    ]
+    .into_iter()
+    .map(|(name, weight)| RohAxis {
+        name: name.to_string(),
+        min: 0.0,
+        max: 1.0,
+        weight,
+    })
+    .collect();
+
+    RohModelShard {
+        subjectid: HOST_DID.to_string(),
+        version: "2.0.0".to_string(),
+        model: RohModelCore {
+            id: "bostrom-rohmodel-v2".to_string(),
+            weights: RohWeights { axes },
+            bands: RohBands {
+                rohceiling_strict: 0.30,
+                rohceiling_research: 0.45,
+            },
+            notes: None,
+        },
+    }
+}
+
+pub fn roh_engine() -> RiskOfHarm {
+    RiskOfHarm::new(rohmodel_v2()).expect("rohmodel v2 satisfies its invariants")
+}
+
+pub fn host_core() -> SovereigntyCore {
+    SovereigntyCore {
+        neurorights: NeurorightsProfile {
+            max_pain_vas: 6.0,
+            max_cognitive_load: 0.8,
+            ..Default::default()
+        },
+        stake: StakePolicy {
+            subjectid: HOST_DID.to_string(),
+            ..Default::default()
+        },
+        corridor: CorridorPolytope::default(),
+        roh: roh_engine(),
+    }
+}
+
+pub fn metrics(hrv_sdnn: f32, emg_tension: f32, fatigue_index: f32) -> NormalizedMetrics {
+    NormalizedMetrics {
+        hrv_sdnn,
+        emg_tension,
+        fatigue_index,
+        ..Default::default()
+    }
+}
+
+pub fn bio_state(roh: f32, pain_vas: f32, cognitive_load: f32, m: NormalizedMetrics) -> NormalizedBioState {
+    NormalizedBioState {
+        metrics: m,
+        roh,
+        pain_vas,
+        cognitive_load,
+    }
+}
+
+pub fn proposal(subjectid: &str, kind: ProposalKind, roh_delta: f32, m: NormalizedMetrics) -> Proposal {
+    Proposal {
+        id: "verification-proposal".to_string(),
+        subjectid: subjectid.to_string(),
+        kind,
+        roh_delta,
+        projected_metrics: m,
+    }
+}
+
+/// Governance-side research token as consumed by `SovereigntyCore::evaluate`.
+pub fn governance_token(
+    subjectid: &str,
+    roh_band: &str,
+    scope: &[&str],
+    valid_from: i64,
+    valid_until: i64,
+    max_effectsize: f32,
+) -> GovernanceToken {
+    GovernanceToken {
+        subjectid: subjectid.to_string(),
+        roh_band: roh_band.to_string(),
+        scope: scope.iter().map(|s| s.to_string()).collect(),
+        valid_from,
+        valid_until,
+        max_effectsize,
+        physioguard: PhysioGuard {
+            min_hrv_sdnn: 30.0,
+            max_emg_tension: 0.7,
+            max_fatigue_index: 0.6,
+        },
+        ..Default::default()
+    }
+}
+
+/// Policy-file research token as consumed by `tokenguard::enforce_research_band`,
+/// valid from `valid_from` through `valid_until` (unix seconds).
+pub fn policy_token(
+    subjectid: &str,
+    roh_band: RohBand,
+    scope: &[&str],
+    maxeffectsize: f32,
+    valid_from: i64,
+    valid_until: i64,
+) -> EvolveToken {
+    EvolveToken {
+        id: "EVOLVE-ROH-RESEARCH-verification".to_string(),
+        subjectid: subjectid.to_string(),
+        kind: TokenKind::Evolve,
+        scope: scope.iter().map(|s| s.to_string()).collect(),
+        roh_band,
+        maxeffectsize,
+        validfrom: Utc.timestamp_opt(valid_from, 0).unwrap(),
+        validuntil: Utc.timestamp_opt(valid_until, 0).unwrap(),
+        physioguard: Some(PhysioGuardLimits {
+            min_hrv_sdnn_ms: 30.0,
+            max_fatigueindex: 0.6,
+            max_pain_vas: 6.0,
+        }),
+    }
+}
+
+pub fn token_bio_state(sdnn_ms: f32, fatigueindex: f32, pain_vas: f32) -> BioState {
+    BioState {
+        hrv: HrvSample { sdnn_ms },
+        fatigueindex,
+        emg_tension: 0.0,
+        pain_vas,
+    }
+}
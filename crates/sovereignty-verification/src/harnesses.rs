@@ -0,0 +1,184 @@
+//! Kani proof harnesses. Each harness name is registered in `registry.rs`.
+
+use std::time::Duration;
+
+use biophysical_blockchain::core::traits::bio_defense::invariants::enforce_decay_multiplier_bounds;
+use bioscale_core::{BrainSpecs, EvolutionDecisionKind, HostBudget};
+use bioscale_metrics::CorridorMetricsSink;
+use bioscale_neuro::BciHostSnapshot;
+use corridors_xr::gaze_v1::{
+    XrCorridorGuardKernel, XrGazeCorridorEnvelopeV1, XrGazeCorridorGuardV1, XrGazeCorridorStateV1,
+};
+use sovereigntycore::riskofharm::{RohBand, RohTransition};
+use sovereigntycore::tokenguard::enforce_research_band;
+use sovereigntycore::{Decision, ProposalKind};
+
+use crate::fixtures::*;
+
+fn any_kind() -> ProposalKind {
+    match kani::any::<u8>() % 4 {
+        0 => ProposalKind::ParamNudge,
+        1 => ProposalKind::ThresholdShift,
+        2 => ProposalKind::ModeShift,
+        _ => ProposalKind::PolicyUpdate,
+    }
+}
+
+fn any_metrics() -> autonomysafety::polytope::NormalizedMetrics {
+    metrics(kani::any(), kani::any(), kani::any())
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn evaluate_never_allows_non_host_subject() {
+    let core = host_core();
+    let state = bio_state(kani::any(), kani::any(), kani::any(), any_metrics());
+    let p = proposal(OTHER_DID, any_kind(), kani::any(), any_metrics());
+    let token = governance_token(
+        OTHER_DID,
+        "research",
+        &["highrisk_research"],
+        kani::any(),
+        kani::any(),
+        kani::any(),
+    );
+    let token = if kani::any() { Some(&token) } else { None };
+
+    let decision = core.evaluate(&state, &p, token, kani::any());
+    assert!(!matches!(decision, Decision::Allowed));
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn evaluate_research_allow_requires_valid_token() {
+    let core = host_core();
+    let state = bio_state(kani::any(), kani::any(), kani::any(), any_metrics());
+    let p = proposal(HOST_DID, any_kind(), kani::any(), any_metrics());
+
+    let band = if kani::any() { "research" } else { "strict" };
+    let scope: &[&str] = if kani::any() { &["highrisk_research"] } else { &["policyupdate"] };
+    let subject = if kani::any() { HOST_DID } else { OTHER_DID };
+    let valid_from: i64 = kani::any();
+    let valid_until: i64 = kani::any();
+    let token = governance_token(subject, band, scope, valid_from, valid_until, kani::any());
+    let token = if kani::any() { Some(&token) } else { None };
+    let now: i64 = kani::any();
+
+    let decision = core.evaluate(&state, &p, token, now);
+
+    let strict_ok = core
+        .roh
+        .evaluate_strict(RohTransition::from_delta(state.roh, p.roh_delta))
+        .is_ok();
+    if matches!(decision, Decision::Allowed) && !strict_ok {
+        let t = token.expect("research allow without token");
+        assert!(t.subjectid == p.subjectid);
+        assert!(t.roh_band == "research");
+        assert!(t.scope.iter().any(|s| s == "highrisk_research"));
+        assert!(valid_from <= now && now <= valid_until);
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn research_band_allow_requires_research_token() {
+    let roh = roh_engine();
+    let band = if kani::any() { RohBand::Research } else { RohBand::Strict };
+    let scope: &[&str] = if kani::any() { &["highrisk_research"] } else { &["envelopetighten"] };
+    let max_effect: f32 = kani::any();
+    let subject = if kani::any() { HOST_DID } else { OTHER_DID };
+    let valid_from = kani::any::<i32>() as i64;
+    let valid_until = kani::any::<i32>() as i64;
+    let now = kani::any::<i32>() as i64;
+    let token = policy_token(subject, band, scope, max_effect, valid_from, valid_until);
+    let effect: f32 = kani::any();
+    let transition = RohTransition {
+        before: kani::any(),
+        after: kani::any(),
+    };
+    let bio = token_bio_state(kani::any(), kani::any(), kani::any());
+
+    if enforce_research_band(&token, HOST_DID, now, effect, &roh, transition, &bio).is_ok() {
+        assert!(token.subjectid == HOST_DID);
+        assert!(valid_from <= now && now <= valid_until);
+        assert!(token.roh_band == RohBand::Research);
+        assert!(token.has_scope("highrisk_research"));
+        assert!(effect.abs() <= max_effect);
+        assert!(transition.after <= roh.ceiling(RohBand::Research));
+    }
+}
+
+#[kani::proof]
+fn decay_multiplier_never_exceeds_one() {
+    let raw: f32 = kani::any();
+    match enforce_decay_multiplier_bounds(raw) {
+        Ok(v) => assert!((0.0..=1.0).contains(&v)),
+        Err(_) => assert!(raw.is_nan() || raw > 1.0),
+    }
+}
+
+struct NullMetrics;
+
+impl CorridorMetricsSink for NullMetrics {
+    fn inc_corridor_breach(&self, _corridor_id: &str, _breach_type: &str, _value: f64) {}
+    fn observe_corridor_kernel_distance(&self, _corridor_id: &str, _distance: f64) {}
+    fn observe_corridor_knowledge_factor(&self, _corridor_id: &str, _kf: f64) {}
+}
+
+fn any_gaze_state() -> XrGazeCorridorStateV1 {
+    XrGazeCorridorStateV1 {
+        spatial_error_cm: kani::any(),
+        event_energy_j: kani::any(),
+        session_energy_j: kani::any(),
+        daily_energy_j: kani::any(),
+        sbio_load_index: kani::any(),
+        local_thermal_delta_c: kani::any(),
+        global_thermal_delta_c: kani::any(),
+        session_duty_fraction: kani::any(),
+        inter_event: Duration::from_millis(kani::any()),
+        continuous_burst: Duration::from_millis(kani::any()),
+        cooldown_since_last_burst: Duration::from_millis(kani::any()),
+        hrv_ratio: kani::any(),
+        eeg_beta_gamma_load: kani::any(),
+        roh_estimate_window: kani::any(),
+    }
+}
+
+/// Spec-level restatement of the envelope, written independently of the guard.
+fn within_envelope(e: &XrGazeCorridorEnvelopeV1, s: &XrGazeCorridorStateV1) -> bool {
+    s.spatial_error_cm <= e.max_spatial_error_cm
+        && s.event_energy_j <= e.per_event_energy_j
+        && s.session_energy_j <= e.per_session_energy_j
+        && s.daily_energy_j <= e.daily_energy_j
+        && s.sbio_load_index <= e.sbio_load_index_max
+        && s.local_thermal_delta_c <= e.local_thermal_delta_c_max
+        && s.global_thermal_delta_c <= e.global_thermal_delta_c_max
+        && s.session_duty_fraction <= e.max_duty_fraction_session
+        && s.inter_event.as_millis() as u64 >= e.min_inter_event_ms
+        && s.continuous_burst.as_millis() as u64 <= e.max_continuous_burst_ms
+        && s.cooldown_since_last_burst.as_millis() as u64 >= e.min_cooldown_between_bursts_ms
+        && s.hrv_ratio >= e.hrv_drop_allowed_ratio
+        && s.eeg_beta_gamma_load <= e.eeg_beta_gamma_ceiling
+        && s.roh_estimate_window <= e.roh_ceiling
+}
+
+#[kani::proof]
+#[kani::unwind(8)]
+fn xr_gaze_any_breach_denies() {
+    let guard = XrGazeCorridorGuardV1::default();
+    let state = any_gaze_state();
+    let decision = guard.check_and_decide(
+        &BrainSpecs::default(),
+        &HostBudget::default(),
+        &BciHostSnapshot::default(),
+        &state,
+        &NullMetrics,
+    );
+
+    if within_envelope(&guard.envelope, &state) {
+        assert!(decision.kind == EvolutionDecisionKind::Allow);
+    } else {
+        assert!(decision.kind == EvolutionDecisionKind::Deny);
+        assert!(!decision.reasons.is_empty());
+    }
+}
@@ -0,0 +1,18 @@
+//! Verification suite for the sovereignty guards.
+//!
+//! - `harnesses`: Kani proof harnesses (compiled only under `cargo kani`).
+//! - `fixtures`: host-shaped inputs shared by harnesses and property tests.
+//! - `registry`: the harness list, mirrored in `verification/kani-harness-index.json`
+//!   so `bioscale-evolution-cli` can gate critical upgrades on it.
+//!
+//! Run with:
+//!   cargo kani -p sovereignty-verification
+//!   cargo test -p sovereignty-verification
+
+#![forbid(unsafe_code)]
+
+pub mod fixtures;
+pub mod registry;
+
+#[cfg(kani)]
+mod harnesses;
@@ -0,0 +1,68 @@
+//! Harness registry in the shape `KaniHarnessIndex` reads.
+//!
+//! `verification/kani-harness-index.json` is the on-disk copy; the
+//! `index_matches_registry` property test keeps the two in sync.
+
+use serde::{Deserialize, Serialize};
+
+pub const INDEX_SCHEMA: &str = "bioscale.kani.harness.index.v1";
+pub const INDEX_PATH: &str = "verification/kani-harness-index.json";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarnessEntry {
+    /// Guard / upgrade id that `has_kani_harness` is queried with.
+    pub id: String,
+    /// Harness function name, as passed to `cargo kani --harness`.
+    pub harness: String,
+    pub crate_name: String,
+    pub invariant: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarnessIndexFile {
+    pub schema: String,
+    pub harnesses: Vec<HarnessEntry>,
+}
+
+const REGISTRY: &[(&str, &str, &str)] = &[
+    (
+        "sovereigntycore.evaluate",
+        "evaluate_never_allows_non_host_subject",
+        "no Allowed decision for a non-host subject",
+    ),
+    (
+        "sovereigntycore.evaluate",
+        "evaluate_research_allow_requires_valid_token",
+        "no research-band Allowed without a valid, in-window research token",
+    ),
+    (
+        "sovereigntycore.tokenguard.enforce_research_band",
+        "research_band_allow_requires_research_token",
+        "Ok only for in-window roh_band=research tokens of the host subject with highrisk_research scope, within effect size and research ceiling",
+    ),
+    (
+        "biophysical_blockchain.bio_defense.enforce_decay_multiplier_bounds",
+        "decay_multiplier_never_exceeds_one",
+        "Ok(v) implies 0.0 <= v <= 1.0; raw > 1.0 or NaN is rejected",
+    ),
+    (
+        "corridors_xr.gaze_v1.XrGazeCorridorGuardV1",
+        "xr_gaze_any_breach_denies",
+        "any envelope breach yields Deny; a fully in-envelope state yields Allow",
+    ),
+];
+
+pub fn harness_index() -> HarnessIndexFile {
+    HarnessIndexFile {
+        schema: INDEX_SCHEMA.to_string(),
+        harnesses: REGISTRY
+            .iter()
+            .map(|(id, harness, invariant)| HarnessEntry {
+                id: id.to_string(),
+                harness: harness.to_string(),
+                crate_name: "sovereignty-verification".to_string(),
+                invariant: invariant.to_string(),
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,148 @@
+//! Decisions routed through `policy_explain::ActivationLog` reach the audit
+//! sink with their explanation.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use bioscale_core::{BrainSpecs, EvolutionDecisionKind, HostBudget};
+use bioscale_metrics::CorridorMetricsSink;
+use bioscale_neuro::BciHostSnapshot;
+use corridors_xr::gaze_v1::{XrGazeCorridorGuardV1, XrGazeCorridorStateV1};
+use evo_schema::PolicyError;
+use policy_explain::{ActivationLog, ActivationRecord, Renderer, TemplateSet, AUDIT_PLANE};
+use sovereignty_verification::fixtures::*;
+use sovereigntycore::audit::{AuditRecord, AuditSink};
+use sovereigntycore::{Decision, ProposalKind};
+
+#[derive(Default)]
+struct MemoryAudit(Mutex<Vec<AuditRecord>>);
+
+impl AuditSink for MemoryAudit {
+    fn append(&self, record: &AuditRecord) -> std::io::Result<()> {
+        self.0.lock().unwrap().push(record.clone());
+        Ok(())
+    }
+}
+
+impl MemoryAudit {
+    fn records(&self) -> Vec<AuditRecord> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+struct FailingAudit;
+
+impl AuditSink for FailingAudit {
+    fn append(&self, _record: &AuditRecord) -> std::io::Result<()> {
+        Err(std::io::Error::other("audit sink offline"))
+    }
+}
+
+struct NullMetrics;
+
+impl CorridorMetricsSink for NullMetrics {
+    fn inc_corridor_breach(&self, _corridor_id: &str, _breach_type: &str, _value: f64) {}
+    fn observe_corridor_kernel_distance(&self, _corridor_id: &str, _distance: f64) {}
+    fn observe_corridor_knowledge_factor(&self, _corridor_id: &str, _kf: f64) {}
+}
+
+fn gaze_state(spatial_error_cm: f32) -> XrGazeCorridorStateV1 {
+    XrGazeCorridorStateV1 {
+        spatial_error_cm,
+        event_energy_j: 0.01,
+        session_energy_j: 1.0,
+        daily_energy_j: 5.0,
+        sbio_load_index: 0.1,
+        local_thermal_delta_c: 0.1,
+        global_thermal_delta_c: 0.1,
+        session_duty_fraction: 0.1,
+        inter_event: Duration::from_millis(500),
+        continuous_burst: Duration::from_millis(100),
+        cooldown_since_last_burst: Duration::from_millis(300),
+        hrv_ratio: 1.0,
+        eeg_beta_gamma_load: 0.2,
+        roh_estimate_window: 0.1,
+    }
+}
+
+#[test]
+fn corridor_decisions_are_recorded_with_their_clauses() {
+    let templates = TemplateSet::default();
+    let audit = MemoryAudit::default();
+    let log = ActivationLog { renderer: Renderer { templates: &templates }, audit: &audit };
+    let guard = XrGazeCorridorGuardV1::default();
+    let check = |id: &str, spatial| {
+        log.check_corridor(
+            &guard,
+            id,
+            HOST_DID,
+            1_000,
+            &BrainSpecs::default(),
+            &HostBudget::default(),
+            &BciHostSnapshot::default(),
+            &gaze_state(spatial),
+            &NullMetrics,
+        )
+        .unwrap()
+    };
+
+    assert!(check("gaze-1", 0.0).kind == EvolutionDecisionKind::Allow);
+    assert!(check("gaze-2", 0.5).kind == EvolutionDecisionKind::Deny);
+
+    let records = audit.records();
+    assert!(records.iter().all(|r| r.plane == AUDIT_PLANE && r.actor == HOST_DID));
+    assert_eq!(records[0].action, "activation_allowed");
+    assert_eq!(records[1].action, "activation_denied");
+    assert_eq!(records[1].subject, "gaze-2");
+    let detail = records[1].detail.as_deref().unwrap();
+    assert!(detail.starts_with("[corridor.spatial_error_exceeds_corridor]"), "{detail}");
+}
+
+#[test]
+fn sovereignty_denials_are_recorded_under_the_proposal() {
+    let templates = TemplateSet::default();
+    let audit = MemoryAudit::default();
+    let log = ActivationLog { renderer: Renderer { templates: &templates }, audit: &audit };
+    let core = host_core();
+    let m = metrics(50.0, 0.2, 0.2);
+    let state = bio_state(0.1, 1.0, 0.1, m.clone());
+    let p = proposal(OTHER_DID, ProposalKind::ParamNudge, 0.0, m);
+
+    let decision = log.evaluate(&core, &state, &p, None, 1_700).unwrap();
+    assert!(matches!(decision, Decision::Denied(_)));
+
+    let records = audit.records();
+    assert_eq!(records.len(), 1);
+    assert_eq!((records[0].action.as_str(), records[0].subject.as_str()), ("activation_denied", p.id.as_str()));
+    assert_eq!(records[0].utc_ms, 1_700_000);
+    assert!(records[0].detail.as_deref().unwrap().starts_with("[sovereignty.subject_not_host]"));
+}
+
+#[test]
+fn unauditable_decisions_are_not_returned() {
+    let templates = TemplateSet::default();
+    let log = ActivationLog { renderer: Renderer { templates: &templates }, audit: &FailingAudit };
+    let m = metrics(50.0, 0.2, 0.2);
+    let state = bio_state(0.1, 1.0, 0.1, m.clone());
+    let p = proposal(HOST_DID, ProposalKind::ParamNudge, 0.0, m);
+    assert!(log.evaluate(&host_core(), &state, &p, None, 0).is_err());
+}
+
+#[test]
+fn consent_refusals_carry_the_policy_clause() {
+    let templates = TemplateSet::default();
+    let audit = MemoryAudit::default();
+    let log = ActivationLog { renderer: Renderer { templates: &templates }, audit: &audit };
+    let record = ActivationRecord::from_consent(
+        "upgrade-7",
+        HOST_DID,
+        42,
+        &Err(PolicyError::MacroStepForbidden),
+        &log.renderer,
+    );
+    log.append(&record).unwrap();
+
+    let records = audit.records();
+    assert_eq!(records[0].action, "activation_denied");
+    assert!(records[0].detail.as_deref().unwrap().starts_with("[consent.macro_step_forbidden]"));
+}
@@ -0,0 +1,109 @@
+//! Signed downgrade consent through `SovereigntyCore::load_rohmodel`.
+
+use std::sync::Mutex;
+
+use ed25519_dalek::SigningKey;
+
+use sovereignty_verification::fixtures::*;
+use sovereigntycore::audit::{AuditRecord, AuditSink};
+use sovereigntycore::downgrade::{
+    DowngradeConsent, DowngradeGate, Ed25519ConsentVerifier, PolicyUpdate,
+};
+
+#[derive(Default)]
+struct MemoryAudit(Mutex<Vec<AuditRecord>>);
+
+impl AuditSink for MemoryAudit {
+    fn append(&self, record: &AuditRecord) -> std::io::Result<()> {
+        self.0.lock().unwrap().push(record.clone());
+        Ok(())
+    }
+}
+
+impl MemoryAudit {
+    fn actions(&self) -> Vec<String> {
+        self.0.lock().unwrap().iter().map(|r| r.action.clone()).collect()
+    }
+}
+
+fn host_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn looser_model() -> organiccpualn::rohmodel::RohModelShard {
+    let mut m = rohmodel_v2();
+    m.version = "2.1.0".to_string();
+    m.model.bands.rohceiling_strict = 0.35;
+    m
+}
+
+#[test]
+fn tightening_model_loads_without_consent() {
+    let mut core = host_core();
+    let audit = MemoryAudit::default();
+    let verifier = Ed25519ConsentVerifier::new(HOST_DID, host_key().verifying_key());
+    let gate = DowngradeGate { verifier: &verifier, audit: &audit };
+
+    let mut tighter = rohmodel_v2();
+    tighter.model.bands.rohceiling_strict = 0.25;
+    let review = core.load_rohmodel(&gate, tighter, None).unwrap();
+    assert!(!review.consented);
+    assert_eq!(core.roh.ceiling_strict(), 0.25);
+}
+
+#[test]
+fn loosening_model_needs_host_signature_over_the_exact_diff() {
+    let mut core = host_core();
+    let audit = MemoryAudit::default();
+    let verifier = Ed25519ConsentVerifier::new(HOST_DID, host_key().verifying_key());
+    let gate = DowngradeGate { verifier: &verifier, audit: &audit };
+
+    let update = PolicyUpdate::rohmodel(core.roh.model(), &looser_model());
+    let consent = DowngradeConsent::sign(&update, &host_key(), 1_700_000_000_000);
+
+    assert!(core.load_rohmodel(&gate, looser_model(), None).is_err());
+
+    // Same versions and keys, but a wider loosening than the host signed.
+    let mut wider = looser_model();
+    wider.model.bands.rohceiling_strict = 0.40;
+    assert!(core.load_rohmodel(&gate, wider, Some(&consent)).is_err());
+
+    let forged = DowngradeConsent::sign(&update, &SigningKey::from_bytes(&[9u8; 32]), 1_700_000_000_000);
+    assert!(core.load_rohmodel(&gate, looser_model(), Some(&forged)).is_err());
+    assert_eq!(core.roh.ceiling_strict(), 0.30);
+
+    let review = core.load_rohmodel(&gate, looser_model(), Some(&consent)).unwrap();
+    assert!(review.consented);
+    assert_eq!(core.roh.ceiling_strict(), 0.35);
+    assert_eq!(
+        audit.actions(),
+        ["update_rejected", "update_rejected", "update_rejected", "loosening_consented"]
+    );
+}
+
+#[test]
+fn model_for_another_subject_is_refused() {
+    let mut core = host_core();
+    let audit = MemoryAudit::default();
+    let verifier = Ed25519ConsentVerifier::new(HOST_DID, host_key().verifying_key());
+    let gate = DowngradeGate { verifier: &verifier, audit: &audit };
+
+    let mut foreign = rohmodel_v2();
+    foreign.subjectid = OTHER_DID.to_string();
+    assert!(core.load_rohmodel(&gate, foreign, None).is_err());
+    assert_eq!(audit.actions(), ["update_rejected"]);
+}
+
+#[test]
+fn model_failing_its_invariants_is_refused_and_audited() {
+    let mut core = host_core();
+    let audit = MemoryAudit::default();
+    let verifier = Ed25519ConsentVerifier::new(HOST_DID, host_key().verifying_key());
+    let gate = DowngradeGate { verifier: &verifier, audit: &audit };
+
+    let mut broken = rohmodel_v2();
+    broken.model.bands.rohceiling_research = 0.50;
+    assert!(core.load_rohmodel(&gate, broken, None).is_err());
+    assert_eq!(audit.actions(), ["update_rejected"]);
+    assert_eq!(core.roh.ceiling_strict(), 0.30);
+}
@@ -0,0 +1,182 @@
+//! HCI export gateway: classification, consent, revocation and audit.
+
+use std::sync::Mutex;
+
+use serde_json::{json, Value};
+
+use sovereignty_verification::fixtures::*;
+use sovereigntycore::audit::{AuditRecord, AuditSink};
+use sovereigntycore::export::{
+    DataClass, ExportConsent, ExportConsentSet, ExportGateway, ExportKind, ExportRequest, FieldClassifier,
+};
+
+#[derive(Default)]
+struct MemoryAudit(Mutex<Vec<AuditRecord>>);
+
+impl AuditSink for MemoryAudit {
+    fn append(&self, record: &AuditRecord) -> std::io::Result<()> {
+        self.0.lock().unwrap().push(record.clone());
+        Ok(())
+    }
+}
+
+impl MemoryAudit {
+    fn actions(&self) -> Vec<String> {
+        self.0.lock().unwrap().iter().map(|r| r.action.clone()).collect()
+    }
+}
+
+const DESTINATION: &str = "review/partner-lab";
+const NOW_MS: i64 = 1_760_832_000_000;
+
+fn artifact() -> Value {
+    json!({
+        "host": { "did": HOST_DID, "shard": "phx-main" },
+        "utc_ms": NOW_MS,
+        "event": { "event_id": "evt-1", "domain": "WaveLoad", "reason": null },
+        "consensus_seqno": 1,
+        "lifeforce_band": "Safe",
+        "eco_band": "Low",
+        "pain_band": null,
+        "civic_audit_id": "civic-1",
+    })
+}
+
+fn request(kind: ExportKind, payload: Value) -> ExportRequest {
+    ExportRequest {
+        subjectid: HOST_DID.to_string(),
+        destination: DESTINATION.to_string(),
+        kind,
+        payload,
+    }
+}
+
+fn eeg_consent(valid_until_utc_ms: i64, revocable: bool) -> ExportConsent {
+    ExportConsent {
+        consent_id: "consent-eeg".to_string(),
+        subjectid: HOST_DID.to_string(),
+        destination: DESTINATION.to_string(),
+        fields: vec!["eeg_samples".to_string()],
+        valid_until_utc_ms,
+        revocable,
+    }
+}
+
+fn with_eeg() -> Value {
+    let mut payload = artifact();
+    payload["eeg_samples"] = json!([0.1, 0.2, 0.3]);
+    payload
+}
+
+#[test]
+fn derived_and_banded_fields_leave_and_are_audited() {
+    let audit = MemoryAudit::default();
+    let consents = ExportConsentSet::default();
+    let classifier = FieldClassifier::for_kind(&ExportKind::ProofArtifact);
+    let gateway = ExportGateway { classifier: &classifier, consents: &consents, audit: &audit };
+
+    let verdicts = gateway.export(&request(ExportKind::ProofArtifact, artifact()), NOW_MS).unwrap();
+    assert!(verdicts.iter().all(|v| v.class != DataClass::Raw && v.consent_id.is_none()));
+    let band = verdicts.iter().find(|v| v.path == "lifeforce_band").unwrap();
+    assert_eq!(band.class, DataClass::Banded);
+    assert_eq!(audit.actions(), ["export_allowed"]);
+}
+
+#[test]
+fn raw_field_without_consent_blocks_the_whole_export() {
+    let audit = MemoryAudit::default();
+    let consents = ExportConsentSet::default();
+    let classifier = FieldClassifier::for_kind(&ExportKind::ProofArtifact);
+    let gateway = ExportGateway { classifier: &classifier, consents: &consents, audit: &audit };
+
+    let err = gateway.export(&request(ExportKind::ProofArtifact, with_eeg()), NOW_MS).unwrap_err();
+    assert!(err.contains("eeg_samples"), "{err}");
+    assert_eq!(audit.actions(), ["export_blocked"]);
+}
+
+#[test]
+fn consent_admits_raw_until_revoked_or_expired() {
+    let audit = MemoryAudit::default();
+    let consents = ExportConsentSet::default();
+    let classifier = FieldClassifier::for_kind(&ExportKind::ProofArtifact);
+    let gateway = ExportGateway { classifier: &classifier, consents: &consents, audit: &audit };
+    let req = request(ExportKind::ProofArtifact, with_eeg());
+
+    consents.grant(eeg_consent(NOW_MS + 1_000, true));
+    let verdicts = gateway.export(&req, NOW_MS).unwrap();
+    let eeg = verdicts.iter().find(|v| v.path == "eeg_samples").unwrap();
+    assert_eq!((eeg.class, eeg.consent_id.as_deref()), (DataClass::Raw, Some("consent-eeg")));
+
+    // Past its window the consent no longer admits anything.
+    assert!(gateway.export(&req, NOW_MS + 1_001).is_err());
+
+    // Another destination is not covered.
+    let mut elsewhere = req.clone();
+    elsewhere.destination = "review/other-lab".to_string();
+    assert!(gateway.export(&elsewhere, NOW_MS).is_err());
+
+    assert!(consents.revoke("consent-eeg"));
+    assert!(gateway.export(&req, NOW_MS).is_err());
+
+    // A consent the host cannot revoke is never honoured.
+    consents.grant(eeg_consent(NOW_MS + 1_000, false));
+    assert!(gateway.export(&req, NOW_MS).is_err());
+
+    assert_eq!(
+        audit.actions(),
+        ["export_allowed", "export_blocked", "export_blocked", "export_blocked", "export_blocked"]
+    );
+}
+
+#[test]
+fn bundle_entries_are_classified_per_field() {
+    let audit = MemoryAudit::default();
+    let consents = ExportConsentSet::default();
+    let classifier = FieldClassifier::for_kind(&ExportKind::ProofBundle);
+    let gateway = ExportGateway { classifier: &classifier, consents: &consents, audit: &audit };
+    let bundle = |artifact: Value| {
+        json!({
+            "format": "organic_cpu.proof.bundle.v1",
+            "source_head": { "next_seq": 2, "hash": "ab" },
+            "entries": [
+                { "bundle": "omitted", "seq": 0, "prev_hash": "00", "entry_digest": "cd", "hash": "ef" },
+                { "bundle": "full", "seq": 1, "prev_hash": "ef", "kind": "artifact",
+                  "artifact": artifact, "hash": "ab", "signature": "99" },
+            ],
+        })
+    };
+
+    gateway.export(&request(ExportKind::ProofBundle, bundle(artifact())), NOW_MS).unwrap();
+    let err = gateway
+        .export(&request(ExportKind::ProofBundle, bundle(with_eeg())), NOW_MS)
+        .unwrap_err();
+    assert!(err.contains("entries[].artifact.eeg_samples"), "{err}");
+}
+
+#[test]
+fn manifest_aggregates_pass_and_unknown_kinds_are_raw() {
+    let audit = MemoryAudit::default();
+    let consents = ExportConsentSet::default();
+    let manifest = json!({
+        "date": "2025-10-19",
+        "host_did": HOST_DID,
+        "upgrades": [{ "upgrade_id": "up-1", "evidence_hex_tags": ["a1f3c9b2"] }],
+        "bci_snapshots": { "eeg_load_min": 0.1, "eeg_load_max": 0.4, "hrv_min": 40.0, "hrv_max": 60.0 },
+        "proof_artifacts": null,
+    });
+
+    let classifier = FieldClassifier::for_kind(&ExportKind::Manifest);
+    let gateway = ExportGateway { classifier: &classifier, consents: &consents, audit: &audit };
+    gateway.export(&request(ExportKind::Manifest, manifest.clone()), NOW_MS).unwrap();
+
+    let mut leaky = manifest.clone();
+    leaky["bci_snapshots"]["eeg_trace"] = json!([0.1, 0.2]);
+    assert!(gateway.export(&request(ExportKind::Manifest, leaky), NOW_MS).is_err());
+
+    let classifier = FieldClassifier::for_kind(&ExportKind::Other("notes".to_string()));
+    let gateway = ExportGateway { classifier: &classifier, consents: &consents, audit: &audit };
+    assert!(gateway
+        .export(&request(ExportKind::Other("notes".to_string()), manifest), NOW_MS)
+        .is_err());
+    assert_eq!(audit.actions(), ["export_allowed", "export_blocked", "export_blocked"]);
+}
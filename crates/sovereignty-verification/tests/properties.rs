@@ -0,0 +1,220 @@
+//! Property-based counterparts of the Kani harnesses, for fast CI runs.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use proptest::prelude::*;
+
+use biophysical_blockchain::core::traits::bio_defense::invariants::enforce_decay_multiplier_bounds;
+use bioscale_core::{BrainSpecs, EvolutionDecisionKind, HostBudget};
+use bioscale_metrics::CorridorMetricsSink;
+use bioscale_neuro::BciHostSnapshot;
+use corridors_xr::gaze_v1::{XrCorridorGuardKernel, XrGazeCorridorGuardV1, XrGazeCorridorStateV1};
+use sovereignty_verification::fixtures::*;
+use sovereignty_verification::registry::{harness_index, HarnessIndexFile, INDEX_PATH};
+use sovereigntycore::riskofharm::{RohBand, RohTransition};
+use sovereigntycore::tokenguard::enforce_research_band;
+use sovereigntycore::{Decision, DenyClause, ProposalKind};
+
+fn kind() -> impl Strategy<Value = ProposalKind> {
+    prop_oneof![
+        Just(ProposalKind::ParamNudge),
+        Just(ProposalKind::ThresholdShift),
+        Just(ProposalKind::ModeShift),
+        Just(ProposalKind::PolicyUpdate),
+    ]
+}
+
+struct NullMetrics;
+
+impl CorridorMetricsSink for NullMetrics {
+    fn inc_corridor_breach(&self, _corridor_id: &str, _breach_type: &str, _value: f64) {}
+    fn observe_corridor_kernel_distance(&self, _corridor_id: &str, _distance: f64) {}
+    fn observe_corridor_knowledge_factor(&self, _corridor_id: &str, _kf: f64) {}
+}
+
+proptest! {
+    #[test]
+    fn evaluate_never_allows_non_host_subject(
+        roh in 0.0f32..1.0,
+        delta in -0.5f32..0.5,
+        pain in 0.0f32..10.0,
+        load in 0.0f32..1.0,
+        k in kind(),
+        with_token in any::<bool>(),
+        now in any::<i64>(),
+    ) {
+        let core = host_core();
+        let m = metrics(50.0, 0.2, 0.2);
+        let state = bio_state(roh, pain, load, m.clone());
+        let p = proposal(OTHER_DID, k, delta, m);
+        let token = governance_token(OTHER_DID, "research", &["highrisk_research"], i64::MIN, i64::MAX, 1.0);
+        let token = with_token.then_some(&token);
+        prop_assert!(!matches!(core.evaluate(&state, &p, token, now), Decision::Allowed));
+    }
+
+    #[test]
+    fn evaluate_research_allow_requires_valid_token(
+        roh in 0.0f32..0.45,
+        delta in 0.0f32..0.2,
+        research in any::<bool>(),
+        scoped in any::<bool>(),
+        from in -1_000i64..1_000,
+        until in -1_000i64..1_000,
+        now in -1_000i64..1_000,
+        with_token in any::<bool>(),
+    ) {
+        let core = host_core();
+        let m = metrics(50.0, 0.2, 0.2);
+        let state = bio_state(roh, 1.0, 0.1, m.clone());
+        let p = proposal(HOST_DID, ProposalKind::ParamNudge, delta, m);
+        let band = if research { "research" } else { "strict" };
+        let scope: &[&str] = if scoped { &["highrisk_research"] } else { &["policyupdate"] };
+        let token = governance_token(HOST_DID, band, scope, from, until, 0.2);
+        let token = with_token.then_some(&token);
+
+        let decision = core.evaluate(&state, &p, token, now);
+        let strict_ok = core.roh.evaluate_strict(RohTransition::from_delta(roh, delta)).is_ok();
+        if matches!(decision, Decision::Allowed) && !strict_ok {
+            prop_assert!(with_token && research && scoped && from <= now && now <= until);
+        }
+    }
+
+    #[test]
+    fn research_band_allow_requires_research_token(
+        research in any::<bool>(),
+        scoped in any::<bool>(),
+        max_effect in 0.0f32..0.1,
+        effect in -0.2f32..0.2,
+        before in 0.0f32..0.6,
+        after in 0.0f32..0.6,
+        sdnn in 0.0f32..80.0,
+        fatigue in 0.0f32..1.0,
+        pain in 0.0f32..10.0,
+        own in any::<bool>(),
+        from in -1_000i64..1_000,
+        until in -1_000i64..1_000,
+        now in -1_000i64..1_000,
+    ) {
+        let roh = roh_engine();
+        let band = if research { RohBand::Research } else { RohBand::Strict };
+        let scope: &[&str] = if scoped { &["highrisk_research"] } else { &["envelopetighten"] };
+        let subject = if own { HOST_DID } else { OTHER_DID };
+        let token = policy_token(subject, band, scope, max_effect, from, until);
+        let t = RohTransition { before, after };
+        let bio = token_bio_state(sdnn, fatigue, pain);
+        if enforce_research_band(&token, HOST_DID, now, effect, &roh, t, &bio).is_ok() {
+            prop_assert!(own && from <= now && now <= until);
+            prop_assert!(research && scoped);
+            prop_assert!(effect.abs() <= max_effect);
+            prop_assert!(after <= roh.ceiling(RohBand::Research));
+        }
+    }
+
+    #[test]
+    fn decay_multiplier_never_exceeds_one(raw in any::<f32>()) {
+        match enforce_decay_multiplier_bounds(raw) {
+            Ok(v) => prop_assert!((0.0..=1.0).contains(&v)),
+            Err(_) => prop_assert!(raw.is_nan() || raw > 1.0),
+        }
+    }
+
+    #[test]
+    fn xr_gaze_any_breach_denies(
+        spatial in 0.0f32..0.4,
+        energy in 0.0f32..0.1,
+        roh in 0.0f32..0.6,
+        hrv in 0.5f32..1.0,
+        inter_event_ms in 0u64..100,
+    ) {
+        let guard = XrGazeCorridorGuardV1::default();
+        let state = XrGazeCorridorStateV1 {
+            spatial_error_cm: spatial,
+            event_energy_j: energy,
+            session_energy_j: 1.0,
+            daily_energy_j: 5.0,
+            sbio_load_index: 0.1,
+            local_thermal_delta_c: 0.1,
+            global_thermal_delta_c: 0.1,
+            session_duty_fraction: 0.1,
+            inter_event: Duration::from_millis(inter_event_ms),
+            continuous_burst: Duration::from_millis(100),
+            cooldown_since_last_burst: Duration::from_millis(300),
+            hrv_ratio: hrv,
+            eeg_beta_gamma_load: 0.2,
+            roh_estimate_window: roh,
+        };
+        let e = &guard.envelope;
+        let breach = spatial > e.max_spatial_error_cm
+            || energy > e.per_event_energy_j
+            || roh > e.roh_ceiling
+            || hrv < e.hrv_drop_allowed_ratio
+            || inter_event_ms < e.min_inter_event_ms;
+        let decision = guard.check_and_decide(
+            &BrainSpecs::default(),
+            &HostBudget::default(),
+            &BciHostSnapshot::default(),
+            &state,
+            &NullMetrics,
+        );
+        if breach {
+            prop_assert!(decision.kind == EvolutionDecisionKind::Deny);
+        } else {
+            prop_assert!(decision.kind == EvolutionDecisionKind::Allow);
+        }
+    }
+}
+
+#[test]
+fn research_band_rejects_non_finite_effect_bound() {
+    let roh = roh_engine();
+    let t = RohTransition { before: 0.1, after: 0.2 };
+    let bio = token_bio_state(60.0, 0.1, 1.0);
+    for bound in [f32::NAN, f32::INFINITY, -0.1] {
+        assert!(roh.evaluate_research(t, 0.05, bound).is_err(), "{bound}");
+        let token = policy_token(HOST_DID, RohBand::Research, &["highrisk_research"], bound, 0, i32::MAX as i64);
+        assert!(enforce_research_band(&token, HOST_DID, 0, 0.05, &roh, t, &bio).is_err(), "{bound}");
+    }
+}
+
+#[test]
+fn research_band_rejects_expired_and_foreign_tokens() {
+    let roh = roh_engine();
+    let t = RohTransition { before: 0.1, after: 0.2 };
+    let bio = token_bio_state(60.0, 0.1, 1.0);
+    let token = policy_token(HOST_DID, RohBand::Research, &["highrisk_research"], 0.1, 100, 200);
+    assert!(enforce_research_band(&token, HOST_DID, 150, 0.05, &roh, t, &bio).is_ok());
+    assert!(enforce_research_band(&token, HOST_DID, 99, 0.05, &roh, t, &bio).is_err());
+    assert!(enforce_research_band(&token, HOST_DID, 201, 0.05, &roh, t, &bio).is_err());
+    assert!(enforce_research_band(&token, OTHER_DID, 150, 0.05, &roh, t, &bio).is_err());
+}
+
+#[test]
+fn evaluate_denies_research_tokens_with_the_tokenguard_clause() {
+    let core = host_core();
+    let m = metrics(50.0, 0.2, 0.2);
+    let state = bio_state(0.29, 1.0, 0.1, m.clone());
+    let p = proposal(HOST_DID, ProposalKind::ParamNudge, 0.05, m);
+    let clause = |token: &governance::token::EvolveToken, now| match core.evaluate(&state, &p, Some(token), now) {
+        Decision::Denied(d) => Some(d.clause),
+        Decision::Allowed => None,
+    };
+
+    let token = governance_token(HOST_DID, "research", &["highrisk_research"], 100, 200, 0.1);
+    assert_eq!(clause(&token, 150), None);
+    assert_eq!(clause(&token, 201), Some(DenyClause::TokenValidity));
+    let foreign = governance_token(OTHER_DID, "research", &["highrisk_research"], 100, 200, 0.1);
+    assert_eq!(clause(&foreign, 150), Some(DenyClause::TokenSubject));
+    let strict = governance_token(HOST_DID, "strict", &["highrisk_research"], 100, 200, 0.1);
+    assert_eq!(clause(&strict, 150), Some(DenyClause::TokenBand));
+    let small = governance_token(HOST_DID, "research", &["highrisk_research"], 100, 200, 0.01);
+    assert_eq!(clause(&small, 150), Some(DenyClause::EffectSize));
+}
+
+#[test]
+fn index_matches_registry() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../..").join(INDEX_PATH);
+    let text = std::fs::read_to_string(&path).expect("kani harness index present");
+    let on_disk: HarnessIndexFile = serde_json::from_str(&text).expect("index parses");
+    assert_eq!(on_disk, harness_index());
+}
@@ -0,0 +1,85 @@
+//! Host-local, append-only audit stream for sovereignty state changes
+//! (proposal review, policy updates, consent and export decisions).
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// One audit line. `plane` groups records by subsystem
+/// (e.g. "proposal.inbox"), `subject` is the object acted on.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub utc_ms: i64,
+    pub plane: String,
+    pub action: String,
+    pub actor: String,
+    pub subject: String,
+    pub detail: Option<String>,
+}
+
+impl AuditRecord {
+    pub fn now(
+        plane: impl Into<String>,
+        action: impl Into<String>,
+        actor: impl Into<String>,
+        subject: impl Into<String>,
+        detail: Option<String>,
+    ) -> Self {
+        Self {
+            utc_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as i64,
+            plane: plane.into(),
+            action: action.into(),
+            actor: actor.into(),
+            subject: subject.into(),
+            detail,
+        }
+    }
+}
+
+pub trait AuditSink: Send + Sync {
+    fn append(&self, record: &AuditRecord) -> std::io::Result<()>;
+}
+
+/// JSONL audit log; each record is one line, append-only.
+#[derive(Debug)]
+pub struct JsonlAuditLog {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonlAuditLog {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AuditSink for JsonlAuditLog {
+    fn append(&self, record: &AuditRecord) -> std::io::Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let line = serde_json::to_string(record)?;
+        file.write_all(line.as_bytes())?;
+        file.write_all(b"\n")?;
+        file.sync_data()
+    }
+}
@@ -0,0 +1,132 @@
+//! Unforgeable, single-use actuation capability.
+//!
+//! Effectors (`src/cybernetic::EffectorAdapter`) consume an
+//! `ActuationCapability` for every command they apply. The only way to
+//! obtain one is `SovereigntyCore::authorize`, which mints it from an
+//! `Allowed` decision, bound to one effector and valid for
+//! `CAPABILITY_TTL_SECS`. Propose-only crates do not depend on this crate,
+//! so they cannot even name the type.
+//!
+//! Holding a capability is a normal, public operation; this control
+//! compiles with the same imports the negative cases below use, so those
+//! fail for the reason stated and not because of a bad path:
+//!
+//! ```
+//! use sovereigntycore::capability::ActuationCapability;
+//! fn spend(cap: ActuationCapability, now_unix: i64) -> Result<String, String> {
+//!     cap.check("stim-0", now_unix)?;
+//!     Ok(cap.proposal_id().to_string())
+//! }
+//! ```
+//!
+//! The capability has private fields and no public constructor:
+//!
+//! ```compile_fail
+//! use sovereigntycore::capability::ActuationCapability;
+//! let forged = ActuationCapability {
+//!     proposal_id: "p".into(),
+//!     subjectid: "bostrom1...".into(),
+//!     effector_id: "stim-0".into(),
+//!     issued_at_unix: 0,
+//!     expires_at_unix: i64::MAX,
+//! };
+//! ```
+//!
+//! Minting is crate-private:
+//!
+//! ```compile_fail
+//! use sovereigntycore::capability::ActuationCapability;
+//! let forged = ActuationCapability::mint("p", "bostrom1...", "stim-0", 0);
+//! ```
+//!
+//! It cannot be cloned or copied out of a borrowed grant:
+//!
+//! ```compile_fail
+//! use sovereigntycore::capability::ActuationCapability;
+//! fn replay(cap: &ActuationCapability) -> ActuationCapability {
+//!     cap.clone()
+//! }
+//! ```
+//!
+//! Spending it moves it, so one grant drives one command:
+//!
+//! ```compile_fail
+//! use sovereigntycore::capability::ActuationCapability;
+//! fn spend(_cap: ActuationCapability) {}
+//! fn twice(cap: ActuationCapability) {
+//!     spend(cap);
+//!     spend(cap);
+//! }
+//! ```
+//!
+//! And it cannot be deserialized from bytes a terminal might produce:
+//!
+//! ```compile_fail
+//! use sovereigntycore::capability::ActuationCapability;
+//! let forged: ActuationCapability = serde_json::from_str("{}").unwrap();
+//! ```
+
+/// Seconds a freshly minted capability stays valid.
+pub const CAPABILITY_TTL_SECS: i64 = 30;
+
+/// Proof that `SovereigntyCore` allowed one specific proposal to drive one
+/// effector, once, before `expires_at_unix`.
+#[derive(Debug)]
+pub struct ActuationCapability {
+    proposal_id: String,
+    subjectid: String,
+    effector_id: String,
+    issued_at_unix: i64,
+    expires_at_unix: i64,
+}
+
+impl ActuationCapability {
+    pub(crate) fn mint(
+        proposal_id: &str,
+        subjectid: &str,
+        effector_id: &str,
+        issued_at_unix: i64,
+    ) -> Self {
+        Self {
+            proposal_id: proposal_id.to_string(),
+            subjectid: subjectid.to_string(),
+            effector_id: effector_id.to_string(),
+            issued_at_unix,
+            expires_at_unix: issued_at_unix.saturating_add(CAPABILITY_TTL_SECS),
+        }
+    }
+
+    pub fn proposal_id(&self) -> &str {
+        &self.proposal_id
+    }
+
+    pub fn subjectid(&self) -> &str {
+        &self.subjectid
+    }
+
+    pub fn effector_id(&self) -> &str {
+        &self.effector_id
+    }
+
+    pub fn issued_at_unix(&self) -> i64 {
+        self.issued_at_unix
+    }
+
+    pub fn expires_at_unix(&self) -> i64 {
+        self.expires_at_unix
+    }
+
+    /// Whether this grant may drive `effector_id` at `now_unix`.
+    pub fn check(&self, effector_id: &str, now_unix: i64) -> Result<(), String> {
+        if effector_id != self.effector_id {
+            return Err(format!(
+                "capability for {} cannot drive {effector_id}",
+                self.effector_id
+            ));
+        }
+        if now_unix < self.issued_at_unix || now_unix >= self.expires_at_unix {
+            return Err("actuation capability expired or not yet valid".into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,452 @@
+//! Downgrade resistance for policy and shard updates
+//! (HCI `HCI_RIGHTS_KERNEL_AND_DOWNGRADE_RESISTANCE`).
+//!
+//! An update is reduced to two `PolicySurface`s, the version in force and
+//! the proposed one. Every differing key is classified as tightening,
+//! neutral or loosening. Loosening is only admitted with a host-signed
+//! `DowngradeConsent` that names each loosened key; every rejection, and
+//! every consented loosening, goes to the audit stream.
+//!
+//! The host signs `consent_payload`: the artifact and versions, the host
+//! DID, both policy digests and the full diff. A signature therefore
+//! covers exactly one loosening of one artifact, value for value.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use governance::neurorights::NeurorightsProfile;
+use organiccpualn::rohmodel::RohModelShard;
+
+use crate::audit::{AuditRecord, AuditSink};
+
+/// Same plane the rights kernel uses for narrowing attempts.
+pub const AUDIT_PLANE: &str = "neuralrope.rights.kernel";
+
+const CONSENT_DOMAIN: &str = "sovereigntycore.downgrade.consent.v1";
+
+/// The comparable part of a policy artifact.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PolicySurface {
+    /// Upper limits; lower is tighter.
+    pub ceilings: BTreeMap<String, f64>,
+    /// Lower limits; higher is tighter.
+    pub floors: BTreeMap<String, f64>,
+    /// Protections; `true` is tighter.
+    pub required: BTreeMap<String, bool>,
+    /// Forbidden practices; more is tighter.
+    pub prohibitions: BTreeSet<String>,
+    /// Values with no safe direction; any change counts as loosening.
+    pub settings: BTreeMap<String, String>,
+}
+
+impl PolicySurface {
+    pub fn ceiling(mut self, key: &str, value: f64) -> Self {
+        self.ceilings.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn floor(mut self, key: &str, value: f64) -> Self {
+        self.floors.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn require(mut self, key: &str, value: bool) -> Self {
+        self.required.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn prohibit(mut self, key: &str) -> Self {
+        self.prohibitions.insert(key.to_string());
+        self
+    }
+
+    pub fn setting(mut self, key: &str, value: impl ToString) -> Self {
+        self.settings.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Band ceilings, plus axis weights as floors: lowering a weight lowers
+    /// the RoH it contributes. Axis ranges have no safe direction.
+    pub fn from_rohmodel(shard: &RohModelShard) -> Self {
+        let mut s = Self::default()
+            .ceiling("rohceiling_strict", shard.roh_ceiling_strict() as f64)
+            .ceiling("rohceiling_research", shard.roh_ceiling_research() as f64);
+        for axis in &shard.model.weights.axes {
+            s = s
+                .floor(&format!("weight.{}", axis.name), axis.weight as f64)
+                .setting(&format!("range.{}", axis.name), format!("{}..{}", axis.min, axis.max));
+        }
+        s
+    }
+
+    pub fn from_neurorights(profile: &NeurorightsProfile) -> Self {
+        Self::default()
+            .ceiling("max_pain_vas", profile.max_pain_vas as f64)
+            .ceiling("max_cognitive_load", profile.max_cognitive_load as f64)
+    }
+
+    /// sha256 of the compact JSON form, hex. All maps are ordered, so equal
+    /// surfaces always hash equal.
+    pub fn digest_hex(&self) -> String {
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        hex::encode(Sha256::digest(bytes))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeClass {
+    Tightening,
+    Neutral,
+    Loosening,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PolicyChange {
+    /// e.g. `ceiling.rohceiling_strict`, `prohibition.raw_export`.
+    pub key: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub class: ChangeClass,
+}
+
+/// Every key that differs between `current` and `proposed`, classified.
+pub fn diff(current: &PolicySurface, proposed: &PolicySurface) -> Vec<PolicyChange> {
+    let mut out = Vec::new();
+    diff_limits(&mut out, "ceiling", &current.ceilings, &proposed.ceilings, |new, old| new < old);
+    diff_limits(&mut out, "floor", &current.floors, &proposed.floors, |new, old| new > old);
+
+    for key in keys(&current.required, &proposed.required) {
+        let (before, after) = (current.required.get(key), proposed.required.get(key));
+        if before == after {
+            continue;
+        }
+        let class = match (before.copied().unwrap_or(false), after.copied().unwrap_or(false)) {
+            (true, false) => ChangeClass::Loosening,
+            (false, true) => ChangeClass::Tightening,
+            _ => ChangeClass::Neutral,
+        };
+        out.push(change("required", key, before, after, class));
+    }
+
+    for key in current.prohibitions.symmetric_difference(&proposed.prohibitions) {
+        let removed = current.prohibitions.contains(key);
+        let class = if removed {
+            ChangeClass::Loosening
+        } else {
+            ChangeClass::Tightening
+        };
+        let (before, after) = if removed {
+            (Some(&true), None)
+        } else {
+            (None, Some(&true))
+        };
+        out.push(change("prohibition", key, before, after, class));
+    }
+
+    for key in keys(&current.settings, &proposed.settings) {
+        let (before, after) = (current.settings.get(key), proposed.settings.get(key));
+        if before != after {
+            out.push(change("setting", key, before, after, ChangeClass::Loosening));
+        }
+    }
+    out
+}
+
+/// A removed limit or a non-finite new value is loosening.
+fn diff_limits(
+    out: &mut Vec<PolicyChange>,
+    prefix: &str,
+    current: &BTreeMap<String, f64>,
+    proposed: &BTreeMap<String, f64>,
+    tighter: impl Fn(f64, f64) -> bool,
+) {
+    for key in keys(current, proposed) {
+        let (before, after) = (current.get(key), proposed.get(key));
+        let class = match (before, after) {
+            (Some(b), Some(a)) if a == b => continue,
+            (_, Some(a)) if !a.is_finite() => ChangeClass::Loosening,
+            (Some(b), Some(a)) if tighter(*a, *b) => ChangeClass::Tightening,
+            (Some(_), _) => ChangeClass::Loosening,
+            (None, Some(_)) => ChangeClass::Tightening,
+            (None, None) => continue,
+        };
+        out.push(change(prefix, key, before, after, class));
+    }
+}
+
+fn keys<'a, V>(a: &'a BTreeMap<String, V>, b: &'a BTreeMap<String, V>) -> BTreeSet<&'a String> {
+    a.keys().chain(b.keys()).collect()
+}
+
+fn change<T: ToString>(
+    prefix: &str,
+    key: &str,
+    before: Option<&T>,
+    after: Option<&T>,
+    class: ChangeClass,
+) -> PolicyChange {
+    PolicyChange {
+        key: format!("{prefix}.{key}"),
+        before: before.map(ToString::to_string),
+        after: after.map(ToString::to_string),
+        class,
+    }
+}
+
+/// A proposed replacement of one policy artifact.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PolicyUpdate {
+    /// Stable artifact id, e.g. `rohmodel:bostrom-rohmodel-v2`.
+    pub artifact: String,
+    pub subjectid: String,
+    pub from_version: String,
+    pub to_version: String,
+    pub current: PolicySurface,
+    pub proposed: PolicySurface,
+}
+
+impl PolicyUpdate {
+    /// Replace the RoH model in force with `proposed`. The artifact id is
+    /// the host's rohmodel slot, so a renamed model is still a replacement.
+    pub fn rohmodel(current: &RohModelShard, proposed: &RohModelShard) -> Self {
+        Self {
+            artifact: format!("rohmodel:{}", current.subjectid),
+            subjectid: proposed.subjectid.clone(),
+            from_version: format!("{}@{}", current.model.id, current.version),
+            to_version: format!("{}@{}", proposed.model.id, proposed.version),
+            current: PolicySurface::from_rohmodel(current),
+            proposed: PolicySurface::from_rohmodel(proposed),
+        }
+    }
+}
+
+/// The exact terms a host signs to consent to a loosening.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ConsentPayload<'a> {
+    pub domain: &'static str,
+    pub host_did: &'a str,
+    pub artifact: &'a str,
+    pub from_version: &'a str,
+    pub to_version: &'a str,
+    pub current_policy_sha256: String,
+    pub proposed_policy_sha256: String,
+    /// Every change of the update, not only the loosened ones.
+    pub diff: Vec<PolicyChange>,
+    pub loosened_keys: &'a BTreeSet<String>,
+    pub signed_at_utc_ms: i64,
+}
+
+/// Canonical bytes signed for consenting to `loosened_keys` of `update`.
+pub fn consent_payload(
+    update: &PolicyUpdate,
+    loosened_keys: &BTreeSet<String>,
+    signed_at_utc_ms: i64,
+) -> Vec<u8> {
+    let payload = ConsentPayload {
+        domain: CONSENT_DOMAIN,
+        host_did: &update.subjectid,
+        artifact: &update.artifact,
+        from_version: &update.from_version,
+        to_version: &update.to_version,
+        current_policy_sha256: update.current.digest_hex(),
+        proposed_policy_sha256: update.proposed.digest_hex(),
+        diff: diff(&update.current, &update.proposed),
+        loosened_keys,
+        signed_at_utc_ms,
+    };
+    serde_json::to_vec(&payload).unwrap_or_default()
+}
+
+/// Host's explicit consent to a specific loosening.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DowngradeConsent {
+    pub artifact: String,
+    pub subjectid: String,
+    pub from_version: String,
+    pub to_version: String,
+    /// Change keys (`PolicyChange::key`) the host agrees to loosen.
+    pub loosened_keys: BTreeSet<String>,
+    pub signed_at_utc_ms: i64,
+    /// ed25519 signature over `consent_payload`, hex.
+    pub signature_hex: String,
+}
+
+impl DowngradeConsent {
+    /// Consent to every loosening in `update`, signed with the host key.
+    pub fn sign(update: &PolicyUpdate, key: &SigningKey, signed_at_utc_ms: i64) -> Self {
+        let loosened_keys: BTreeSet<String> = diff(&update.current, &update.proposed)
+            .into_iter()
+            .filter(|c| c.class == ChangeClass::Loosening)
+            .map(|c| c.key)
+            .collect();
+        let payload = consent_payload(update, &loosened_keys, signed_at_utc_ms);
+        Self {
+            artifact: update.artifact.clone(),
+            subjectid: update.subjectid.clone(),
+            from_version: update.from_version.clone(),
+            to_version: update.to_version.clone(),
+            signature_hex: hex::encode(key.sign(&payload).to_bytes()),
+            loosened_keys,
+            signed_at_utc_ms,
+        }
+    }
+}
+
+/// Checks that a downgrade consent was signed by the host for `update`.
+/// (Distinct from `alndid::ConsentVerifier`, which covers session consent.)
+pub trait DowngradeConsentVerifier {
+    fn verify(&self, update: &PolicyUpdate, consent: &DowngradeConsent) -> Result<(), String>;
+}
+
+/// Verifies consents against the one ed25519 key bound to the host DID.
+#[derive(Clone, Debug)]
+pub struct Ed25519ConsentVerifier {
+    host_did: String,
+    key: VerifyingKey,
+}
+
+impl Ed25519ConsentVerifier {
+    pub fn new(host_did: &str, key: VerifyingKey) -> Self {
+        Self {
+            host_did: host_did.to_string(),
+            key,
+        }
+    }
+
+    pub fn from_hex(host_did: &str, key_hex: &str) -> Result<Self, String> {
+        let bytes: [u8; 32] = hex::decode(key_hex)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or("host key is not 32 bytes of hex")?;
+        let key = VerifyingKey::from_bytes(&bytes).map_err(|e| format!("host key: {e}"))?;
+        Ok(Self::new(host_did, key))
+    }
+}
+
+impl DowngradeConsentVerifier for Ed25519ConsentVerifier {
+    fn verify(&self, update: &PolicyUpdate, consent: &DowngradeConsent) -> Result<(), String> {
+        if consent.subjectid != self.host_did || update.subjectid != self.host_did {
+            return Err("consent is not from the host".into());
+        }
+        let sig: [u8; 64] = hex::decode(&consent.signature_hex)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or("consent signature is not 64 bytes of hex")?;
+        let payload = consent_payload(update, &consent.loosened_keys, consent.signed_at_utc_ms);
+        self.key
+            .verify(&payload, &Signature::from_bytes(&sig))
+            .map_err(|_| "consent signature does not verify against the host key".to_string())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UpdateReview {
+    pub changes: Vec<PolicyChange>,
+    /// True if loosening changes were admitted under consent.
+    pub consented: bool,
+}
+
+impl UpdateReview {
+    pub fn loosening(&self) -> impl Iterator<Item = &PolicyChange> {
+        self.changes.iter().filter(|c| c.class == ChangeClass::Loosening)
+    }
+}
+
+pub struct DowngradeGate<'a> {
+    pub verifier: &'a dyn DowngradeConsentVerifier,
+    pub audit: &'a dyn AuditSink,
+}
+
+impl DowngradeGate<'_> {
+    /// Admit or reject `update`. Tightening and neutral updates pass;
+    /// loosening needs `consent` covering every loosened key.
+    pub fn review(
+        &self,
+        update: &PolicyUpdate,
+        consent: Option<&DowngradeConsent>,
+    ) -> Result<UpdateReview, String> {
+        let changes = diff(&update.current, &update.proposed);
+        let loosened: BTreeSet<&str> = changes
+            .iter()
+            .filter(|c| c.class == ChangeClass::Loosening)
+            .map(|c| c.key.as_str())
+            .collect();
+        if loosened.is_empty() {
+            return Ok(UpdateReview {
+                changes,
+                consented: false,
+            });
+        }
+
+        let verdict = match consent {
+            None => Err("loosening update carries no host consent".to_string()),
+            Some(c) => Self::check_consent(update, c, &loosened)
+                .and_then(|_| self.verifier.verify(update, c)),
+        };
+        let keys = loosened.iter().copied().collect::<Vec<_>>().join(",");
+        match verdict {
+            Ok(()) => {
+                self.log(update, "loosening_consented", Some(keys))
+                    .map_err(|e| format!("consented loosening not auditable: {e}"))?;
+                Ok(UpdateReview {
+                    changes,
+                    consented: true,
+                })
+            }
+            Err(reason) => Err(self.refuse(update, &reason, Some(&keys))),
+        }
+    }
+
+    /// Record `update` as rejected for `reason` without reviewing its diff,
+    /// e.g. a shard for another subject or one that fails its own
+    /// invariants. Returns the error to hand back to the caller.
+    pub fn refuse(&self, update: &PolicyUpdate, reason: &str, keys: Option<&str>) -> String {
+        let detail = match keys {
+            Some(keys) => format!("{reason}; {keys}"),
+            None => reason.to_string(),
+        };
+        match self.log(update, "update_rejected", Some(detail)) {
+            Ok(()) => reason.to_string(),
+            Err(e) => format!("{reason} (audit failed: {e})"),
+        }
+    }
+
+    fn check_consent(
+        update: &PolicyUpdate,
+        consent: &DowngradeConsent,
+        loosened: &BTreeSet<&str>,
+    ) -> Result<(), String> {
+        if consent.subjectid != update.subjectid {
+            return Err("consent is not from the host".into());
+        }
+        if consent.artifact != update.artifact
+            || consent.from_version != update.from_version
+            || consent.to_version != update.to_version
+        {
+            return Err("consent is for a different update".into());
+        }
+        let uncovered: Vec<&str> = loosened
+            .iter()
+            .copied()
+            .filter(|k| !consent.loosened_keys.contains(*k))
+            .collect();
+        if !uncovered.is_empty() {
+            return Err(format!("consent does not cover {}", uncovered.join(",")));
+        }
+        Ok(())
+    }
+
+    fn log(&self, update: &PolicyUpdate, action: &str, detail: Option<String>) -> std::io::Result<()> {
+        self.audit.append(&AuditRecord::now(
+            AUDIT_PLANE,
+            action,
+            update.subjectid.as_str(),
+            format!("{}@{}->{}", update.artifact, update.from_version, update.to_version),
+            detail,
+        ))
+    }
+}
@@ -0,0 +1,54 @@
+//! Typed view of EVOLVE/SMART token documents as stored under `policies/`
+//! (see `policies/bostrom-evolve-token.schema.json`).
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::riskofharm::RohBand;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TokenKind {
+    Smart,
+    Evolve,
+}
+
+/// Physiological floor/ceiling a research token must hold under.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PhysioGuardLimits {
+    pub min_hrv_sdnn_ms: f32,
+    pub max_fatigueindex: f32,
+    pub max_pain_vas: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EvolveToken {
+    pub id: String,
+    pub subjectid: String,
+    pub kind: TokenKind,
+    pub scope: Vec<String>,
+    pub roh_band: RohBand,
+    pub maxeffectsize: f32,
+    pub validfrom: DateTime<Utc>,
+    pub validuntil: DateTime<Utc>,
+    #[serde(default)]
+    pub physioguard: Option<PhysioGuardLimits>,
+}
+
+impl EvolveToken {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scope.iter().any(|s| s == scope)
+    }
+
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        self.validfrom <= now && now <= self.validuntil
+    }
+}
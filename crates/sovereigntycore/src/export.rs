@@ -0,0 +1,347 @@
+//! Export gateway for anything leaving the host (proof artifacts,
+//! manifests, telemetry aggregates), enforcing HCI
+//! `HCI_DATA_RIGHTS_AND_KO_EXPORT` and the corridor `noraweegexport` clause.
+//!
+//! Every leaf field of an outbound payload is classified as raw, derived or
+//! banded, with the rules for its `ExportKind`. Fields no rule covers count
+//! as raw. Raw fields only leave under a
+//! matching, revocable, unexpired consent for the same subject and
+//! destination; otherwise the whole export is blocked. Every attempt is
+//! written to the audit stream, and an attempt that cannot be audited does
+//! not leave.
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::audit::{AuditRecord, AuditSink};
+
+pub const AUDIT_PLANE: &str = "hci.export";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataClass {
+    /// Samples, traces or balances (EEG, gaze, raw physiology).
+    Raw,
+    /// Hashes, ids, counts and other values that cannot reconstruct raw state.
+    Derived,
+    /// Coarse labels such as `LifeforceBand` or `EcoBand`.
+    Banded,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportKind {
+    ProofArtifact,
+    /// `organic_cpu::evidence::bundle::ProofBundle`.
+    ProofBundle,
+    Manifest,
+    TelemetryAggregate,
+    Other(String),
+}
+
+/// True if `path` is `pattern` or lies under it (`event` covers `event.domain`,
+/// `upgrades[]` covers `upgrades[].upgrade_id`).
+fn covers(pattern: &str, path: &str) -> bool {
+    path == pattern
+        || (path.starts_with(pattern) && path.as_bytes().get(pattern.len()) == Some(&b'.'))
+}
+
+/// Dotted-path rules; the longest matching pattern wins.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FieldClassifier {
+    rules: BTreeMap<String, DataClass>,
+}
+
+impl FieldClassifier {
+    pub fn rule(mut self, pattern: &str, class: DataClass) -> Self {
+        self.rules.insert(pattern.to_string(), class);
+        self
+    }
+
+    /// Every rule of `inner`, applied under `prefix`.
+    pub fn nested(mut self, prefix: &str, inner: &FieldClassifier) -> Self {
+        for (pattern, class) in &inner.rules {
+            self.rules.insert(format!("{prefix}.{pattern}"), *class);
+        }
+        self
+    }
+
+    /// Rules for `kind`. `Other` has none, so all of its fields are raw.
+    pub fn for_kind(kind: &ExportKind) -> Self {
+        match kind {
+            ExportKind::ProofArtifact => Self::proof_artifact(),
+            ExportKind::ProofBundle => Self::proof_bundle(),
+            ExportKind::Manifest => Self::manifest(),
+            ExportKind::TelemetryAggregate => Self::telemetry_aggregate(),
+            ExportKind::Other(_) => Self::default(),
+        }
+    }
+
+    /// Rules for `organic_cpu::evidence::BiophysicalProofArtifact`.
+    pub fn proof_artifact() -> Self {
+        Self::default()
+            .rule("host", DataClass::Derived)
+            .rule("lorentz_ts", DataClass::Derived)
+            .rule("utc_ms", DataClass::Derived)
+            .rule("origin_plane", DataClass::Derived)
+            .rule("event", DataClass::Derived)
+            .rule("pre_state_hash_hex", DataClass::Derived)
+            .rule("post_state_hash_hex", DataClass::Derived)
+            .rule("consensus_seqno", DataClass::Derived)
+            .rule("lifeforce_band", DataClass::Banded)
+            .rule("lifeforce_ok", DataClass::Derived)
+            .rule("eco_band", DataClass::Banded)
+            .rule("eco_cost_hint", DataClass::Derived)
+            .rule("pain_band", DataClass::Banded)
+            .rule("metabolic_mode", DataClass::Derived)
+            .rule("consent_proof_hash_hex", DataClass::Derived)
+            .rule("provenance_hash_hex", DataClass::Derived)
+            .rule("civic_audit_id", DataClass::Derived)
+    }
+
+    /// Rules for a proof bundle: link fields, seals and key rotations of
+    /// each entry, and artifacts under `proof_artifact`.
+    pub fn proof_bundle() -> Self {
+        let link_fields = [
+            "bundle",
+            "kind",
+            "seq",
+            "prev_hash",
+            "entry_digest",
+            "hash",
+            "signature",
+            "salts",
+            "commitments",
+            "did",
+            "new_key_hex",
+            "effective_from_seqno",
+            "possession_sig_hex",
+        ];
+        link_fields
+            .iter()
+            .fold(Self::default(), |c, f| c.rule(&format!("entries[].{f}"), DataClass::Derived))
+            .rule("format", DataClass::Derived)
+            .rule("source_head", DataClass::Derived)
+            .nested("entries[].artifact", &Self::proof_artifact())
+    }
+
+    /// Rules for a daily BCI window aggregate (per-channel min and max).
+    pub fn telemetry_aggregate() -> Self {
+        [
+            "eeg_load_min",
+            "eeg_load_max",
+            "hrv_min",
+            "hrv_max",
+            "temp_c_min",
+            "temp_c_max",
+            "duty_cycle_min",
+            "duty_cycle_max",
+        ]
+        .iter()
+        .fold(Self::default(), |c, f| c.rule(f, DataClass::Derived))
+    }
+
+    /// Rules for the daily evolution manifest written by
+    /// `bioscale-evolution-cli`.
+    pub fn manifest() -> Self {
+        Self::default()
+            .rule("date", DataClass::Derived)
+            .rule("host_did", DataClass::Derived)
+            .rule("bostrom_address", DataClass::Derived)
+            .rule("git_commit", DataClass::Derived)
+            .rule("crate_versions[]", DataClass::Derived)
+            .rule("upgrades[]", DataClass::Derived)
+            .rule("aln_particles[]", DataClass::Derived)
+            .rule("metrics_schema_version", DataClass::Derived)
+            .rule("proof_artifacts", DataClass::Derived)
+            .nested("bci_snapshots", &Self::telemetry_aggregate())
+    }
+
+    pub fn classify(&self, path: &str) -> DataClass {
+        self.rules
+            .iter()
+            .filter(|(pattern, _)| covers(pattern, path))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, class)| *class)
+            .unwrap_or(DataClass::Raw)
+    }
+}
+
+/// Host consent to export specific raw fields to one destination.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportConsent {
+    pub consent_id: String,
+    pub subjectid: String,
+    pub destination: String,
+    /// Field patterns, same syntax as `FieldClassifier` rules.
+    pub fields: Vec<String>,
+    pub valid_until_utc_ms: i64,
+    pub revocable: bool,
+}
+
+/// Source of currently granted (not revoked) export consents.
+pub trait ExportConsentLookup {
+    fn active_consents(&self, subjectid: &str) -> Vec<ExportConsent>;
+}
+
+/// In-memory consent set; `revoke` takes effect for the next export.
+#[derive(Debug, Default)]
+pub struct ExportConsentSet {
+    consents: RwLock<BTreeMap<String, ExportConsent>>,
+}
+
+impl ExportConsentSet {
+    /// Consents from a JSON array of `ExportConsent`.
+    pub fn load(path: &std::path::Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        let consents: Vec<ExportConsent> =
+            serde_json::from_str(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+        let set = Self::default();
+        for consent in consents {
+            set.grant(consent);
+        }
+        Ok(set)
+    }
+
+    pub fn grant(&self, consent: ExportConsent) {
+        let mut map = self.consents.write().unwrap_or_else(|e| e.into_inner());
+        map.insert(consent.consent_id.clone(), consent);
+    }
+
+    pub fn revoke(&self, consent_id: &str) -> bool {
+        let mut map = self.consents.write().unwrap_or_else(|e| e.into_inner());
+        map.remove(consent_id).is_some()
+    }
+}
+
+impl ExportConsentLookup for ExportConsentSet {
+    fn active_consents(&self, subjectid: &str) -> Vec<ExportConsent> {
+        let map = self.consents.read().unwrap_or_else(|e| e.into_inner());
+        map.values().filter(|c| c.subjectid == subjectid).cloned().collect()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportRequest {
+    pub subjectid: String,
+    pub destination: String,
+    pub kind: ExportKind,
+    pub payload: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FieldVerdict {
+    pub path: String,
+    pub class: DataClass,
+    /// Consent that admitted a raw field.
+    pub consent_id: Option<String>,
+}
+
+pub struct ExportGateway<'a> {
+    pub classifier: &'a FieldClassifier,
+    pub consents: &'a dyn ExportConsentLookup,
+    pub audit: &'a dyn AuditSink,
+}
+
+impl ExportGateway<'_> {
+    /// Classify and admit `request`, or block it. Either way it is audited.
+    pub fn export(&self, request: &ExportRequest, now_utc_ms: i64) -> Result<Vec<FieldVerdict>, String> {
+        let consents: Vec<ExportConsent> = self
+            .consents
+            .active_consents(&request.subjectid)
+            .into_iter()
+            .filter(|c| {
+                c.revocable
+                    && c.subjectid == request.subjectid
+                    && c.destination == request.destination
+                    && now_utc_ms <= c.valid_until_utc_ms
+            })
+            .collect();
+
+        let mut paths = Vec::new();
+        leaf_paths(&request.payload, String::new(), &mut paths);
+        paths.sort();
+        paths.dedup();
+
+        let mut verdicts = Vec::with_capacity(paths.len());
+        let mut blocked = Vec::new();
+        for path in paths {
+            let class = self.classifier.classify(&path);
+            let consent_id = if class == DataClass::Raw {
+                let found = consents
+                    .iter()
+                    .find(|c| c.fields.iter().any(|p| covers(p, &path)))
+                    .map(|c| c.consent_id.clone());
+                if found.is_none() {
+                    blocked.push(path.clone());
+                }
+                found
+            } else {
+                None
+            };
+            verdicts.push(FieldVerdict {
+                path,
+                class,
+                consent_id,
+            });
+        }
+
+        let subject = format!("{:?}:{}", request.kind, request.subjectid);
+        if !blocked.is_empty() {
+            let reason = format!("raw fields without export consent: {}", blocked.join(","));
+            return match self.log("export_blocked", request, subject, Some(reason.clone())) {
+                Ok(()) => Err(reason),
+                Err(e) => Err(format!("{reason} (audit failed: {e})")),
+            };
+        }
+
+        let consented: Vec<&str> = verdicts.iter().filter_map(|v| v.consent_id.as_deref()).collect();
+        let detail = (!consented.is_empty()).then(|| format!("raw under consent {}", consented.join(",")));
+        self.log("export_allowed", request, subject, detail)
+            .map_err(|e| format!("export not auditable: {e}"))?;
+        Ok(verdicts)
+    }
+
+    fn log(
+        &self,
+        action: &str,
+        request: &ExportRequest,
+        subject: String,
+        detail: Option<String>,
+    ) -> std::io::Result<()> {
+        self.audit.append(&AuditRecord::now(
+            AUDIT_PLANE,
+            action,
+            request.destination.as_str(),
+            subject,
+            detail,
+        ))
+    }
+}
+
+/// Dotted paths of all leaves. An array holding objects is descended as
+/// `name[]`, once per element; other arrays are leaves, so a sample vector
+/// is one field. A non-object payload is a single leaf with an empty path.
+fn leaf_paths(value: &Value, prefix: String, out: &mut Vec<String>) {
+    match value {
+        Value::Array(items) if items.iter().any(Value::is_object) => {
+            for item in items {
+                leaf_paths(item, format!("{prefix}[]"), out);
+            }
+        }
+        Value::Object(map) if !map.is_empty() || prefix.is_empty() => {
+            for (k, v) in map {
+                let path = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{prefix}.{k}")
+                };
+                leaf_paths(v, path, out);
+            }
+        }
+        _ => out.push(prefix),
+    }
+}
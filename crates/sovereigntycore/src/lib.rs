@@ -1,11 +1,26 @@
-use autonomysafety::{polytope::CorridorPolytope, roh::RiskOfHarm};
+use autonomysafety::polytope::CorridorPolytope;
 use governance::{neurorights::NeurorightsProfile, stake::StakePolicy, token::EvolveToken};
 use serde::{Deserialize, Serialize};
 
+pub mod audit;
+pub mod capability;
+pub mod downgrade;
+pub mod evolvetoken;
+pub mod export;
+pub mod riskofharm;
+pub mod tokenguard;
+
+use capability::ActuationCapability;
+use downgrade::{DowngradeConsent, DowngradeGate, PolicyUpdate, UpdateReview};
+use organiccpualn::rohmodel::RohModelShard;
+use riskofharm::{RiskOfHarm, RohTransition};
+use tokenguard::{BioState, HrvSample};
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NormalizedBioState {
     pub metrics: autonomysafety::polytope::NormalizedMetrics,
-    pub roh: RiskOfHarm,
+    /// Current RoH score, as computed by `SovereigntyCore::roh`.
+    pub roh: f32,
     pub pain_vas: f32,
     pub cognitive_load: f32,
 }
@@ -27,41 +42,133 @@ pub struct Proposal {
     pub projected_metrics: autonomysafety::polytope::NormalizedMetrics,
 }
 
+/// Stable id of the rule behind a denial. Explanations and audits key on
+/// this, never on the message text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DenyClause {
+    SubjectNotHost,
+    PainEnvelope,
+    CognitiveEnvelope,
+    CorridorPolytope,
+    EvolveTokenRequired,
+    TokenSubject,
+    TokenBand,
+    TokenScope,
+    TokenValidity,
+    Physioguard,
+    ResearchKind,
+    RohStrictIncrease,
+    RohStrictCeiling,
+    EffectBound,
+    EffectSize,
+    RohResearchCeiling,
+    RohNotFinite,
+}
+
+impl DenyClause {
+    pub const ALL: [DenyClause; 17] = [
+        DenyClause::SubjectNotHost,
+        DenyClause::PainEnvelope,
+        DenyClause::CognitiveEnvelope,
+        DenyClause::CorridorPolytope,
+        DenyClause::EvolveTokenRequired,
+        DenyClause::TokenSubject,
+        DenyClause::TokenBand,
+        DenyClause::TokenScope,
+        DenyClause::TokenValidity,
+        DenyClause::Physioguard,
+        DenyClause::ResearchKind,
+        DenyClause::RohStrictIncrease,
+        DenyClause::RohStrictCeiling,
+        DenyClause::EffectBound,
+        DenyClause::EffectSize,
+        DenyClause::RohResearchCeiling,
+        DenyClause::RohNotFinite,
+    ];
+
+    /// Clause id, e.g. `subject_not_host`.
+    pub fn id(self) -> &'static str {
+        match self {
+            DenyClause::SubjectNotHost => "subject_not_host",
+            DenyClause::PainEnvelope => "pain_envelope",
+            DenyClause::CognitiveEnvelope => "cognitive_envelope",
+            DenyClause::CorridorPolytope => "corridor_polytope",
+            DenyClause::EvolveTokenRequired => "evolve_token_required",
+            DenyClause::TokenSubject => "token_subject",
+            DenyClause::TokenBand => "token_band",
+            DenyClause::TokenScope => "token_scope",
+            DenyClause::TokenValidity => "token_validity",
+            DenyClause::Physioguard => "physioguard",
+            DenyClause::ResearchKind => "research_kind",
+            DenyClause::RohStrictIncrease => "roh_strict_increase",
+            DenyClause::RohStrictCeiling => "roh_strict_ceiling",
+            DenyClause::EffectBound => "effect_bound",
+            DenyClause::EffectSize => "effect_size",
+            DenyClause::RohResearchCeiling => "roh_research_ceiling",
+            DenyClause::RohNotFinite => "roh_not_finite",
+        }
+    }
+}
+
+/// Why a proposal was denied: the rule and a human-readable message.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Denial {
+    pub clause: DenyClause,
+    pub message: String,
+}
+
+impl Denial {
+    pub fn new(clause: DenyClause, message: impl Into<String>) -> Self {
+        Self {
+            clause,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Denial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<Denial> for String {
+    fn from(d: Denial) -> Self {
+        d.message
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Decision {
     Allowed,
-    Denied(String),
+    Denied(Denial),
+}
+
+impl Decision {
+    fn deny(clause: DenyClause, message: &str) -> Self {
+        Decision::Denied(Denial::new(clause, message))
+    }
 }
 
 pub struct SovereigntyCore {
     pub neurorights: NeurorightsProfile,
     pub stake: StakePolicy,
     pub corridor: CorridorPolytope,
+    /// The one RoH engine; strict and research ceilings come only from here.
+    pub roh: RiskOfHarm,
 }
 
 impl SovereigntyCore {
-    fn check_neurorights(&self, state: &NormalizedBioState) -> Result<(), String> {
+    fn check_neurorights(&self, state: &NormalizedBioState) -> Result<(), Denial> {
         if state.pain_vas > self.neurorights.max_pain_vas {
-            return Err("Pain envelope exceeded".into());
+            return Err(Denial::new(DenyClause::PainEnvelope, "Pain envelope exceeded"));
         }
         if state.cognitive_load > self.neurorights.max_cognitive_load {
-            return Err("Cognitive load envelope exceeded".into());
-        }
-        Ok(())
-    }
-
-    fn check_physioguard(
-        guard: &governance::token::PhysioGuard,
-        metrics: &autonomysafety::polytope::NormalizedMetrics,
-    ) -> Result<(), String> {
-        if metrics.hrv_sdnn < guard.min_hrv_sdnn {
-            return Err("HRV below physioguard baseline".into());
-        }
-        if metrics.emg_tension > guard.max_emg_tension {
-            return Err("EMG tension above physioguard limit".into());
-        }
-        if metrics.fatigue_index > guard.max_fatigue_index {
-            return Err("Fatigue index above physioguard limit".into());
+            return Err(Denial::new(
+                DenyClause::CognitiveEnvelope,
+                "Cognitive load envelope exceeded",
+            ));
         }
         Ok(())
     }
@@ -74,7 +181,7 @@ impl SovereigntyCore {
         now_unix: i64,
     ) -> Decision {
         if proposal.subjectid != self.stake.subjectid {
-            return Decision::Denied("Subject is not host".into());
+            return Decision::deny(DenyClause::SubjectNotHost, "Subject is not host");
         }
 
         if let Err(e) = self.check_neurorights(state) {
@@ -82,52 +189,100 @@ impl SovereigntyCore {
         }
 
         if !self.corridor.is_safe(&proposal.projected_metrics) {
-            return Decision::Denied("Projected state leaves safety corridor".into());
+            return Decision::deny(
+                DenyClause::CorridorPolytope,
+                "Projected state leaves safety corridor",
+            );
         }
 
         // strict band
-        let strict_candidate = state.roh.evaluate_strict(proposal.roh_delta);
-        if let Ok(new_roh) = strict_candidate {
+        let transition = RohTransition::from_delta(state.roh, proposal.roh_delta);
+        if self.roh.evaluate_strict(transition).is_ok() {
             return Decision::Allowed;
         }
 
         // research band path requires EVOLVE token
         let t = match token {
             None => {
-                return Decision::Denied("RoH override requires EVOLVE token".into());
+                return Decision::deny(
+                    DenyClause::EvolveTokenRequired,
+                    "RoH override requires EVOLVE token",
+                );
             }
             Some(tok) => tok,
         };
 
-        if t.subjectid != proposal.subjectid {
-            return Decision::Denied("Token subject mismatch".into());
-        }
-        if t.roh_band != "research" {
-            return Decision::Denied("Token roh_band must be 'research'".into());
-        }
-        if !t.scope.iter().any(|s| s == "highrisk_research") {
-            return Decision::Denied("Token missing highrisk_research scope".into());
-        }
-        if now_unix < t.valid_from || now_unix > t.valid_until {
-            return Decision::Denied("Token expired or not yet valid".into());
-        }
-
-        if let Err(e) = Self::check_physioguard(&t.physioguard, &proposal.projected_metrics) {
-            return Decision::Denied(e);
-        }
-
         match proposal.kind {
             ProposalKind::ParamNudge | ProposalKind::ThresholdShift => {}
             _ => {
-                return Decision::Denied(
-                    "High-risk research allows only ParamNudge or ThresholdShift".into(),
+                return Decision::deny(
+                    DenyClause::ResearchKind,
+                    "High-risk research allows only ParamNudge or ThresholdShift",
                 )
             }
         }
 
-        match state.roh.evaluate_research(proposal.roh_delta, t.max_effectsize) {
-            Ok(_new_roh) => Decision::Allowed,
+        let m = &proposal.projected_metrics;
+        let projected = BioState {
+            hrv: HrvSample { sdnn_ms: m.hrv_sdnn },
+            fatigueindex: m.fatigue_index,
+            emg_tension: m.emg_tension,
+            pain_vas: state.pain_vas,
+        };
+        match tokenguard::enforce_research_band(
+            t,
+            &proposal.subjectid,
+            now_unix,
+            proposal.roh_delta,
+            &self.roh,
+            transition,
+            &projected,
+        ) {
+            Ok(()) => Decision::Allowed,
             Err(e) => Decision::Denied(e),
         }
     }
+
+    /// Load a new RoH model. The swap goes through `gate`: tightening is
+    /// admitted as is, loosening only under host-signed `consent`, and the
+    /// shard must satisfy its own invariants either way. A shard for another
+    /// subject or with broken invariants is refused through the gate too,
+    /// so every refusal reaches its audit sink.
+    pub fn load_rohmodel(
+        &mut self,
+        gate: &DowngradeGate<'_>,
+        proposed: RohModelShard,
+        consent: Option<&DowngradeConsent>,
+    ) -> Result<UpdateReview, String> {
+        let update = PolicyUpdate::rohmodel(self.roh.model(), &proposed);
+        if proposed.subjectid != self.stake.subjectid {
+            return Err(gate.refuse(&update, "RoH model is not for the host", None));
+        }
+        let next = RiskOfHarm::new(proposed).map_err(|e| gate.refuse(&update, &e, None))?;
+        let review = gate.review(&update, consent)?;
+        self.roh = next;
+        Ok(review)
+    }
+
+    /// Evaluate and, only on `Allowed`, mint the capability effectors require.
+    /// The capability drives `effector_id` once and expires after
+    /// `capability::CAPABILITY_TTL_SECS`.
+    pub fn authorize(
+        &self,
+        state: &NormalizedBioState,
+        proposal: &Proposal,
+        token: Option<&EvolveToken>,
+        effector_id: &str,
+        now_unix: i64,
+    ) -> Result<ActuationCapability, String> {
+        match self.evaluate(state, proposal, token, now_unix) {
+            Decision::Allowed => Ok(ActuationCapability::mint(
+                &proposal.id,
+                &proposal.subjectid,
+                effector_id,
+                now_unix,
+            )),
+            Decision::Denied(denial) => Err(denial.message),
+        }
+    }
 }
@@ -1,8 +1,59 @@
+//! Single RoH engine shared by `SovereigntyCore::evaluate` and `tokenguard`.
+//!
+//! Callers may express a candidate step either as `(current, delta)` or as
+//! `(before, after)` inputs; both are normalized into a `RohTransition` and
+//! admitted by the same rule, so the strict and research ceilings always
+//! come from one `RohModelShard`.
+
+use organiccpualn::rohmodel::{RohInputs, RohModelShard};
+use serde::{Deserialize, Serialize};
+
+use crate::{Denial, DenyClause};
+
+/// Which ceiling a transition is checked against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RohBand {
+    Strict,
+    Research,
+}
+
+/// A candidate RoH step, independent of how the caller computed it.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RohTransition {
+    pub before: f32,
+    pub after: f32,
+}
+
+impl RohTransition {
+    pub fn from_delta(current: f32, delta: f32) -> Self {
+        Self {
+            before: current,
+            after: current + delta,
+        }
+    }
+
+    pub fn delta(&self) -> f32 {
+        self.after - self.before
+    }
+}
+
 pub struct RiskOfHarm {
     model: RohModelShard,
 }
 
 impl RiskOfHarm {
+    /// Wrap a model shard; the shard must satisfy its own invariants.
+    pub fn new(model: RohModelShard) -> Result<Self, String> {
+        model.validate_invariants()?;
+        Ok(Self { model })
+    }
+
+    /// The model in force, e.g. as the `current` side of a downgrade review.
+    pub fn model(&self) -> &RohModelShard {
+        &self.model
+    }
+
     pub fn ceiling_strict(&self) -> f32 {
         self.model.roh_ceiling_strict()
     }
@@ -10,15 +61,94 @@ impl RiskOfHarm {
         self.model.roh_ceiling_research()
     }
 
-    pub fn check_normal(&self, before: RohInputs, after: RohInputs) -> bool {
-        let roh_before = self.model.compute_roh(before);
-        let roh_after  = self.model.compute_roh(after);
-        roh_after <= roh_before && roh_after <= self.ceiling_strict()
+    pub fn ceiling(&self, band: RohBand) -> f32 {
+        match band {
+            RohBand::Strict => self.ceiling_strict(),
+            RohBand::Research => self.ceiling_research(),
+        }
+    }
+
+    pub fn compute(&self, inputs: &RohInputs) -> f32 {
+        self.model.compute_roh(inputs)
+    }
+
+    /// Strict band: RoH must not increase and must stay under the strict ceiling.
+    /// Returns the admitted post-step RoH.
+    pub fn evaluate_strict(&self, t: RohTransition) -> Result<f32, Denial> {
+        Self::check_finite(t)?;
+        if t.after > t.before {
+            return Err(Denial::new(
+                DenyClause::RohStrictIncrease,
+                "RoH may not increase in strict band",
+            ));
+        }
+        if t.after > self.ceiling(RohBand::Strict) {
+            return Err(Denial::new(
+                DenyClause::RohStrictCeiling,
+                "roh_after exceeds strict ceiling",
+            ));
+        }
+        Ok(t.after)
+    }
+
+    /// Research band: `effect` must be within the token's `max_effectsize`
+    /// and RoH must stay under the research ceiling. Increases are allowed.
+    /// A non-finite or negative `max_effectsize` is rejected outright, so a
+    /// NaN bound cannot make every comparison pass.
+    pub fn evaluate_research(
+        &self,
+        t: RohTransition,
+        effect: f32,
+        max_effectsize: f32,
+    ) -> Result<f32, Denial> {
+        Self::check_finite(t)?;
+        if !max_effectsize.is_finite() || max_effectsize < 0.0 {
+            return Err(Denial::new(
+                DenyClause::EffectBound,
+                "token.maxeffectsize is not a finite, non-negative bound",
+            ));
+        }
+        if !effect.is_finite() || effect.abs() > max_effectsize {
+            return Err(Denial::new(
+                DenyClause::EffectSize,
+                "effect size exceeds token.maxeffectsize",
+            ));
+        }
+        if t.after > self.ceiling(RohBand::Research) {
+            return Err(Denial::new(
+                DenyClause::RohResearchCeiling,
+                "roh_after exceeds research ceiling",
+            ));
+        }
+        Ok(t.after)
+    }
+
+    pub fn check_normal(&self, before: &RohInputs, after: &RohInputs) -> bool {
+        self.evaluate_strict(self.transition(before, after)).is_ok()
+    }
+
+    pub fn check_research(
+        &self,
+        before: &RohInputs,
+        after: &RohInputs,
+        effect: f32,
+        max_effectsize: f32,
+    ) -> bool {
+        self.evaluate_research(self.transition(before, after), effect, max_effectsize)
+            .is_ok()
+    }
+
+    pub fn transition(&self, before: &RohInputs, after: &RohInputs) -> RohTransition {
+        RohTransition {
+            before: self.compute(before),
+            after: self.compute(after),
+        }
     }
 
-    pub fn check_research(&self, before: RohInputs, after: RohInputs) -> bool {
-        let roh_before = self.model.compute_roh(before);
-        let roh_after  = self.model.compute_roh(after);
-        roh_after <= self.ceiling_research()
+    fn check_finite(t: RohTransition) -> Result<(), Denial> {
+        if !t.before.is_finite() || !t.after.is_finite() {
+            return Err(Denial::new(DenyClause::RohNotFinite, "RoH transition is not finite"));
+        }
+        Ok(())
     }
 }
@@ -1,27 +1,138 @@
-pub fn enforce_research_band(
-    token: &EvolveToken,
+use crate::evolvetoken::EvolveToken;
+use crate::riskofharm::{RiskOfHarm, RohBand, RohTransition};
+use crate::{Denial, DenyClause};
+
+#[derive(Clone, Debug)]
+pub struct HrvSample {
+    pub sdnn_ms: f32,
+}
+
+/// Host physiology snapshot checked against a token's physioguard.
+#[derive(Clone, Debug)]
+pub struct BioState {
+    pub hrv: HrvSample,
+    pub fatigueindex: f32,
+    pub emg_tension: f32,
+    pub pain_vas: f32,
+}
+
+/// What the research gate reads from an EVOLVE token. Implemented for the
+/// policy-file token and for the governance token `SovereigntyCore::evaluate`
+/// takes, so both go through `enforce_research_band`.
+pub trait ResearchToken {
+    fn subjectid(&self) -> &str;
+    /// Validity window in unix seconds, inclusive.
+    fn valid_window(&self) -> (i64, i64);
+    fn is_research_band(&self) -> bool;
+    fn has_scope(&self, scope: &str) -> bool;
+    fn max_effectsize(&self) -> f32;
+    /// Whether `biostate` is inside the token's physioguard.
+    fn physioguard_holds(&self, biostate: &BioState) -> Result<(), Denial>;
+}
+
+impl ResearchToken for EvolveToken {
+    fn subjectid(&self) -> &str {
+        &self.subjectid
+    }
+
+    fn valid_window(&self) -> (i64, i64) {
+        (self.validfrom.timestamp(), self.validuntil.timestamp())
+    }
+
+    fn is_research_band(&self) -> bool {
+        self.roh_band == RohBand::Research
+    }
+
+    fn has_scope(&self, scope: &str) -> bool {
+        EvolveToken::has_scope(self, scope)
+    }
+
+    fn max_effectsize(&self) -> f32 {
+        self.maxeffectsize
+    }
+
+    fn physioguard_holds(&self, biostate: &BioState) -> Result<(), Denial> {
+        let guard = self.physioguard.as_ref().ok_or_else(|| {
+            Denial::new(DenyClause::Physioguard, "research token missing physioguard")
+        })?;
+        if !(biostate.hrv.sdnn_ms >= guard.min_hrv_sdnn_ms
+            && biostate.fatigueindex <= guard.max_fatigueindex
+            && biostate.pain_vas <= guard.max_pain_vas)
+        {
+            return Err(Denial::new(DenyClause::Physioguard, "physioguard thresholds violated"));
+        }
+        Ok(())
+    }
+}
+
+impl ResearchToken for governance::token::EvolveToken {
+    fn subjectid(&self) -> &str {
+        &self.subjectid
+    }
+
+    fn valid_window(&self) -> (i64, i64) {
+        (self.valid_from, self.valid_until)
+    }
+
+    fn is_research_band(&self) -> bool {
+        self.roh_band == "research"
+    }
+
+    fn has_scope(&self, scope: &str) -> bool {
+        self.scope.iter().any(|s| s == scope)
+    }
+
+    fn max_effectsize(&self) -> f32 {
+        self.max_effectsize
+    }
+
+    fn physioguard_holds(&self, biostate: &BioState) -> Result<(), Denial> {
+        let guard = &self.physioguard;
+        let fail = |message| Err(Denial::new(DenyClause::Physioguard, message));
+        if biostate.hrv.sdnn_ms < guard.min_hrv_sdnn {
+            return fail("HRV below physioguard baseline");
+        }
+        if biostate.emg_tension > guard.max_emg_tension {
+            return fail("EMG tension above physioguard limit");
+        }
+        if biostate.fatigueindex > guard.max_fatigue_index {
+            return fail("Fatigue index above physioguard limit");
+        }
+        Ok(())
+    }
+}
+
+/// Research-band gate for EVOLVE tokens. The token must be issued to
+/// `subjectid` and valid at `now_unix` (inclusive window). The effect-size
+/// and RoH ceiling checks are delegated to `RiskOfHarm::evaluate_research`.
+pub fn enforce_research_band<T: ResearchToken + ?Sized>(
+    token: &T,
+    subjectid: &str,
+    now_unix: i64,
     effect_l2: f32,
-    roh_after: f32,
-    roh_ceiling_research: f32,
+    roh: &RiskOfHarm,
+    transition: RohTransition,
     biostate: &BioState,
-) -> Result<(), String> {
-    if token.roh_band != RohBand::Research {
-        return Err("RoH override requires roh_band=research".into());
-    }
-    if !token.scope.contains(&"highrisk_research".to_string()) {
-        return Err("Token scope missing highrisk_research".into());
+) -> Result<(), Denial> {
+    if token.subjectid() != subjectid {
+        return Err(Denial::new(DenyClause::TokenSubject, "Token subject mismatch"));
     }
-    if effect_l2 > token.maxeffectsize {
-        return Err("effect size exceeds token.maxeffectsize".into());
+    let (valid_from, valid_until) = token.valid_window();
+    if now_unix < valid_from || now_unix > valid_until {
+        return Err(Denial::new(DenyClause::TokenValidity, "Token expired or not yet valid"));
     }
-    if roh_after > roh_ceiling_research {
-        return Err("roh_after exceeds research ceiling".into());
+    if !token.is_research_band() {
+        return Err(Denial::new(
+            DenyClause::TokenBand,
+            "RoH override requires roh_band=research",
+        ));
     }
-    if biostate.hrv.sdnn_ms < token.physioguard.min_hrv_sdnn_ms
-        || biostate.fatigueindex > token.physioguard.max_fatigueindex
-        || biostate.pain_vas > token.physioguard.max_pain_vas
-    {
-        return Err("physioguard thresholds violated".into());
+    if !token.has_scope("highrisk_research") {
+        return Err(Denial::new(
+            DenyClause::TokenScope,
+            "Token scope missing highrisk_research",
+        ));
     }
-    Ok(())
+    roh.evaluate_research(transition, effect_l2, token.max_effectsize())?;
+    token.physioguard_holds(biostate)
 }
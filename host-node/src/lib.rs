@@ -9,8 +9,10 @@ use organic_cpu::evidence::{
     MetabolicConsentMode,
     PainBand,
     ProofEmitter,
+    ProofResult,
     current_utc_ms,
 };
+use organic_cpu::evidence::outbox::ProofOutbox;
 use crate::chain::biophysicalruntime::{
     BioTokenState,
     LorentzTimestamp,
@@ -29,8 +31,10 @@ where
     hostid: alndid::ALNDID,
     runtime: Arc<BiophysicalRuntime<D, C, HC>>,
     storage: HostStorage,
-    // NEW:
+    // NEW: one emitter; fan out, filter or buffer with `evidence::compose`.
     proof_emitter: Arc<dyn ProofEmitter>,
+    // Artifacts written ahead of each frame commit, for re-emission.
+    proof_outbox: ProofOutbox,
     // ...
 }
 
@@ -46,6 +50,7 @@ where
         runtime: BiophysicalRuntime<D, C, HC>,
         initial_state: BioTokenState,
         proof_emitter: Arc<dyn ProofEmitter>,
+        proof_outbox: ProofOutbox,
     ) -> (Self, tokio::sync::mpsc::Receiver<GossipFrame>) {
         // existing setup...
         let storage = HostStorage::new(initial_state);
@@ -56,9 +61,15 @@ where
             runtime: Arc::new(runtime),
             storage,
             proof_emitter,
+            proof_outbox,
             // ...
         };
 
+        // Re-emit artifacts for frames committed before a crash.
+        if let Err(e) = node.reconcile_proofs() {
+            eprintln!("Warning: proof backlog not re-emitted at startup: {:?}", e);
+        }
+
         (node, gossip_rx)
     }
 }
@@ -89,18 +100,31 @@ async fn handle_rpc(&self, req: RpcRequest) -> RpcResponse {
 
             match result {
                 Ok(frame) => {
-                    // persist new state + frame:
-                    self.storage.apply_state_and_frame(state.clone(), frame.clone());
-
-                    // === NEW: build and emit proof artifact ===
-                    if let Err(e) = self.emit_proof_for_transition(
+                    // The artifact is written ahead of the commit, so it
+                    // can be re-emitted after a crash.
+                    let artifact = self.build_proof_artifact(
                         &pre_state,
                         &state,
                         &frame,
                         &host_frame,
                         &header,
-                    ) {
-                        eprintln!("Warning: failed to emit proof artifact: {:?}", e);
+                    );
+                    if let Err(e) = self.proof_outbox.record(&artifact) {
+                        return RpcResponse::Error {
+                            error: format!("proof outbox write failed: {:?}", e),
+                        };
+                    }
+
+                    // persist new state + frame:
+                    self.storage.apply_state_and_frame(state.clone(), frame.clone());
+
+                    // Emits this frame's artifact and any earlier backlog;
+                    // whatever fails stays pending for the next attempt.
+                    if let Err(e) = self.reconcile_proofs() {
+                        eprintln!(
+                            "Warning: proof artifact for seqno {} pending re-emission: {:?}",
+                            frame.seqno, e
+                        );
                     }
 
                     // existing gossip + response...
@@ -127,14 +151,43 @@ where
     C: alndid::ConsentVerifier + Send + Sync + 'static,
     HC: biospectreconsensus::HostConsensus + Send + Sync + 'static,
 {
-    fn emit_proof_for_transition(
+    /// Emit outbox artifacts for committed frames the emitter has not
+    /// durably written yet. Returns how many were emitted.
+    ///
+    /// Progress comes from the emitter when it tracks one (it survives a
+    /// crash with the log); otherwise from the outbox cursor. Emitted
+    /// records are then trimmed from the outbox.
+    pub fn reconcile_proofs(&self) -> ProofResult<usize> {
+        let Some(committed) = self.storage.read_last_frame().map(|f| f.seqno) else {
+            return Ok(0);
+        };
+        let mut emitted = 0;
+        for artifact in self.proof_outbox.pending(self.proofs_emitted_through()?, committed)? {
+            self.proof_emitter.emit_proof_artifact(&artifact)?;
+            self.proof_outbox.set_cursor(artifact.consensus_seqno)?;
+            emitted += 1;
+        }
+        if let Some(through) = self.proofs_emitted_through()? {
+            self.proof_outbox.trim(through)?;
+        }
+        Ok(emitted)
+    }
+
+    fn proofs_emitted_through(&self) -> ProofResult<Option<u64>> {
+        match self.proof_emitter.emitted_through() {
+            Some(seqno) => Ok(Some(seqno)),
+            None => self.proof_outbox.cursor(),
+        }
+    }
+
+    fn build_proof_artifact(
         &self,
         pre_state: &BioTokenState,
         post_state: &BioTokenState,
         frame: &biospectreconsensus::ConsensusFrame,
         host_frame: &ALNHostFrame,
         header: &RpcSecurityHeader,
-    ) -> ProofResult<()> {
+    ) -> BiophysicalProofArtifact {
         // Map runtime types to evidence types.
 
         let host = HostIdentity {
@@ -179,7 +232,7 @@ where
         let provenance_hash_hex = self.lookup_provenance_hash_for_frame(frame);
         let civic_audit_id = self.lookup_civic_audit_id_for_frame(frame);
 
-        let artifact = BiophysicalProofArtifact {
+        BiophysicalProofArtifact {
             host,
             lorentz_ts,
             utc_ms: current_utc_ms(),
@@ -197,9 +250,7 @@ where
             consent_proof_hash_hex,
             provenance_hash_hex,
             civic_audit_id,
-        };
-
-        self.proof_emitter.emit_proof_artifact(&artifact)
+        }
     }
 
     // Stub mappings; implement using your existing lifeforce/eco modules.
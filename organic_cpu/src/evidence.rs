@@ -4,7 +4,20 @@
 //! - `BiophysicalProofArtifact`: a machine-readable, non-financial,
 //!   host-sealed attestation object per accepted runtime event.
 //! - `ProofEmitter`: a trait for host-local emitters that append
-//!   these artifacts to an immutable audit stream (e.g. JSONL file).
+//!   these artifacts to an append-only audit stream (e.g. JSONL file).
+//! - `chain`: a hash-chained log format that makes that stream
+//!   tamper-evident, and its verifier.
+//! - `seal`: host signatures over that chain, with key rotation.
+//! - `cbor`: length-prefixed CBOR storage for the same chained records.
+//! - `bundle`: filtered extracts of a chained log that verify on their own.
+//! - `compose`: tee, filter, buffered and in-memory emitters with error
+//!   policies.
+//! - `durable`: fsync policy and torn-record recovery for log files.
+//! - `index`: SQLite index mirroring a log (feature `sqlite-index`).
+//! - `merkle`: daily Merkle roots and inclusion proofs over artifacts.
+//! - `outbox`: write-ahead artifacts for re-emission after a crash.
+//! - `redact`: salted field commitments for redacted export.
+//! - `segment`: segmented logs with a manifest and signed compaction.
 //!
 //! Design guarantees:
 //! - No transfer / stake / financial semantics.
@@ -28,6 +41,19 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 
+pub mod bundle;
+pub mod cbor;
+pub mod chain;
+pub mod compose;
+pub mod durable;
+#[cfg(feature = "sqlite-index")]
+pub mod index;
+pub mod merkle;
+pub mod outbox;
+pub mod redact;
+pub mod seal;
+pub mod segment;
+
 /// Minimal ALN/Bostrom host identity view for evidence.
 /// This carries no roles or privileges: it is just an anchor.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -113,6 +139,7 @@ pub struct EventSummary {
 }
 
 /// A single, host-local, organic_cpu biophysical proof artifact.
+/// Host-sealed when written through a sealed `chain` emitter.
 ///
 /// This object is designed for machine readability, discovery,
 /// and biophysical proof, without exposing raw internal state.
@@ -156,7 +183,7 @@ pub struct BiophysicalProofArtifact {
     pub eco_cost_hint: f64,
 
     /// Pain corridor band at the time of application, if any.
-    pub pain_band: Option[PainBand],
+    pub pain_band: Option<PainBand>,
 
     /// Metabolic consent mode snapshot.
     pub metabolic_mode: MetabolicConsentMode,
@@ -183,6 +210,16 @@ pub enum ProofError {
     Io(#[from] std::io::Error),
     #[error("serialization error while encoding proof artifact: {0}")]
     Serialization(#[from] serde_json::Error),
+    #[error("CBOR error while encoding proof artifact: {0}")]
+    Cbor(String),
+    #[error("proof emitter unavailable: {0}")]
+    Unavailable(String),
+    #[error("proof index error: {0}")]
+    Index(String),
+    #[error("{0}")]
+    Chain(#[from] chain::ChainBreak),
+    #[error("proof seal error: {0}")]
+    Seal(#[from] seal::SealError),
 }
 
 /// Trait implemented by host-local emitters that know how to persist
@@ -195,10 +232,21 @@ pub trait ProofEmitter: Send + Sync + 'static {
     /// - host-local (no remote writes),
     /// - non-financial (no token semantics).
     fn emit_proof_artifact(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<()>;
+
+    /// `consensus_seqno` through which this emitter has durably written
+    /// every artifact it was offered, from the lowest one on, if it tracks
+    /// one. Used to re-emit artifacts lost in a crash or dropped; emitters
+    /// that track it must accept re-offered artifacts without writing them
+    /// twice.
+    fn emitted_through(&self) -> Option<u64> {
+        None
+    }
 }
 
 /// JSONL file-based proof emitter.
-/// Each artifact is written as one JSON line, append-only.
+/// Each artifact is written as one JSON line, append-only, and synced.
+/// Lines are not chained and a torn last line is not repaired; use
+/// `chain::ChainedJsonlProofEmitter` for a verifiable, recoverable log.
 #[derive(Clone, Debug)]
 pub struct JsonlFileProofEmitter {
     path: PathBuf,
@@ -229,9 +277,10 @@ impl ProofEmitter for JsonlFileProofEmitter {
             .append(true)
             .open(&self.path)?;
 
-        let line = serde_json::to_string(artifact)?;
-        file.write_all(line.as_bytes())?;
-        file.write_all(b"\n")?;
+        let mut line = serde_json::to_vec(artifact)?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+        file.sync_data()?;
         Ok(())
     }
 }
@@ -0,0 +1,246 @@
+//! Filtered extracts of a chained proof log that verify on their own.
+//!
+//! A bundle keeps the whole chain but withholds entries that were not
+//! selected: an omitted record keeps only its link fields, entry digest and
+//! signature. Links and seals still check end to end, so a reviewer can
+//! verify the selected artifacts against the source head without seeing
+//! the rest. Key rotations are always kept in full.
+//!
+//! A redacted bundle also withholds chosen fields of the selected
+//! artifacts, keeping their commitments (see `redact`). A selected record
+//! written without commitments for a field that has a value is omitted
+//! whole instead.
+
+use std::path::Path;
+
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+
+use super::chain::{
+    link_hash, scan_jsonl, BreakKind, ChainBreak, ChainEntry, ChainHead, ChainVerifier, ChainedProofRecord,
+};
+use super::redact::{FieldReveal, RedactableField, RedactedRecord};
+use super::seal::{SealError, SealReport, SealVerifier};
+use super::ProofResult;
+
+pub const BUNDLE_FORMAT: &str = "organic_cpu.proof.bundle.v1";
+
+/// Link fields of a record whose entry is withheld.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OmittedRecord {
+    pub seq: u64,
+    pub prev_hash: String,
+    pub entry_digest: String,
+    pub hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "bundle", rename_all = "snake_case")]
+pub enum BundleEntry {
+    Full(ChainedProofRecord),
+    Omitted(OmittedRecord),
+    Redacted(RedactedRecord),
+}
+
+impl BundleEntry {
+    pub fn seq(&self) -> u64 {
+        match self {
+            BundleEntry::Full(r) => r.body.seq,
+            BundleEntry::Omitted(o) => o.seq,
+            BundleEntry::Redacted(r) => r.seq,
+        }
+    }
+
+    pub fn hash(&self) -> &str {
+        match self {
+            BundleEntry::Full(r) => &r.hash,
+            BundleEntry::Omitted(o) => &o.hash,
+            BundleEntry::Redacted(r) => &r.hash,
+        }
+    }
+
+    pub fn signature(&self) -> Option<&str> {
+        match self {
+            BundleEntry::Full(r) => r.signature.as_deref(),
+            BundleEntry::Omitted(o) => o.signature.as_deref(),
+            BundleEntry::Redacted(r) => r.signature.as_deref(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProofBundle {
+    pub format: String,
+    /// Head of the source log when the bundle was cut.
+    pub source_head: ChainHead,
+    pub entries: Vec<BundleEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleReport {
+    pub head: ChainHead,
+    pub included_artifacts: usize,
+    pub omitted: usize,
+    /// Included artifacts with withheld fields.
+    #[serde(default)]
+    pub redacted: usize,
+    /// Present when the source log is sealed.
+    pub seal: Option<SealReport>,
+}
+
+impl ProofBundle {
+    /// Cut a bundle from a chained JSONL log, keeping artifacts for which
+    /// `select` returns true. The source chain is verified while reading.
+    pub fn from_jsonl<P, F>(path: P, select: F) -> ProofResult<Self>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&ChainedProofRecord) -> bool,
+    {
+        Self::from_jsonl_redacted(path, select, &[])
+    }
+
+    /// As `from_jsonl`, withholding `redact` fields of included artifacts.
+    pub fn from_jsonl_redacted<P, F>(path: P, mut select: F, redact: &[RedactableField]) -> ProofResult<Self>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&ChainedProofRecord) -> bool,
+    {
+        let mut entries = Vec::new();
+        let source_head = scan_jsonl(path, |record| {
+            let kept = match &record.body.entry {
+                ChainEntry::KeyRotation(_) => Some(BundleEntry::Full(record.clone())),
+                ChainEntry::Artifact { .. } if select(record) => {
+                    if redact.is_empty() {
+                        Some(BundleEntry::Full(record.clone()))
+                    } else {
+                        RedactedRecord::from_record(record, redact).map(BundleEntry::Redacted)
+                    }
+                }
+                _ => None,
+            };
+            entries.push(match kept {
+                Some(entry) => entry,
+                None => BundleEntry::Omitted(OmittedRecord {
+                    seq: record.body.seq,
+                    prev_hash: record.body.prev_hash.clone(),
+                    entry_digest: record.body.entry.digest()?,
+                    hash: record.hash.clone(),
+                    signature: record.signature.clone(),
+                })
+            });
+            Ok(())
+        })?;
+        Ok(Self {
+            format: BUNDLE_FORMAT.to_string(),
+            source_head,
+            entries,
+        })
+    }
+
+    /// Drop every entry after the last signed one, so a bundle from a sealed
+    /// log carries no unsealed tail; the source head moves back to match.
+    /// Returns the number of entries dropped (all of them if none is signed).
+    pub fn cut_at_last_seal(&mut self) -> usize {
+        let keep = self
+            .entries
+            .iter()
+            .rposition(|e| e.signature().is_some())
+            .map_or(0, |i| i + 1);
+        let dropped = self.entries.len() - keep;
+        self.entries.truncate(keep);
+        self.source_head = match self.entries.last() {
+            Some(e) => ChainHead {
+                next_seq: e.seq() + 1,
+                hash: e.hash().to_string(),
+            },
+            None => ChainHead::default(),
+        };
+        dropped
+    }
+
+    /// Included artifacts, in log order.
+    pub fn records(&self) -> impl Iterator<Item = &ChainedProofRecord> {
+        self.entries.iter().filter_map(|e| match e {
+            BundleEntry::Full(r) if r.artifact().is_some() => Some(r),
+            _ => None,
+        })
+    }
+
+    pub fn redacted(&self, seq: u64) -> Option<&RedactedRecord> {
+        self.entries.iter().find_map(|e| match e {
+            BundleEntry::Redacted(r) if r.seq == seq => Some(r),
+            _ => None,
+        })
+    }
+
+    /// Whether `reveal` discloses a field of a redacted record in this
+    /// bundle. Verify the bundle itself first.
+    pub fn check_reveal(&self, reveal: &FieldReveal) -> bool {
+        self.redacted(reveal.seq).is_some_and(|r| reveal.verify(r))
+    }
+
+    /// Check links, seals (if the log is sealed) and the source head.
+    pub fn verify(&self, trusted: Option<VerifyingKey>) -> ProofResult<BundleReport> {
+        let sealed = matches!(
+            self.entries.first(),
+            Some(BundleEntry::Full(r)) if matches!(r.body.entry, ChainEntry::KeyRotation(_))
+        );
+        if trusted.is_some() && !sealed {
+            return Err(SealError::MissingGenesisKey { seq: 0 }.into());
+        }
+        let mut chain = ChainVerifier::default();
+        let mut seals = trusted.map(SealVerifier::trusting).unwrap_or_default();
+        let (mut included, mut omitted, mut redacted) = (0, 0, 0);
+
+        for entry in &self.entries {
+            match entry {
+                BundleEntry::Full(record) => {
+                    chain.push(record)?;
+                    if sealed {
+                        seals.push(record)?;
+                    }
+                    if record.artifact().is_some() {
+                        included += 1;
+                    }
+                }
+                BundleEntry::Omitted(o) => {
+                    let computed = link_hash(o.seq, &o.prev_hash, &o.entry_digest);
+                    chain.push_link(o.seq, &o.prev_hash, Some(&computed), &o.hash)?;
+                    if sealed {
+                        seals.push_omitted(o.seq, &o.hash, o.signature.as_deref())?;
+                    }
+                    omitted += 1;
+                }
+                BundleEntry::Redacted(r) => {
+                    chain.push_link(r.seq, &r.prev_hash, r.computed_hash().as_deref(), &r.hash)?;
+                    if sealed {
+                        seals.push(&r.as_sealed_record())?;
+                    }
+                    included += 1;
+                    redacted += 1;
+                }
+            }
+        }
+
+        let head = chain.head().clone();
+        if head != self.source_head {
+            return Err(ChainBreak {
+                line: self.entries.len(),
+                kind: BreakKind::AnchorMismatch {
+                    expected_head: self.source_head.hash.clone(),
+                    found_head: head.hash,
+                },
+            }
+            .into());
+        }
+        Ok(BundleReport {
+            head,
+            included_artifacts: included,
+            omitted,
+            redacted,
+            seal: sealed.then(|| seals.finish()),
+        })
+    }
+}
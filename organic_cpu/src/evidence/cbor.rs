@@ -0,0 +1,445 @@
+//! Length-prefixed CBOR storage for chained proof logs.
+//!
+//! Each record is one frame: a schema version byte, a big-endian `u32`
+//! payload length, then the `ChainedProofRecord` as CBOR (same schema as a
+//! JSONL line). Record hashes and seals are over the canonical encoding
+//! (`ChainEntry::canonical_bytes`), not the stored bytes, so a log
+//! verifies the same in either format and converts losslessly: the
+//! converters re-verify every link after decoding.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::chain::{
+    scan_jsonl, verify_sealed_jsonl, BreakKind, ChainBreak, ChainHead, ChainVerifier, ChainWriter, ChainedProofRecord,
+    SeqnoSet,
+};
+use super::durable::{FsyncPolicy, LogAppender};
+use super::redact::{RedactableField, SaltKey};
+use super::seal::{HostSigner, SealError, SealReport, SealVerifier};
+use super::segment::{scan_segments, verify_segments};
+use super::{BiophysicalProofArtifact, ProofEmitter, ProofError, ProofResult};
+
+/// Schema version byte leading every frame.
+pub const CBOR_SCHEMA_VERSION: u8 = 1;
+
+const HEADER_LEN: u64 = 5;
+/// Larger payloads are treated as corrupt rather than allocated.
+const MAX_PAYLOAD_LEN: u32 = 16 << 20;
+
+/// Encode one record as a frame.
+pub fn encode_frame(record: &ChainedProofRecord) -> ProofResult<Vec<u8>> {
+    let mut payload = Vec::new();
+    ciborium::ser::into_writer(record, &mut payload).map_err(|e| ProofError::Cbor(e.to_string()))?;
+    let len = u32::try_from(payload.len()).map_err(|_| ProofError::Cbor("record exceeds 4 GiB".into()))?;
+    let mut frame = Vec::with_capacity(payload.len() + HEADER_LEN as usize);
+    frame.push(CBOR_SCHEMA_VERSION);
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+/// Read the next frame. `Ok(None)` at a clean end of stream; a short or
+/// undecodable frame is `Err` with the reason, for `push_decoded`.
+fn read_frame<R: Read>(reader: &mut R) -> ProofResult<Option<Result<ChainedProofRecord, String>>> {
+    let mut header = [0u8; HEADER_LEN as usize];
+    match reader.read_exact(&mut header[..1]) {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    if header[0] != CBOR_SCHEMA_VERSION {
+        return Ok(Some(Err(format!("unsupported schema version {}", header[0]))));
+    }
+    let mut payload = Vec::new();
+    let read = reader
+        .read_exact(&mut header[1..])
+        .and_then(|()| {
+            let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]);
+            if len > MAX_PAYLOAD_LEN {
+                return Err(ErrorKind::InvalidData.into());
+            }
+            payload.resize(len as usize, 0);
+            reader.read_exact(&mut payload)
+        });
+    match read {
+        Ok(()) => {}
+        Err(e) if e.kind() == ErrorKind::InvalidData => return Ok(Some(Err("oversized frame".into()))),
+        Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(Some(Err("truncated frame".into()))),
+        Err(e) => return Err(e.into()),
+    }
+    Ok(Some(ciborium::de::from_reader(payload.as_slice()).map_err(|e| e.to_string())))
+}
+
+/// Cut a torn trailing frame. Returns how many bytes were removed.
+/// Complete frames are kept even if they do not decode; that is a chain
+/// break for the verifier. A frame is torn only if it is the last one: a
+/// valid version byte, then a header or payload that runs past the end of
+/// the file without holding a complete record. A bad version byte, an
+/// oversized length, or a length that runs over later records is a chain
+/// break, and nothing is cut.
+pub fn truncate_torn_frames<P: AsRef<Path>>(path: P) -> ProofResult<u64> {
+    let mut file = match OpenOptions::new().read(true).write(true).open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+    let len = file.metadata()?.len();
+    let mut keep = 0u64;
+    let mut line = 0;
+    let mut header = [0u8; HEADER_LEN as usize];
+    while keep < len {
+        line += 1;
+        let corrupt = |reason: String| ChainBreak {
+            line,
+            kind: BreakKind::Unparseable(reason),
+        };
+        let available = (len - keep).min(HEADER_LEN) as usize;
+        file.seek(SeekFrom::Start(keep))?;
+        file.read_exact(&mut header[..available])?;
+        if header[0] != CBOR_SCHEMA_VERSION {
+            return Err(corrupt(format!("unsupported schema version {}", header[0])).into());
+        }
+        if available < HEADER_LEN as usize {
+            break;
+        }
+        let payload = u32::from_be_bytes([header[1], header[2], header[3], header[4]]);
+        if payload > MAX_PAYLOAD_LEN {
+            return Err(corrupt("oversized frame".into()).into());
+        }
+        if len - keep - HEADER_LEN < u64::from(payload) {
+            // A torn payload is a strict prefix of one record; anything
+            // that decodes whole means the length field is damaged.
+            let mut rest = Vec::new();
+            file.read_to_end(&mut rest)?;
+            match ciborium::de::from_reader::<ciborium::Value, _>(rest.as_slice()) {
+                Err(ciborium::de::Error::Io(e)) if e.kind() == ErrorKind::UnexpectedEof => break,
+                _ => return Err(corrupt("frame length runs past the records after it".into()).into()),
+            }
+        }
+        keep += HEADER_LEN + u64::from(payload);
+    }
+    if keep < len {
+        file.set_len(keep)?;
+        file.sync_all()?;
+    }
+    Ok(len - keep)
+}
+
+/// As `chain::scan_jsonl_with`, over a CBOR log.
+pub fn scan_cbor_with<P, F>(path: P, verifier: &mut ChainVerifier, mut f: F) -> ProofResult<()>
+where
+    P: AsRef<Path>,
+    F: FnMut(&ChainedProofRecord) -> ProofResult<()>,
+{
+    let mut reader = BufReader::new(File::open(path)?);
+    while let Some(decoded) = read_frame(&mut reader)? {
+        let record = verifier.push_decoded(decoded)?;
+        f(&record)?;
+    }
+    Ok(())
+}
+
+/// Verify a CBOR log from genesis, passing each record to `f`.
+pub fn scan_cbor<P, F>(path: P, f: F) -> ProofResult<ChainHead>
+where
+    P: AsRef<Path>,
+    F: FnMut(&ChainedProofRecord) -> ProofResult<()>,
+{
+    let mut verifier = ChainVerifier::default();
+    scan_cbor_with(path, &mut verifier, f)?;
+    Ok(verifier.head().clone())
+}
+
+pub fn verify_cbor<P: AsRef<Path>>(path: P) -> ProofResult<ChainHead> {
+    scan_cbor(path, |_| Ok(()))
+}
+
+/// As `chain::verify_sealed_jsonl`, over a CBOR log.
+pub fn verify_sealed_cbor<P: AsRef<Path>>(
+    path: P,
+    trusted: Option<ed25519_dalek::VerifyingKey>,
+) -> ProofResult<(ChainHead, SealReport)> {
+    let mut seals = trusted.map(SealVerifier::trusting).unwrap_or_default();
+    let head = scan_cbor(path, |r| Ok(seals.push(r)?))?;
+    Ok((head, seals.finish()))
+}
+
+/// Whether `path` names a CBOR log (`.cbor` extension).
+pub fn is_cbor_path(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "cbor")
+}
+
+/// `segment::scan_segments` for a directory, otherwise `scan_cbor` or
+/// `chain::scan_jsonl` by extension.
+pub fn scan_log<P, F>(path: P, f: F) -> ProofResult<ChainHead>
+where
+    P: AsRef<Path>,
+    F: FnMut(&ChainedProofRecord) -> ProofResult<()>,
+{
+    if path.as_ref().is_dir() {
+        scan_segments(path.as_ref(), f)
+    } else if is_cbor_path(path.as_ref()) {
+        scan_cbor(path, f)
+    } else {
+        scan_jsonl(path, f)
+    }
+}
+
+/// Where a scan of a single-file log stopped: the chain head and the byte
+/// offset just past its last record.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogPosition {
+    pub head: ChainHead,
+    pub offset: u64,
+}
+
+/// Continue a JSONL or CBOR log (by extension) from `from`, verifying each
+/// record against the head there and passing it to `f`. The log before
+/// `from.offset` is not re-read: a record after it that does not link to
+/// `from.head`, or a file shorter than the offset, is a chain break. A
+/// JSONL line without its newline is still being written and is left for
+/// the next scan.
+pub fn scan_log_from<P, F>(path: P, from: &LogPosition, mut f: F) -> ProofResult<LogPosition>
+where
+    P: AsRef<Path>,
+    F: FnMut(&ChainedProofRecord) -> ProofResult<()>,
+{
+    let path = path.as_ref();
+    let file = File::open(path)?;
+    if file.metadata()?.len() < from.offset {
+        return Err(ChainBreak {
+            line: 0,
+            kind: BreakKind::AnchorMismatch {
+                expected_head: from.head.hash.clone(),
+                found_head: String::new(),
+            },
+        }
+        .into());
+    }
+    let mut reader = BufReader::new(file);
+    reader.seek(SeekFrom::Start(from.offset))?;
+    let mut verifier = ChainVerifier::from_head(from.head.clone());
+    let mut offset = from.offset;
+    if is_cbor_path(path) {
+        while let Some(decoded) = read_frame(&mut reader)? {
+            f(&verifier.push_decoded(decoded)?)?;
+            offset = reader.stream_position()?;
+        }
+    } else {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let n = reader.read_line(&mut line)?;
+            if n == 0 || !line.ends_with('\n') {
+                break;
+            }
+            if let Some(record) = verifier.push_line(&line)? {
+                f(&record)?;
+            }
+            offset += n as u64;
+        }
+    }
+    Ok(LogPosition {
+        head: verifier.head().clone(),
+        offset,
+    })
+}
+
+/// `segment::verify_segments` for a directory, otherwise
+/// `verify_sealed_cbor` or `chain::verify_sealed_jsonl` by extension.
+pub fn verify_sealed_log<P: AsRef<Path>>(
+    path: P,
+    trusted: Option<ed25519_dalek::VerifyingKey>,
+) -> ProofResult<(ChainHead, SealReport)> {
+    if path.as_ref().is_dir() {
+        let report = verify_segments(path.as_ref(), trusted)?;
+        let seal = report.seal.ok_or(SealError::MissingGenesisKey { seq: 0 })?;
+        Ok((report.head, seal))
+    } else if is_cbor_path(path.as_ref()) {
+        verify_sealed_cbor(path, trusted)
+    } else {
+        verify_sealed_jsonl(path, trusted)
+    }
+}
+
+fn create_new(path: &Path) -> ProofResult<BufWriter<File>> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(BufWriter::new(OpenOptions::new().write(true).create_new(true).open(path)?))
+}
+
+fn finish(writer: BufWriter<File>) -> ProofResult<()> {
+    writer
+        .into_inner()
+        .map_err(|e| ProofError::Io(e.into_error()))?
+        .sync_all()?;
+    Ok(())
+}
+
+/// Convert a JSONL log to a new CBOR log, verifying every link.
+pub fn jsonl_to_cbor<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> ProofResult<ChainHead> {
+    let mut out = create_new(dst.as_ref())?;
+    let head = scan_jsonl(src, |record| {
+        out.write_all(&encode_frame(record)?)?;
+        Ok(())
+    })?;
+    finish(out)?;
+    Ok(head)
+}
+
+/// Convert a CBOR log to a new JSONL log, verifying every link.
+pub fn cbor_to_jsonl<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> ProofResult<ChainHead> {
+    let mut out = create_new(dst.as_ref())?;
+    let head = scan_cbor(src, |record| {
+        serde_json::to_writer(&mut out, record)?;
+        out.write_all(b"\n")?;
+        Ok(())
+    })?;
+    finish(out)?;
+    Ok(head)
+}
+
+#[derive(Debug)]
+struct EmitterState {
+    writer: ChainWriter,
+    appender: LogAppender,
+}
+
+/// CBOR counterpart of `chain::ChainedJsonlProofEmitter`.
+#[derive(Debug)]
+pub struct CborProofEmitter {
+    recovered_bytes: u64,
+    state: Mutex<EmitterState>,
+}
+
+impl CborProofEmitter {
+    /// Unsealed log: chained, not signed.
+    pub fn open<P: AsRef<Path>>(path: P, fsync: FsyncPolicy) -> ProofResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let recovered_bytes = truncate_torn_frames(&path)?;
+        let mut written = SeqnoSet::default();
+        let head = if path.exists() {
+            scan_cbor(&path, |r| {
+                if let Some(a) = r.artifact() {
+                    written.insert(a.consensus_seqno);
+                }
+                Ok(())
+            })?
+        } else {
+            ChainHead::default()
+        };
+        Ok(Self::with_writer(
+            path,
+            fsync,
+            recovered_bytes,
+            ChainWriter::unsealed(head, written),
+        ))
+    }
+
+    /// Sealed log; as `ChainedJsonlProofEmitter::open_sealed`.
+    pub fn open_sealed<P: AsRef<Path>>(
+        path: P,
+        signer: HostSigner,
+        seal_every: u32,
+        fsync: FsyncPolicy,
+    ) -> ProofResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let recovered_bytes = truncate_torn_frames(&path)?;
+        let mut seals = SealVerifier::default();
+        let mut written = SeqnoSet::default();
+        let head = if path.exists() {
+            scan_cbor(&path, |r| {
+                seals.push(r)?;
+                if let Some(a) = r.artifact() {
+                    written.insert(a.consensus_seqno);
+                }
+                Ok(())
+            })?
+        } else {
+            ChainHead::default()
+        };
+        let (writer, genesis) = ChainWriter::sealed(head, &seals.finish(), written, signer, seal_every)?;
+        let emitter = Self::with_writer(path, fsync, recovered_bytes, writer);
+        if let Some(record) = genesis {
+            emitter.write(&mut emitter.lock(), &record)?;
+        }
+        Ok(emitter)
+    }
+
+    fn with_writer(path: PathBuf, fsync: FsyncPolicy, recovered_bytes: u64, writer: ChainWriter) -> Self {
+        Self {
+            recovered_bytes,
+            state: Mutex::new(EmitterState {
+                writer,
+                appender: LogAppender::framed(path, fsync, |p| {
+                    truncate_torn_frames(p).map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e.to_string()))
+                }),
+            }),
+        }
+    }
+
+    /// See `ChainWriter::commit_fields`.
+    pub fn with_committed_fields(self, key: SaltKey, fields: Vec<RedactableField>) -> Self {
+        self.lock().writer.commit_fields(key, fields);
+        self
+    }
+
+    pub fn head(&self) -> ChainHead {
+        self.lock().writer.head().clone()
+    }
+
+    /// Bytes of torn trailing frame removed when opening.
+    pub fn recovered_bytes(&self) -> u64 {
+        self.recovered_bytes
+    }
+
+    /// Force buffered records to disk regardless of the fsync policy.
+    pub fn sync(&self) -> ProofResult<()> {
+        Ok(self.lock().appender.sync()?)
+    }
+
+    /// Sign a checkpoint covering every artifact written so far. Fails with
+    /// `SealError::MissingGenesisKey` on an unsealed log.
+    pub fn seal(&self) -> ProofResult<()> {
+        let mut state = self.lock();
+        let record = state.writer.checkpoint_record()?;
+        self.write(&mut state, &record)
+    }
+
+    /// As `ChainedJsonlProofEmitter::rotate_key`.
+    pub fn rotate_key(&self, new_key: ed25519_dalek::SigningKey, effective_from_seqno: u64) -> ProofResult<()> {
+        let mut state = self.lock();
+        let record = state.writer.rotation_record(&new_key, effective_from_seqno)?;
+        self.write(&mut state, &record)?;
+        state.writer.install_key(new_key, effective_from_seqno);
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, EmitterState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self, state: &mut EmitterState, record: &ChainedProofRecord) -> ProofResult<()> {
+        state.appender.append_frame(&encode_frame(record)?)?;
+        state.writer.advance(record);
+        Ok(())
+    }
+}
+
+impl ProofEmitter for CborProofEmitter {
+    fn emit_proof_artifact(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<()> {
+        let mut state = self.lock();
+        match state.writer.artifact_record(artifact)? {
+            Some(record) => self.write(&mut state, &record),
+            None => Ok(()),
+        }
+    }
+
+    fn emitted_through(&self) -> Option<u64> {
+        self.lock().writer.emitted_through()
+    }
+}
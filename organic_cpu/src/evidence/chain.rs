@@ -0,0 +1,713 @@
+//! Hash-chained proof log.
+//!
+//! Each `BiophysicalProofArtifact` is wrapped in a `ChainedProofRecord`
+//! carrying a per-log sequence number, the hash of the previous record
+//! (`GENESIS_HASH` for the first) and its own hash over both. The record
+//! hash commits to the entry through its digest, so a link can be checked
+//! from `(seq, prev_hash, entry_digest)` alone (see `bundle`). Key
+//! rotations and seal checkpoints (see `seal`) are entries in the same
+//! chain; a record may carry a host signature over its hash. Inserting,
+//! deleting, reordering or editing a line breaks the chain at that point;
+//! `ChainVerifier` reports the first broken link.
+//!
+//! Truncating the tail of the log leaves a valid, shorter chain. Detecting
+//! that needs an anchor kept elsewhere (a recorded head hash or length),
+//! which `verify_jsonl_against` checks.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::durable::{truncate_torn_tail, FsyncPolicy, LogAppender};
+use super::redact::{commitments, committed_digest, FieldSalts, RedactableField, SaltKey};
+use super::seal::{HostSigner, KeyRotation, SealError, SealReport, SealVerifier};
+use super::{current_utc_ms, BiophysicalProofArtifact, ProofEmitter, ProofResult};
+
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChainEntry {
+    Artifact {
+        artifact: BiophysicalProofArtifact,
+        /// Salts of fields committed for redaction (see `redact`).
+        #[serde(default, skip_serializing_if = "FieldSalts::is_empty")]
+        salts: FieldSalts,
+    },
+    KeyRotation(KeyRotation),
+    /// Carries a signature sealing everything before it.
+    Checkpoint { utc_ms: i64 },
+}
+
+impl ChainEntry {
+    /// Canonical encoding that digests, and so record hashes and seals,
+    /// are taken over: compact JSON in schema field order. It does not
+    /// depend on how the record is stored (JSONL or `cbor` frames).
+    /// Non-finite floats have no JSON form and are rejected.
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, serde_json::Error> {
+        if let ChainEntry::Artifact { artifact, .. } = self {
+            if !artifact.eco_cost_hint.is_finite() {
+                return Err(serde::ser::Error::custom("eco_cost_hint is not finite"));
+            }
+        }
+        serde_json::to_vec(self)
+    }
+
+    /// Salted artifacts digest over their field commitments instead
+    /// (`redact::committed_digest`).
+    pub fn digest(&self) -> Result<String, serde_json::Error> {
+        match self {
+            ChainEntry::Artifact { artifact, salts } if !salts.is_empty() => {
+                committed_digest(artifact, &commitments(artifact, salts))
+            }
+            _ => Ok(hex::encode(Sha256::digest(self.canonical_bytes()?))),
+        }
+    }
+}
+
+/// Record hash from its link fields.
+pub fn link_hash(seq: u64, prev_hash: &str, entry_digest: &str) -> String {
+    hex::encode(Sha256::digest(format!("{seq}:{prev_hash}:{entry_digest}").as_bytes()))
+}
+
+/// Hashed part of a record.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ChainedRecordBody {
+    pub seq: u64,
+    pub prev_hash: String,
+    pub entry: ChainEntry,
+}
+
+impl ChainedRecordBody {
+    pub fn hash(&self) -> Result<String, serde_json::Error> {
+        Ok(link_hash(self.seq, &self.prev_hash, &self.entry.digest()?))
+    }
+}
+
+/// One line of a chained proof log.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ChainedProofRecord {
+    #[serde(flatten)]
+    pub body: ChainedRecordBody,
+    pub hash: String,
+    /// Host seal over `hash`; not part of the hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl ChainedProofRecord {
+    pub fn artifact(&self) -> Option<&BiophysicalProofArtifact> {
+        match &self.body.entry {
+            ChainEntry::Artifact { artifact, .. } => Some(artifact),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BreakKind {
+    /// The line is not a chained record.
+    Unparseable(String),
+    /// Sequence number out of order: a record was inserted, deleted or moved.
+    SeqMismatch { expected: u64, found: u64 },
+    /// `prev_hash` does not name the previous record.
+    PrevHashMismatch,
+    /// Contents were edited after hashing.
+    HashMismatch,
+    /// The log ends before, or diverges from, the expected anchor.
+    AnchorMismatch { expected_head: String, found_head: String },
+}
+
+/// First broken link in a chain. `line` is 1-based (the record index in
+/// binary logs).
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[error("proof chain broken at line {line}: {kind:?}")]
+pub struct ChainBreak {
+    pub line: usize,
+    pub kind: BreakKind,
+}
+
+/// Position after the last verified record.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainHead {
+    pub next_seq: u64,
+    pub hash: String,
+}
+
+impl Default for ChainHead {
+    fn default() -> Self {
+        Self {
+            next_seq: 0,
+            hash: GENESIS_HASH.to_string(),
+        }
+    }
+}
+
+/// Incremental verifier; feed records in log order.
+#[derive(Clone, Debug, Default)]
+pub struct ChainVerifier {
+    head: ChainHead,
+    line: usize,
+}
+
+impl ChainVerifier {
+    /// Continue a chain from a known head (e.g. a previous segment).
+    pub fn from_head(head: ChainHead) -> Self {
+        Self { head, line: 0 }
+    }
+
+    pub fn head(&self) -> &ChainHead {
+        &self.head
+    }
+
+    pub fn push(&mut self, record: &ChainedProofRecord) -> Result<(), ChainBreak> {
+        let computed = record.body.hash().ok();
+        self.push_link(record.body.seq, &record.body.prev_hash, computed.as_deref(), &record.hash)
+    }
+
+    /// Check one link given the recomputed hash (`None` if it could not be
+    /// computed) and the hash the record claims.
+    pub fn push_link(
+        &mut self,
+        seq: u64,
+        prev_hash: &str,
+        computed: Option<&str>,
+        claimed: &str,
+    ) -> Result<(), ChainBreak> {
+        self.line += 1;
+        let kind = if seq != self.head.next_seq {
+            Some(BreakKind::SeqMismatch {
+                expected: self.head.next_seq,
+                found: seq,
+            })
+        } else if prev_hash != self.head.hash {
+            Some(BreakKind::PrevHashMismatch)
+        } else if computed != Some(claimed) {
+            Some(BreakKind::HashMismatch)
+        } else {
+            None
+        };
+        if let Some(kind) = kind {
+            return Err(ChainBreak { line: self.line, kind });
+        }
+        self.head = ChainHead {
+            next_seq: seq + 1,
+            hash: claimed.to_string(),
+        };
+        Ok(())
+    }
+
+    /// Parse and verify one JSONL line. Blank lines are skipped.
+    pub fn push_line(&mut self, line: &str) -> Result<Option<ChainedProofRecord>, ChainBreak> {
+        if line.trim().is_empty() {
+            self.line += 1;
+            return Ok(None);
+        }
+        self.push_decoded(serde_json::from_str(line).map_err(|e| e.to_string()))
+            .map(Some)
+    }
+
+    /// Verify a record decoded from any storage format; a decode error is
+    /// reported as `Unparseable` at the next position.
+    pub fn push_decoded(
+        &mut self,
+        decoded: Result<ChainedProofRecord, String>,
+    ) -> Result<ChainedProofRecord, ChainBreak> {
+        let record = decoded.map_err(|e| ChainBreak {
+            line: self.line + 1,
+            kind: BreakKind::Unparseable(e),
+        })?;
+        self.push(&record)?;
+        Ok(record)
+    }
+}
+
+/// Verify a chained JSONL log from genesis, passing each record to `f`.
+pub fn scan_jsonl<P, F>(path: P, f: F) -> ProofResult<ChainHead>
+where
+    P: AsRef<Path>,
+    F: FnMut(&ChainedProofRecord) -> ProofResult<()>,
+{
+    let mut verifier = ChainVerifier::default();
+    scan_jsonl_with(path, &mut verifier, f)?;
+    Ok(verifier.head)
+}
+
+/// Continue `verifier` over one more file, e.g. the next log segment.
+pub fn scan_jsonl_with<P, F>(path: P, verifier: &mut ChainVerifier, mut f: F) -> ProofResult<()>
+where
+    P: AsRef<Path>,
+    F: FnMut(&ChainedProofRecord) -> ProofResult<()>,
+{
+    for line in BufReader::new(File::open(path)?).lines() {
+        if let Some(record) = verifier.push_line(&line?)? {
+            f(&record)?;
+        }
+    }
+    Ok(())
+}
+
+/// Verify a chained JSONL log from genesis and return its head.
+pub fn verify_jsonl<P: AsRef<Path>>(path: P) -> ProofResult<ChainHead> {
+    scan_jsonl(path, |_| Ok(()))
+}
+
+/// Verify chain and host seals. With `trusted`, the genesis key must match.
+pub fn verify_sealed_jsonl<P: AsRef<Path>>(
+    path: P,
+    trusted: Option<ed25519_dalek::VerifyingKey>,
+) -> ProofResult<(ChainHead, SealReport)> {
+    let mut seals = trusted.map(SealVerifier::trusting).unwrap_or_default();
+    let head = scan_jsonl(path, |r| Ok(seals.push(r)?))?;
+    Ok((head, seals.finish()))
+}
+
+/// As `verify_jsonl`, and require the chain to pass through `anchor`
+/// (a head recorded earlier), so a truncated or rewritten tail is caught.
+pub fn verify_jsonl_against<P: AsRef<Path>>(path: P, anchor: &ChainHead) -> ProofResult<ChainHead> {
+    let mut verifier = ChainVerifier::default();
+    let mut at_anchor = (anchor.next_seq == 0).then(|| GENESIS_HASH.to_string());
+    for line in BufReader::new(File::open(path)?).lines() {
+        verifier.push_line(&line?)?;
+        if verifier.head.next_seq == anchor.next_seq && at_anchor.is_none() {
+            at_anchor = Some(verifier.head.hash.clone());
+        }
+    }
+    match at_anchor {
+        Some(h) if h == anchor.hash => Ok(verifier.head),
+        found => Err(ChainBreak {
+            line: verifier.line,
+            kind: BreakKind::AnchorMismatch {
+                expected_head: anchor.hash.clone(),
+                found_head: found.unwrap_or_else(|| verifier.head.hash.clone()),
+            },
+        }
+        .into()),
+    }
+}
+
+/// Consensus seqnos of the artifacts in a log, as disjoint runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SeqnoSet {
+    /// Run start -> run end (inclusive).
+    runs: BTreeMap<u64, u64>,
+}
+
+impl SeqnoSet {
+    /// Every seqno up to `max`, e.g. history compacted away.
+    pub fn up_to(max: u64) -> Self {
+        Self {
+            runs: BTreeMap::from([(0, max)]),
+        }
+    }
+
+    pub fn contains(&self, seqno: u64) -> bool {
+        self.runs
+            .range(..=seqno)
+            .next_back()
+            .is_some_and(|(_, end)| seqno <= *end)
+    }
+
+    /// Add `seqno`; false if it was already present.
+    pub fn insert(&mut self, seqno: u64) -> bool {
+        if self.contains(seqno) {
+            return false;
+        }
+        let start = match self.runs.range(..seqno).next_back() {
+            Some((start, end)) if end + 1 == seqno => *start,
+            _ => seqno,
+        };
+        let end = match seqno.checked_add(1).and_then(|next| self.runs.remove(&next)) {
+            Some(end) => end,
+            None => seqno,
+        };
+        self.runs.insert(start, end);
+        true
+    }
+
+    /// Add every seqno from 0 through `max`.
+    pub fn insert_up_to(&mut self, max: u64) {
+        let mut end = max;
+        while let Some((&start, &run_end)) = self.runs.range(..=max.saturating_add(1)).next_back() {
+            self.runs.remove(&start);
+            end = end.max(run_end);
+        }
+        self.runs.insert(0, end);
+    }
+
+    /// End of the run holding the lowest seqno: every seqno from the
+    /// lowest up to here is present.
+    pub fn through(&self) -> Option<u64> {
+        self.runs.first_key_value().map(|(_, end)| *end)
+    }
+
+    pub fn max(&self) -> Option<u64> {
+        self.runs.last_key_value().map(|(_, end)| *end)
+    }
+
+    /// Runs `(start, end)`, inclusive, in ascending order.
+    pub fn runs(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.runs.iter().map(|(start, end)| (*start, *end))
+    }
+}
+
+/// Chain state independent of storage: builds the next record, signs it
+/// per the seal policy and advances once the record is durably written.
+#[derive(Debug)]
+pub struct ChainWriter {
+    head: ChainHead,
+    signer: Option<HostSigner>,
+    /// `effective_from_seqno` of the signer's key.
+    key_from: u64,
+    /// `consensus_seqno`s written; rotations must start above the
+    /// highest, and none is written twice.
+    written: SeqnoSet,
+    /// Sign every n-th artifact; 1 seals each one, 0 only on checkpoints.
+    seal_every: u32,
+    unsealed: u32,
+    /// Fields to commit with salts from this key (see `redact`).
+    commit: Option<(SaltKey, Vec<RedactableField>)>,
+}
+
+impl ChainWriter {
+    /// Unsealed writer resuming at `head`, over a log holding `written`.
+    pub fn unsealed(head: ChainHead, written: SeqnoSet) -> Self {
+        Self {
+            head,
+            signer: None,
+            key_from: 0,
+            written,
+            seal_every: 0,
+            unsealed: 0,
+            commit: None,
+        }
+    }
+
+    /// Sealed writer resuming at `head` with the seal state in `report`.
+    /// For a log without keys yet, also returns the genesis rotation
+    /// record, which the caller must write and then `advance` past.
+    pub fn sealed(
+        head: ChainHead,
+        report: &SealReport,
+        written: SeqnoSet,
+        signer: HostSigner,
+        seal_every: u32,
+    ) -> ProofResult<(Self, Option<ChainedProofRecord>)> {
+        let mut writer = Self {
+            head,
+            signer: None,
+            key_from: 0,
+            written,
+            seal_every,
+            unsealed: report.unsealed_tail.len() as u32,
+            commit: None,
+        };
+        let key_hex = hex::encode(signer.verifying_key().as_bytes());
+        match report.keys.last() {
+            None => {
+                let genesis = KeyRotation::new(&signer.did, signer.signing_key(), 0);
+                writer.signer = Some(signer);
+                let record = writer.record(ChainEntry::KeyRotation(genesis), true)?;
+                Ok((writer, Some(record)))
+            }
+            Some((from, current)) if *current == key_hex && report.did == signer.did => {
+                writer.key_from = *from;
+                writer.signer = Some(signer);
+                Ok((writer, None))
+            }
+            Some(_) => Err(SealError::SignerMismatch { did: signer.did }.into()),
+        }
+    }
+
+    pub fn head(&self) -> &ChainHead {
+        &self.head
+    }
+
+    pub fn signer(&self) -> Option<&HostSigner> {
+        self.signer.as_ref()
+    }
+
+    pub fn max_consensus_seqno(&self) -> Option<u64> {
+        self.written.max()
+    }
+
+    /// Contiguous watermark; see `SeqnoSet::through`.
+    pub fn emitted_through(&self) -> Option<u64> {
+        self.written.through()
+    }
+
+    /// Commit `fields` of every later artifact with salts derived from
+    /// `key`, so they can be redacted on export.
+    pub fn commit_fields(&mut self, key: SaltKey, fields: Vec<RedactableField>) {
+        self.commit = (!fields.is_empty()).then_some((key, fields));
+    }
+
+    /// Next record for `entry`; signed if `sign` and a signer is set.
+    pub fn record(&self, entry: ChainEntry, sign: bool) -> ProofResult<ChainedProofRecord> {
+        let body = ChainedRecordBody {
+            seq: self.head.next_seq,
+            prev_hash: self.head.hash.clone(),
+            entry,
+        };
+        let hash = body.hash()?;
+        let signature = match (&self.signer, sign) {
+            (Some(signer), true) => Some(signer.seal(&hash)),
+            _ => None,
+        };
+        Ok(ChainedProofRecord { body, hash, signature })
+    }
+
+    /// Signed checkpoint sealing every artifact so far. A writer without a
+    /// signer has nothing to seal with and refuses instead of writing an
+    /// unsigned checkpoint.
+    pub fn checkpoint_record(&self) -> ProofResult<ChainedProofRecord> {
+        if self.signer.is_none() {
+            return Err(SealError::MissingGenesisKey { seq: self.head.next_seq }.into());
+        }
+        self.record(ChainEntry::Checkpoint { utc_ms: current_utc_ms() }, true)
+    }
+
+    /// Next record for `artifact`, checked against the signer and sealed
+    /// per `seal_every`. `None` if its `consensus_seqno` is already in the
+    /// log: re-offered artifacts are not written twice.
+    pub fn artifact_record(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<Option<ChainedProofRecord>> {
+        let seq = self.head.next_seq;
+        if self.written.contains(artifact.consensus_seqno) {
+            return Ok(None);
+        }
+        if let Some(signer) = &self.signer {
+            if artifact.host.did != signer.did {
+                return Err(SealError::HostMismatch {
+                    seq,
+                    expected: signer.did.clone(),
+                    found: artifact.host.did.clone(),
+                }
+                .into());
+            }
+            if artifact.consensus_seqno < self.key_from {
+                return Err(SealError::KeyNotActive {
+                    seq,
+                    consensus_seqno: artifact.consensus_seqno,
+                }
+                .into());
+            }
+        }
+        let sign = self.seal_every > 0 && self.unsealed + 1 >= self.seal_every;
+        self.record(
+            ChainEntry::Artifact {
+                artifact: artifact.clone(),
+                salts: match &self.commit {
+                    Some((key, fields)) => fields.iter().map(|f| (*f, key.salt(seq, *f))).collect(),
+                    None => FieldSalts::new(),
+                },
+            },
+            sign,
+        )
+        .map(Some)
+    }
+
+    /// Rotation record handing sealing to `new_key`; write it, `advance`,
+    /// then `install_key`. It must take effect at the seqno right after
+    /// the highest written: the writer holds one key, so a future-dated
+    /// rotation would leave the artifacts in between with no key to seal
+    /// them.
+    pub fn rotation_record(
+        &self,
+        new_key: &ed25519_dalek::SigningKey,
+        effective_from_seqno: u64,
+    ) -> ProofResult<ChainedProofRecord> {
+        let seq = self.head.next_seq;
+        let Some(signer) = &self.signer else {
+            return Err(SealError::MissingGenesisKey { seq }.into());
+        };
+        if let Some(seen) = self.written.max().filter(|s| effective_from_seqno <= *s) {
+            return Err(SealError::RetroactiveRotation {
+                seq,
+                effective_from: effective_from_seqno,
+                seen,
+            }
+            .into());
+        }
+        let next = self.written.max().map_or(0, |m| m + 1);
+        if effective_from_seqno != next {
+            return Err(SealError::FutureRotation {
+                seq,
+                effective_from: effective_from_seqno,
+                next,
+            }
+            .into());
+        }
+        let rotation = KeyRotation::new(&signer.did, new_key, effective_from_seqno);
+        self.record(ChainEntry::KeyRotation(rotation), true)
+    }
+
+    /// Switch to `new_key` once its `rotation_record` is written.
+    pub fn install_key(&mut self, new_key: ed25519_dalek::SigningKey, effective_from_seqno: u64) {
+        if let Some(signer) = &self.signer {
+            self.signer = Some(HostSigner::new(&signer.did, new_key));
+            self.key_from = effective_from_seqno;
+        }
+    }
+
+    /// Move past a record that has been written.
+    pub fn advance(&mut self, record: &ChainedProofRecord) {
+        if record.signature.is_some() {
+            self.unsealed = 0;
+        } else if record.artifact().is_some() {
+            self.unsealed += 1;
+        }
+        if let Some(a) = record.artifact() {
+            self.written.insert(a.consensus_seqno);
+        }
+        self.head = ChainHead {
+            next_seq: record.body.seq + 1,
+            hash: record.hash.clone(),
+        };
+    }
+}
+
+#[derive(Debug)]
+struct EmitterState {
+    writer: ChainWriter,
+    appender: LogAppender,
+}
+
+/// JSONL emitter that appends `ChainedProofRecord`s durably.
+/// Opening an existing log cuts a torn trailing record, verifies the rest
+/// and resumes from its head.
+#[derive(Debug)]
+pub struct ChainedJsonlProofEmitter {
+    /// Bytes of torn tail removed when opening.
+    recovered_bytes: u64,
+    state: Mutex<EmitterState>,
+}
+
+impl ChainedJsonlProofEmitter {
+    /// Unsealed log: chained, not signed.
+    pub fn open<P: AsRef<Path>>(path: P, fsync: FsyncPolicy) -> ProofResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let recovered_bytes = truncate_torn_tail(&path)?;
+        let mut written = SeqnoSet::default();
+        let head = if path.exists() {
+            scan_jsonl(&path, |r| {
+                if let Some(a) = r.artifact() {
+                    written.insert(a.consensus_seqno);
+                }
+                Ok(())
+            })?
+        } else {
+            ChainHead::default()
+        };
+        Ok(Self {
+            recovered_bytes,
+            state: Mutex::new(EmitterState {
+                writer: ChainWriter::unsealed(head, written),
+                appender: LogAppender::lines(path, fsync),
+            }),
+        })
+    }
+
+    /// Sealed log. A new log starts with a genesis rotation for `signer`;
+    /// an existing one must currently be sealed by `signer`'s key.
+    pub fn open_sealed<P: AsRef<Path>>(
+        path: P,
+        signer: HostSigner,
+        seal_every: u32,
+        fsync: FsyncPolicy,
+    ) -> ProofResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let recovered_bytes = truncate_torn_tail(&path)?;
+        let mut seals = SealVerifier::default();
+        let mut written = SeqnoSet::default();
+        let head = if path.exists() {
+            scan_jsonl(&path, |r| {
+                seals.push(r)?;
+                if let Some(a) = r.artifact() {
+                    written.insert(a.consensus_seqno);
+                }
+                Ok(())
+            })?
+        } else {
+            ChainHead::default()
+        };
+        let (writer, genesis) = ChainWriter::sealed(head, &seals.finish(), written, signer, seal_every)?;
+        let emitter = Self {
+            recovered_bytes,
+            state: Mutex::new(EmitterState {
+                writer,
+                appender: LogAppender::lines(path, fsync),
+            }),
+        };
+        if let Some(record) = genesis {
+            emitter.write(&mut emitter.lock(), &record)?;
+        }
+        Ok(emitter)
+    }
+
+    /// See `ChainWriter::commit_fields`.
+    pub fn with_committed_fields(self, key: SaltKey, fields: Vec<RedactableField>) -> Self {
+        self.lock().writer.commit_fields(key, fields);
+        self
+    }
+
+    pub fn head(&self) -> ChainHead {
+        self.lock().writer.head().clone()
+    }
+
+    pub fn recovered_bytes(&self) -> u64 {
+        self.recovered_bytes
+    }
+
+    /// Force buffered records to disk regardless of the fsync policy.
+    pub fn sync(&self) -> ProofResult<()> {
+        Ok(self.lock().appender.sync()?)
+    }
+
+    /// Sign a checkpoint covering every artifact written so far. Fails with
+    /// `SealError::MissingGenesisKey` on an unsealed log.
+    pub fn seal(&self) -> ProofResult<()> {
+        let mut state = self.lock();
+        let record = state.writer.checkpoint_record()?;
+        self.write(&mut state, &record)
+    }
+
+    /// Hand sealing over to `new_key` from `effective_from_seqno` on, which
+    /// must be the next seqno after the highest written. The rotation is
+    /// signed by the current key, which also seals any pending artifacts.
+    pub fn rotate_key(&self, new_key: ed25519_dalek::SigningKey, effective_from_seqno: u64) -> ProofResult<()> {
+        let mut state = self.lock();
+        let record = state.writer.rotation_record(&new_key, effective_from_seqno)?;
+        self.write(&mut state, &record)?;
+        state.writer.install_key(new_key, effective_from_seqno);
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, EmitterState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self, state: &mut EmitterState, record: &ChainedProofRecord) -> ProofResult<()> {
+        state.appender.append_line(&serde_json::to_vec(record)?)?;
+        state.writer.advance(record);
+        Ok(())
+    }
+}
+
+impl ProofEmitter for ChainedJsonlProofEmitter {
+    fn emit_proof_artifact(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<()> {
+        let mut state = self.lock();
+        match state.writer.artifact_record(artifact)? {
+            Some(record) => self.write(&mut state, &record),
+            None => Ok(()),
+        }
+    }
+
+    fn emitted_through(&self) -> Option<u64> {
+        self.lock().writer.emitted_through()
+    }
+}
@@ -0,0 +1,514 @@
+//! Composable `ProofEmitter`s.
+//!
+//! - `PolicyEmitter`: applies an `ErrorPolicy` to any emitter.
+//! - `TeeProofEmitter`: writes every artifact to several backends.
+//! - `FilterProofEmitter`: passes on only artifacts matching a domain or
+//!   band predicate. A tee of filters routes artifacts to backends.
+//! - `BufferedProofEmitter`: bounded queue drained by a worker thread, so
+//!   the RPC path never waits on storage or sleeps.
+//! - `InMemoryProofEmitter`: keeps artifacts in memory, for tests.
+//!
+//! `emitted_through` is a contiguous watermark and stays one through every
+//! combinator: a tee reports its slowest backend, a buffer what its backend
+//! has written, a filter counts the artifacts it skipped as handled, and
+//! `PolicyEmitter` and `BufferedProofEmitter` hold it below
+//! the lowest artifact they dropped until that one is written. So
+//! `HostNode::reconcile_proofs` re-offers whatever a fail-open or buffered
+//! path lost, along with some already written; the chained emitters skip
+//! `consensus_seqno`s already in their log rather than write them twice.
+//! The dropped set is in memory; after a restart the chained emitters'
+//! own watermark, read back from the log, covers the same gaps.
+
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::chain::SeqnoSet;
+use super::{
+    BiophysicalProofArtifact, EcoBand, EventDomain, LifeforceBand, PainBand, ProofEmitter, ProofError, ProofResult,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorPolicy {
+    /// Return the error to the caller.
+    FailClosed,
+    /// Count the failure and report success.
+    FailOpen,
+    /// Try again up to `attempts` more times, sleeping `backoff` between
+    /// tries, then fail closed. `PolicyEmitter` sleeps on the caller's
+    /// thread; `BufferedProofEmitter` on its worker.
+    Retry { attempts: u32, backoff: Duration },
+}
+
+impl Default for ErrorPolicy {
+    fn default() -> Self {
+        ErrorPolicy::FailClosed
+    }
+}
+
+/// Counters shared between an emitter and whoever monitors it.
+#[derive(Debug, Default)]
+pub struct EmitterMetrics {
+    emitted: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+    retries: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    /// Artifacts written by the backend.
+    pub emitted: u64,
+    /// Backend errors, including those swallowed by `FailOpen`.
+    pub failed: u64,
+    /// Artifacts not written: swallowed failures and buffer overflow.
+    pub dropped: u64,
+    pub retries: u64,
+}
+
+impl EmitterMetrics {
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            emitted: self.emitted.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+        }
+    }
+
+    fn bump(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// `consensus_seqno`s dropped and not yet written since.
+#[derive(Debug, Default)]
+struct DroppedSeqnos(Mutex<BTreeSet<u64>>);
+
+impl DroppedSeqnos {
+    fn insert(&self, seqno: u64) {
+        self.lock().insert(seqno);
+    }
+
+    fn remove(&self, seqno: u64) {
+        self.lock().remove(&seqno);
+    }
+
+    /// `through`, held below the lowest dropped seqno.
+    fn cap(&self, through: Option<u64>) -> Option<u64> {
+        match self.lock().first() {
+            Some(lowest) => through.min(lowest.checked_sub(1)),
+            None => through,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeSet<u64>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Applies `policy` to `inner`'s errors. `Retry` sleeps on the calling
+/// thread; on an async path, retry behind a `BufferedProofEmitter` instead.
+pub struct PolicyEmitter {
+    inner: Arc<dyn ProofEmitter>,
+    policy: ErrorPolicy,
+    metrics: Arc<EmitterMetrics>,
+    dropped: DroppedSeqnos,
+}
+
+impl PolicyEmitter {
+    pub fn new(inner: Arc<dyn ProofEmitter>, policy: ErrorPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            metrics: Arc::default(),
+            dropped: DroppedSeqnos::default(),
+        }
+    }
+
+    pub fn metrics(&self) -> Arc<EmitterMetrics> {
+        Arc::clone(&self.metrics)
+    }
+}
+
+impl ProofEmitter for PolicyEmitter {
+    fn emit_proof_artifact(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<()> {
+        let mut result = self.inner.emit_proof_artifact(artifact);
+        if let ErrorPolicy::Retry { attempts, backoff } = self.policy {
+            for _ in 0..attempts {
+                if result.is_ok() {
+                    break;
+                }
+                EmitterMetrics::bump(&self.metrics.failed);
+                EmitterMetrics::bump(&self.metrics.retries);
+                std::thread::sleep(backoff);
+                result = self.inner.emit_proof_artifact(artifact);
+            }
+        }
+        match result {
+            Ok(()) => {
+                EmitterMetrics::bump(&self.metrics.emitted);
+                self.dropped.remove(artifact.consensus_seqno);
+                Ok(())
+            }
+            Err(e) => {
+                EmitterMetrics::bump(&self.metrics.failed);
+                if self.policy == ErrorPolicy::FailOpen {
+                    EmitterMetrics::bump(&self.metrics.dropped);
+                    self.dropped.insert(artifact.consensus_seqno);
+                    Ok(())
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    /// `inner`'s position, held below any artifact `FailOpen` dropped.
+    fn emitted_through(&self) -> Option<u64> {
+        self.dropped.cap(self.inner.emitted_through())
+    }
+}
+
+/// Writes to every backend in order; all are attempted, and the first
+/// error is returned. Wrap a backend in `PolicyEmitter` to change that.
+pub struct TeeProofEmitter {
+    backends: Vec<Arc<dyn ProofEmitter>>,
+}
+
+impl TeeProofEmitter {
+    pub fn new(backends: Vec<Arc<dyn ProofEmitter>>) -> Self {
+        Self { backends }
+    }
+}
+
+impl ProofEmitter for TeeProofEmitter {
+    fn emit_proof_artifact(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<()> {
+        let mut first_err = None;
+        for backend in &self.backends {
+            if let Err(e) = backend.emit_proof_artifact(artifact) {
+                first_err.get_or_insert(e);
+            }
+        }
+        first_err.map_or(Ok(()), Err)
+    }
+
+    /// The lowest backend position; `None` if any backend does not track one.
+    fn emitted_through(&self) -> Option<u64> {
+        self.backends
+            .iter()
+            .map(|b| b.emitted_through())
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .min()
+    }
+}
+
+type Predicate = Box<dyn Fn(&BiophysicalProofArtifact) -> bool + Send + Sync>;
+
+/// Passes matching artifacts to `inner` and drops the rest.
+///
+/// `inner` never sees the skipped seqnos, so its own watermark stops at
+/// the first one. The filter keeps the seqnos it has handled (skipped, or
+/// accepted by `inner`) and reports the watermark over those together with
+/// `inner`'s. `inner` must write before returning, so keep a
+/// `BufferedProofEmitter` outside the filter, not inside it.
+pub struct FilterProofEmitter {
+    inner: Arc<dyn ProofEmitter>,
+    predicate: Predicate,
+    handled: Mutex<SeqnoSet>,
+}
+
+impl FilterProofEmitter {
+    pub fn new<F>(inner: Arc<dyn ProofEmitter>, predicate: F) -> Self
+    where
+        F: Fn(&BiophysicalProofArtifact) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            inner,
+            predicate: Box::new(predicate),
+            handled: Mutex::default(),
+        }
+    }
+
+    pub fn domains(inner: Arc<dyn ProofEmitter>, domains: Vec<EventDomain>) -> Self {
+        Self::new(inner, move |a| domains.contains(&a.event.domain))
+    }
+
+    pub fn lifeforce_bands(inner: Arc<dyn ProofEmitter>, bands: Vec<LifeforceBand>) -> Self {
+        Self::new(inner, move |a| bands.contains(&a.lifeforce_band))
+    }
+
+    pub fn eco_bands(inner: Arc<dyn ProofEmitter>, bands: Vec<EcoBand>) -> Self {
+        Self::new(inner, move |a| bands.contains(&a.eco_band))
+    }
+
+    /// Artifacts without a pain band never match.
+    pub fn pain_bands(inner: Arc<dyn ProofEmitter>, bands: Vec<PainBand>) -> Self {
+        Self::new(inner, move |a| a.pain_band.as_ref().is_some_and(|b| bands.contains(b)))
+    }
+}
+
+impl FilterProofEmitter {
+    fn lock(&self) -> std::sync::MutexGuard<'_, SeqnoSet> {
+        self.handled.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ProofEmitter for FilterProofEmitter {
+    fn emit_proof_artifact(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<()> {
+        if (self.predicate)(artifact) {
+            self.inner.emit_proof_artifact(artifact)?;
+        }
+        self.lock().insert(artifact.consensus_seqno);
+        Ok(())
+    }
+
+    /// Watermark over the seqnos handled here and everything `inner` has
+    /// written. The handled set is in memory: after a restart it starts
+    /// from `inner`'s watermark, and reconcile re-offers what lies above.
+    fn emitted_through(&self) -> Option<u64> {
+        let inner = self.inner.emitted_through();
+        let mut handled = self.lock();
+        if let Some(through) = inner {
+            handled.insert_up_to(through);
+        }
+        handled.through()
+    }
+}
+
+enum BufferMsg {
+    Artifact(Box<BiophysicalProofArtifact>),
+    Flush(SyncSender<()>),
+}
+
+/// Bounded queue in front of `inner`, drained by a worker thread.
+/// Enqueueing never blocks: an artifact already queued is not queued
+/// again, and a full queue fails the emit (`FailClosed`, `Retry`) or drops
+/// the artifact (`FailOpen`).
+///
+/// Backend errors are handled on the worker: `Retry` tries again there,
+/// sleeping between attempts. Artifacts that still fail are dropped and
+/// counted; under `FailClosed` and `Retry` the first such error also makes
+/// every later emit fail until `clear_failure`.
+pub struct BufferedProofEmitter {
+    inner: Arc<dyn ProofEmitter>,
+    tx: Option<SyncSender<BufferMsg>>,
+    worker: Option<JoinHandle<()>>,
+    policy: ErrorPolicy,
+    metrics: Arc<EmitterMetrics>,
+    failure: Arc<Mutex<Option<String>>>,
+    failed: Arc<AtomicBool>,
+    /// Seqnos waiting in the queue.
+    queued: Arc<Mutex<BTreeSet<u64>>>,
+    dropped: Arc<DroppedSeqnos>,
+}
+
+impl BufferedProofEmitter {
+    pub fn new(inner: Arc<dyn ProofEmitter>, capacity: usize, policy: ErrorPolicy) -> Self {
+        let (tx, rx) = mpsc::sync_channel::<BufferMsg>(capacity.max(1));
+        let metrics = Arc::<EmitterMetrics>::default();
+        let failure = Arc::new(Mutex::new(None));
+        let failed = Arc::new(AtomicBool::new(false));
+        let queued = Arc::new(Mutex::new(BTreeSet::new()));
+        let dropped = Arc::<DroppedSeqnos>::default();
+        let worker = {
+            let (inner, metrics, failure, failed, queued, dropped) = (
+                Arc::clone(&inner),
+                Arc::clone(&metrics),
+                Arc::clone(&failure),
+                Arc::clone(&failed),
+                Arc::clone(&queued),
+                Arc::clone(&dropped),
+            );
+            std::thread::spawn(move || {
+                for msg in rx {
+                    let artifact = match msg {
+                        BufferMsg::Artifact(artifact) => artifact,
+                        BufferMsg::Flush(done) => {
+                            let _ = done.send(());
+                            continue;
+                        }
+                    };
+                    let seqno = artifact.consensus_seqno;
+                    let mut result = inner.emit_proof_artifact(&artifact);
+                    if let ErrorPolicy::Retry { attempts, backoff } = policy {
+                        for _ in 0..attempts {
+                            if result.is_ok() {
+                                break;
+                            }
+                            EmitterMetrics::bump(&metrics.failed);
+                            EmitterMetrics::bump(&metrics.retries);
+                            std::thread::sleep(backoff);
+                            result = inner.emit_proof_artifact(&artifact);
+                        }
+                    }
+                    match result {
+                        Ok(()) => {
+                            EmitterMetrics::bump(&metrics.emitted);
+                            dropped.remove(seqno);
+                        }
+                        Err(e) => {
+                            EmitterMetrics::bump(&metrics.failed);
+                            EmitterMetrics::bump(&metrics.dropped);
+                            dropped.insert(seqno);
+                            if policy != ErrorPolicy::FailOpen {
+                                let mut slot = failure.lock().unwrap_or_else(|e| e.into_inner());
+                                slot.get_or_insert_with(|| e.to_string());
+                                failed.store(true, Ordering::Release);
+                            }
+                        }
+                    }
+                    queued.lock().unwrap_or_else(|e| e.into_inner()).remove(&seqno);
+                }
+            })
+        };
+        Self {
+            inner,
+            tx: Some(tx),
+            worker: Some(worker),
+            policy,
+            metrics,
+            failure,
+            failed,
+            queued,
+            dropped,
+        }
+    }
+
+    pub fn metrics(&self) -> Arc<EmitterMetrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// First backend error since the last `clear_failure`, if any.
+    pub fn failure(&self) -> Option<String> {
+        self.failure.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn clear_failure(&self) {
+        *self.failure.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        self.failed.store(false, Ordering::Release);
+    }
+
+    /// Block until everything queued before this call has been handled.
+    pub fn flush(&self) -> ProofResult<()> {
+        let (done_tx, done_rx) = mpsc::sync_channel(1);
+        self.sender()?
+            .send(BufferMsg::Flush(done_tx))
+            .map_err(|_| worker_gone())?;
+        done_rx.recv().map_err(|_| worker_gone())
+    }
+
+    fn sender(&self) -> ProofResult<&SyncSender<BufferMsg>> {
+        self.tx.as_ref().ok_or_else(worker_gone)
+    }
+}
+
+fn worker_gone() -> ProofError {
+    ProofError::Unavailable("buffer worker stopped".into())
+}
+
+impl ProofEmitter for BufferedProofEmitter {
+    fn emit_proof_artifact(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<()> {
+        if self.policy != ErrorPolicy::FailOpen && self.failed.load(Ordering::Acquire) {
+            return Err(ProofError::Unavailable(self.failure().unwrap_or_default()));
+        }
+        let tx = self.sender()?;
+        let seqno = artifact.consensus_seqno;
+        let mut queued = self.queued.lock().unwrap_or_else(|e| e.into_inner());
+        if !queued.insert(seqno) {
+            return Ok(());
+        }
+        match tx.try_send(BufferMsg::Artifact(Box::new(artifact.clone()))) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                queued.remove(&seqno);
+                match e {
+                    TrySendError::Disconnected(_) => Err(worker_gone()),
+                    TrySendError::Full(_) if self.policy == ErrorPolicy::FailOpen => {
+                        EmitterMetrics::bump(&self.metrics.dropped);
+                        self.dropped.insert(seqno);
+                        Ok(())
+                    }
+                    TrySendError::Full(_) => Err(ProofError::Unavailable("proof buffer full".into())),
+                }
+            }
+        }
+    }
+
+    /// What the backend has written, not what is queued, held below any
+    /// artifact dropped.
+    fn emitted_through(&self) -> Option<u64> {
+        self.dropped.cap(self.inner.emitted_through())
+    }
+}
+
+impl Drop for BufferedProofEmitter {
+    /// Drain the queue before returning.
+    fn drop(&mut self) {
+        self.tx.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Keeps emitted artifacts in memory, once per `consensus_seqno`.
+/// `set_failing` makes emits fail, for exercising error policies.
+#[derive(Debug, Default)]
+pub struct InMemoryProofEmitter {
+    artifacts: Mutex<Vec<BiophysicalProofArtifact>>,
+    failing: AtomicBool,
+}
+
+impl InMemoryProofEmitter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn artifacts(&self) -> Vec<BiophysicalProofArtifact> {
+        self.lock().clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::Release);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<BiophysicalProofArtifact>> {
+        self.artifacts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ProofEmitter for InMemoryProofEmitter {
+    fn emit_proof_artifact(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<()> {
+        if self.failing.load(Ordering::Acquire) {
+            return Err(ProofError::Unavailable("in-memory emitter set to fail".into()));
+        }
+        let mut artifacts = self.lock();
+        if artifacts.iter().all(|a| a.consensus_seqno != artifact.consensus_seqno) {
+            artifacts.push(artifact.clone());
+        }
+        Ok(())
+    }
+
+    fn emitted_through(&self) -> Option<u64> {
+        let mut written = SeqnoSet::default();
+        for a in self.lock().iter() {
+            written.insert(a.consensus_seqno);
+        }
+        written.through()
+    }
+}
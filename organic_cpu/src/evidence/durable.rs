@@ -0,0 +1,223 @@
+//! Crash-safe append for line-oriented proof logs.
+//!
+//! Records are appended through one open handle and synced per
+//! `FsyncPolicy`. A crash mid-write can leave a torn trailing record (bytes
+//! after the last newline); `truncate_torn_tail` cuts it off on startup.
+//! Complete lines are never dropped here: a damaged complete line is a
+//! chain break for the verifier to report.
+//!
+//! A record whose write or sync fails is cut back off before the error is
+//! returned, so the file never holds a record the emitter's chain head
+//! has not advanced over.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FsyncPolicy {
+    /// `fdatasync` after every record; an emitted record survives a crash.
+    Always,
+    /// Sync after every n records; up to n - 1 records may be lost.
+    EveryN(u32),
+    /// Leave syncing to the OS.
+    Never,
+}
+
+impl Default for FsyncPolicy {
+    fn default() -> Self {
+        FsyncPolicy::Always
+    }
+}
+
+/// Truncate bytes after the last newline. Returns how many were removed.
+pub fn truncate_torn_tail<P: AsRef<Path>>(path: P) -> std::io::Result<u64> {
+    let mut file = match OpenOptions::new().read(true).write(true).open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let len = file.metadata()?.len();
+
+    // Scan back from the end in blocks for the last newline.
+    let mut end = len;
+    let mut buf = [0u8; 4096];
+    let keep = loop {
+        if end == 0 {
+            break 0;
+        }
+        let start = end.saturating_sub(buf.len() as u64);
+        let n = (end - start) as usize;
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut buf[..n])?;
+        if let Some(i) = buf[..n].iter().rposition(|b| *b == b'\n') {
+            break start + i as u64 + 1;
+        }
+        end = start;
+    };
+
+    if keep < len {
+        file.set_len(keep)?;
+        file.sync_all()?;
+    }
+    Ok(len - keep)
+}
+
+/// Append handle that applies an `FsyncPolicy`.
+#[derive(Debug)]
+pub struct DurableAppender {
+    file: File,
+    policy: FsyncPolicy,
+    unsynced: u32,
+    /// Length to cut back to before the next write, set when rolling back a
+    /// failed record itself failed.
+    rollback_to: Option<u64>,
+}
+
+impl DurableAppender {
+    /// Open for append, creating parent directories and the file, after
+    /// truncating any torn tail. Returns the appender and the bytes cut.
+    pub fn open<P: AsRef<Path>>(path: P, policy: FsyncPolicy) -> std::io::Result<(Self, u64)> {
+        Self::open_with(path, policy, |p| truncate_torn_tail(p))
+    }
+
+    /// As `open`, with `recover` cutting the torn tail for a non-line
+    /// framing.
+    pub fn open_with<P, R>(path: P, policy: FsyncPolicy, recover: R) -> std::io::Result<(Self, u64)>
+    where
+        P: AsRef<Path>,
+        R: FnOnce(&Path) -> std::io::Result<u64>,
+    {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let truncated = recover(path)?;
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok((
+            Self {
+                file,
+                policy,
+                unsynced: 0,
+                rollback_to: None,
+            },
+            truncated,
+        ))
+    }
+
+    /// Append one record; `line` must not contain a newline. The record and
+    /// its newline go out in a single write.
+    pub fn append_line(&mut self, line: &[u8]) -> std::io::Result<()> {
+        let mut buf = Vec::with_capacity(line.len() + 1);
+        buf.extend_from_slice(line);
+        buf.push(b'\n');
+        self.append_frame(&buf)
+    }
+
+    /// Append one already-framed record in a single write. The caller
+    /// handles torn-tail recovery for its framing. On error the file is cut
+    /// back to its length before the call.
+    pub fn append_frame(&mut self, frame: &[u8]) -> std::io::Result<()> {
+        if let Some(len) = self.rollback_to {
+            self.file.set_len(len)?;
+            self.rollback_to = None;
+        }
+        let start = self.file.metadata()?.len();
+        let written = self.file.write_all(frame).and_then(|()| {
+            self.unsynced += 1;
+            match self.policy {
+                FsyncPolicy::Always => self.sync(),
+                FsyncPolicy::EveryN(n) if self.unsynced >= n.max(1) => self.sync(),
+                _ => Ok(()),
+            }
+        });
+        if written.is_err() {
+            self.rollback_to = Some(start);
+            if self.file.set_len(start).is_ok() {
+                self.rollback_to = None;
+            }
+        }
+        written
+    }
+
+    pub fn sync(&mut self) -> std::io::Result<()> {
+        self.file.sync_data()?;
+        self.unsynced = 0;
+        Ok(())
+    }
+}
+
+/// Lazily opened `DurableAppender` for the file an emitter is writing.
+/// Opening cuts the torn tail with `recover`.
+#[derive(Debug)]
+pub struct LogAppender {
+    path: PathBuf,
+    policy: FsyncPolicy,
+    recover: fn(&Path) -> std::io::Result<u64>,
+    file: Option<DurableAppender>,
+}
+
+impl LogAppender {
+    /// Newline-delimited records.
+    pub fn lines(path: PathBuf, policy: FsyncPolicy) -> Self {
+        Self::framed(path, policy, |p| truncate_torn_tail(p))
+    }
+
+    /// Self-delimiting frames; `recover` as for `DurableAppender::open_with`.
+    pub fn framed(path: PathBuf, policy: FsyncPolicy, recover: fn(&Path) -> std::io::Result<u64>) -> Self {
+        Self {
+            path,
+            policy,
+            recover,
+            file: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one record, newline-terminated; see `DurableAppender::append_line`.
+    pub fn append_line(&mut self, line: &[u8]) -> std::io::Result<()> {
+        self.open()?.append_line(line)
+    }
+
+    /// See `DurableAppender::append_frame`.
+    pub fn append_frame(&mut self, frame: &[u8]) -> std::io::Result<()> {
+        self.open()?.append_frame(frame)
+    }
+
+    /// Sync if a file is open.
+    pub fn sync(&mut self) -> std::io::Result<()> {
+        match self.file.as_mut() {
+            Some(file) => file.sync(),
+            None => Ok(()),
+        }
+    }
+
+    /// Sync and close the current file; later appends go to `path`.
+    pub fn switch_to(&mut self, path: PathBuf) -> std::io::Result<()> {
+        self.sync()?;
+        self.file = None;
+        self.path = path;
+        Ok(())
+    }
+
+    fn open(&mut self) -> std::io::Result<&mut DurableAppender> {
+        let file = match self.file.take() {
+            Some(f) => f,
+            None => DurableAppender::open_with(&self.path, self.policy, self.recover)?.0,
+        };
+        Ok(self.file.insert(file))
+    }
+}
+
+impl Drop for DurableAppender {
+    fn drop(&mut self) {
+        if self.unsynced > 0 {
+            let _ = self.file.sync_data();
+        }
+    }
+}
@@ -0,0 +1,464 @@
+//! Embedded SQLite index over a chained proof log (feature `sqlite-index`).
+//!
+//! The log stays authoritative; the index is a rebuildable mirror for
+//! audits. Every chain record goes into `records` (so the index can
+//! re-verify its own chain) and artifacts also into `artifacts`, keyed by
+//! log seq, with columns for consensus seqno, domain, bands, consent and
+//! provenance hashes. `meta` holds the chain head the index has reached
+//! and, for a single-file log, the byte offset just past that record.
+//!
+//! `sync` resumes reading the log at that offset, checking that the next
+//! record links to the head. If it does not (or there is no offset), the
+//! log is verified from genesis and must pass through the head; if it does
+//! not, the index has drifted and is rebuilt from the log. Resuming trusts
+//! the log before the offset; `audit` re-verifies the stored chain against
+//! the recorded head and re-derives every `artifacts` row from its record.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row, Transaction};
+use serde::{Deserialize, Serialize};
+
+use super::cbor::{scan_log, scan_log_from, LogPosition};
+use super::chain::{BreakKind, ChainBreak, ChainHead, ChainVerifier, ChainedProofRecord};
+use super::merkle::utc_day;
+use super::{EcoBand, EventDomain, LifeforceBand, PainBand, ProofError, ProofResult};
+
+const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS records (
+    seq INTEGER PRIMARY KEY,
+    hash TEXT NOT NULL,
+    record_json TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS artifacts (
+    seq INTEGER PRIMARY KEY REFERENCES records(seq),
+    consensus_seqno INTEGER NOT NULL,
+    utc_ms INTEGER NOT NULL,
+    day TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    domain TEXT NOT NULL,
+    lifeforce_band TEXT NOT NULL,
+    lifeforce_ok INTEGER NOT NULL,
+    eco_band TEXT NOT NULL,
+    pain_band TEXT,
+    metabolic_mode TEXT NOT NULL,
+    consent_proof_hash_hex TEXT,
+    provenance_hash_hex TEXT
+);
+CREATE INDEX IF NOT EXISTS artifacts_seqno ON artifacts(consensus_seqno);
+CREATE INDEX IF NOT EXISTS artifacts_day ON artifacts(day);
+CREATE INDEX IF NOT EXISTS artifacts_domain ON artifacts(domain);
+CREATE INDEX IF NOT EXISTS artifacts_bands ON artifacts(lifeforce_band, eco_band, pain_band);
+CREATE INDEX IF NOT EXISTS artifacts_consent ON artifacts(consent_proof_hash_hex);
+CREATE INDEX IF NOT EXISTS artifacts_provenance ON artifacts(provenance_hash_hex);
+";
+
+impl From<rusqlite::Error> for ProofError {
+    fn from(e: rusqlite::Error) -> Self {
+        ProofError::Index(e.to_string())
+    }
+}
+
+/// Column text for an enum: its JSON form (`"WaveLoad"`, `{"Other":"x"}`).
+fn label<T: Serialize>(v: &T) -> ProofResult<String> {
+    Ok(serde_json::to_string(v)?)
+}
+
+/// `artifacts` columns after `seq`, in schema order.
+const ARTIFACT_COLUMNS: &str = "consensus_seqno, utc_ms, day, event_id, domain, lifeforce_band, lifeforce_ok, \
+     eco_band, pain_band, metabolic_mode, consent_proof_hash_hex, provenance_hash_hex";
+
+/// The `artifacts` row for one record.
+#[derive(Debug, PartialEq)]
+struct ArtifactRow {
+    consensus_seqno: i64,
+    utc_ms: i64,
+    day: String,
+    event_id: String,
+    domain: String,
+    lifeforce_band: String,
+    lifeforce_ok: bool,
+    eco_band: String,
+    pain_band: Option<String>,
+    metabolic_mode: String,
+    consent_proof_hash_hex: Option<String>,
+    provenance_hash_hex: Option<String>,
+}
+
+impl ArtifactRow {
+    fn from_record(record: &ChainedProofRecord) -> ProofResult<Option<Self>> {
+        let Some(a) = record.artifact() else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            consensus_seqno: a.consensus_seqno as i64,
+            utc_ms: a.utc_ms,
+            day: utc_day(a.utc_ms),
+            event_id: a.event.event_id.clone(),
+            domain: label(&a.event.domain)?,
+            lifeforce_band: label(&a.lifeforce_band)?,
+            lifeforce_ok: a.lifeforce_ok,
+            eco_band: label(&a.eco_band)?,
+            pain_band: a.pain_band.as_ref().map(label).transpose()?,
+            metabolic_mode: label(&a.metabolic_mode)?,
+            consent_proof_hash_hex: a.consent_proof_hash_hex.clone(),
+            provenance_hash_hex: a.provenance_hash_hex.clone(),
+        }))
+    }
+
+    /// Read `ARTIFACT_COLUMNS` starting at column `at`.
+    fn read(row: &Row<'_>, at: usize) -> rusqlite::Result<Self> {
+        Ok(Self {
+            consensus_seqno: row.get(at)?,
+            utc_ms: row.get(at + 1)?,
+            day: row.get(at + 2)?,
+            event_id: row.get(at + 3)?,
+            domain: row.get(at + 4)?,
+            lifeforce_band: row.get(at + 5)?,
+            lifeforce_ok: row.get(at + 6)?,
+            eco_band: row.get(at + 7)?,
+            pain_band: row.get(at + 8)?,
+            metabolic_mode: row.get(at + 9)?,
+            consent_proof_hash_hex: row.get(at + 10)?,
+            provenance_hash_hex: row.get(at + 11)?,
+        })
+    }
+}
+
+/// Artifact query; every set field must match.
+#[derive(Clone, Debug, Default)]
+pub struct IndexQuery {
+    pub domain: Option<EventDomain>,
+    pub lifeforce: Option<LifeforceBand>,
+    pub eco: Option<EcoBand>,
+    pub pain: Option<PainBand>,
+    pub consent_proof_hash_hex: Option<String>,
+    pub provenance_hash_hex: Option<String>,
+    /// Inclusive `consensus_seqno` range.
+    pub seqno: (Option<u64>, Option<u64>),
+    /// Inclusive `utc_ms` range.
+    pub utc_ms: (Option<i64>, Option<i64>),
+    pub limit: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub head: ChainHead,
+    /// Records added by this sync.
+    pub appended: u64,
+    /// The index did not match the log and was rebuilt.
+    pub rebuilt: bool,
+}
+
+#[derive(Debug)]
+pub struct ProofIndex {
+    conn: Mutex<Connection>,
+}
+
+impl ProofIndex {
+    pub fn open<P: AsRef<Path>>(path: P) -> ProofResult<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        let version: Option<String> = conn
+            .query_row("SELECT value FROM meta WHERE key = 'schema_version'", [], |r| r.get(0))
+            .optional()?;
+        match version {
+            None => {
+                conn.execute(
+                    "INSERT INTO meta (key, value) VALUES ('schema_version', ?1)",
+                    params![SCHEMA_VERSION.to_string()],
+                )?;
+            }
+            Some(v) if v == SCHEMA_VERSION.to_string() => {}
+            Some(v) => return Err(ProofError::Index(format!("unsupported index schema version {v}"))),
+        }
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Chain head the index has reached.
+    pub fn head(&self) -> ProofResult<ChainHead> {
+        read_head(&self.lock())
+    }
+
+    /// Bring the index up to the log at `log` (JSONL, CBOR or a segment
+    /// directory); rebuild it if it has drifted from the log.
+    pub fn sync<P: AsRef<Path>>(&self, log: P) -> ProofResult<SyncReport> {
+        let log = log.as_ref();
+        let mut conn = self.lock();
+        if let Some(from) = read_position(&conn)? {
+            let tx = conn.transaction()?;
+            let mut appended = 0;
+            let resumed = scan_log_from(log, &from, |record| {
+                insert(&tx, record)?;
+                appended += 1;
+                Ok(())
+            });
+            match resumed {
+                Ok(to) => {
+                    write_position(&tx, &to.head, Some(to.offset))?;
+                    tx.commit()?;
+                    return Ok(SyncReport {
+                        head: to.head,
+                        appended,
+                        rebuilt: false,
+                    });
+                }
+                // Changed before the offset: fall back to a full check.
+                Err(ProofError::Chain(_)) => drop(tx),
+                Err(e) => return Err(e),
+            }
+        }
+        let tx = conn.transaction()?;
+        match append_from(&tx, log) {
+            Ok((head, appended)) => {
+                tx.commit()?;
+                Ok(SyncReport {
+                    head,
+                    appended,
+                    rebuilt: false,
+                })
+            }
+            Err(ProofError::Chain(ChainBreak {
+                kind: BreakKind::AnchorMismatch { .. },
+                ..
+            })) => {
+                drop(tx);
+                let (head, appended) = rebuild(&mut conn, log)?;
+                Ok(SyncReport {
+                    head,
+                    appended,
+                    rebuilt: true,
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Drop everything and re-index `log` from genesis.
+    pub fn rebuild<P: AsRef<Path>>(&self, log: P) -> ProofResult<ChainHead> {
+        Ok(rebuild(&mut self.lock(), log.as_ref())?.0)
+    }
+
+    /// Re-verify the stored records as a chain and compare with the
+    /// recorded head, and check every `hash` and `artifacts` column against
+    /// its record, catching edits to the index file itself.
+    pub fn audit(&self) -> ProofResult<ChainHead> {
+        let conn = self.lock();
+        let recorded = read_head(&conn)?;
+        let mut verifier = ChainVerifier::default();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT r.hash, r.record_json, a.seq, {ARTIFACT_COLUMNS}
+             FROM records r LEFT JOIN artifacts a ON a.seq = r.seq ORDER BY r.seq"
+        ))?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let (hash, json): (String, String) = (row.get(0)?, row.get(1)?);
+            let record = verifier.push_decoded(serde_json::from_str(&json).map_err(|e| e.to_string()))?;
+            let indexed = match row.get::<_, Option<i64>>(2)? {
+                Some(_) => Some(ArtifactRow::read(row, 3)?),
+                None => None,
+            };
+            if hash != record.hash || indexed != ArtifactRow::from_record(&record)? {
+                return Err(drift(&recorded, verifier.head()));
+            }
+        }
+        let orphans: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM artifacts WHERE seq NOT IN (SELECT seq FROM records)",
+            [],
+            |r| r.get(0),
+        )?;
+        if *verifier.head() != recorded || orphans != 0 {
+            return Err(drift(&recorded, verifier.head()));
+        }
+        Ok(recorded)
+    }
+
+    /// Matching artifact records in log order.
+    pub fn query(&self, q: &IndexQuery) -> ProofResult<Vec<ChainedProofRecord>> {
+        let mut sql = String::from("SELECT r.record_json FROM artifacts a JOIN records r ON r.seq = a.seq WHERE 1 = 1");
+        let mut args: Vec<rusqlite::types::Value> = Vec::new();
+        let mut eq = |column: &str, value: Option<String>| {
+            if let Some(v) = value {
+                sql.push_str(&format!(" AND a.{column} = ?{}", args.len() + 1));
+                args.push(v.into());
+            }
+        };
+        eq("domain", q.domain.as_ref().map(label).transpose()?);
+        eq("lifeforce_band", q.lifeforce.as_ref().map(label).transpose()?);
+        eq("eco_band", q.eco.as_ref().map(label).transpose()?);
+        eq("pain_band", q.pain.as_ref().map(label).transpose()?);
+        eq("consent_proof_hash_hex", q.consent_proof_hash_hex.clone());
+        eq("provenance_hash_hex", q.provenance_hash_hex.clone());
+        let mut bound = |clause: &str, value: Option<i64>| {
+            if let Some(v) = value {
+                sql.push_str(&format!(" AND a.{clause} ?{}", args.len() + 1));
+                args.push(v.into());
+            }
+        };
+        bound("consensus_seqno >=", q.seqno.0.map(|s| s as i64));
+        bound("consensus_seqno <=", q.seqno.1.map(|s| s as i64));
+        bound("utc_ms >=", q.utc_ms.0);
+        bound("utc_ms <=", q.utc_ms.1);
+        sql.push_str(" ORDER BY a.seq");
+        if let Some(limit) = q.limit {
+            sql.push_str(&format!(" LIMIT {limit}"));
+        }
+
+        let conn = self.lock();
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(args), |r| r.get::<_, String>(0))?;
+        let records = rows.map(|json| Ok(serde_json::from_str(&json?)?)).collect();
+        records
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn read_head(conn: &Connection) -> ProofResult<ChainHead> {
+    let get = |key: &str| -> ProofResult<Option<String>> {
+        Ok(conn
+            .query_row("SELECT value FROM meta WHERE key = ?1", params![key], |r| r.get(0))
+            .optional()?)
+    };
+    match (get("head_next_seq")?, get("head_hash")?) {
+        (Some(seq), Some(hash)) => Ok(ChainHead {
+            next_seq: seq
+                .parse()
+                .map_err(|_| ProofError::Index(format!("bad head_next_seq '{seq}'")))?,
+            hash,
+        }),
+        _ => Ok(ChainHead::default()),
+    }
+}
+
+/// Head and log offset to resume from, if the last sync recorded one.
+fn read_position(conn: &Connection) -> ProofResult<Option<LogPosition>> {
+    let offset: Option<String> = conn
+        .query_row("SELECT value FROM meta WHERE key = 'log_offset'", [], |r| r.get(0))
+        .optional()?;
+    let Some(offset) = offset else {
+        return Ok(None);
+    };
+    Ok(Some(LogPosition {
+        head: read_head(conn)?,
+        offset: offset
+            .parse()
+            .map_err(|_| ProofError::Index(format!("bad log_offset '{offset}'")))?,
+    }))
+}
+
+/// Record `head`, and the offset just past it for a single-file log.
+fn write_position(tx: &Transaction<'_>, head: &ChainHead, offset: Option<u64>) -> ProofResult<()> {
+    tx.execute(
+        "INSERT OR REPLACE INTO meta (key, value) VALUES ('head_next_seq', ?1), ('head_hash', ?2)",
+        params![head.next_seq.to_string(), head.hash],
+    )?;
+    match offset {
+        Some(offset) => tx.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES ('log_offset', ?1)",
+            params![offset.to_string()],
+        )?,
+        None => tx.execute("DELETE FROM meta WHERE key = 'log_offset'", [])?,
+    };
+    Ok(())
+}
+
+fn drift(expected: &ChainHead, found: &ChainHead) -> ProofError {
+    ChainBreak {
+        line: found.next_seq as usize,
+        kind: BreakKind::AnchorMismatch {
+            expected_head: expected.hash.clone(),
+            found_head: found.hash.clone(),
+        },
+    }
+    .into()
+}
+
+/// Verify `log` from genesis, require it to pass through the index head,
+/// and insert the records after it.
+fn append_from(tx: &Transaction<'_>, log: &Path) -> ProofResult<(ChainHead, u64)> {
+    let indexed = read_head(tx)?;
+    let mut at_indexed = (indexed.next_seq == 0).then(ChainHead::default);
+    let mut appended = 0;
+    let mut visit = |record: &ChainedProofRecord| {
+        let seq = record.body.seq;
+        if seq + 1 == indexed.next_seq {
+            at_indexed = Some(ChainHead {
+                next_seq: seq + 1,
+                hash: record.hash.clone(),
+            });
+        }
+        if seq >= indexed.next_seq {
+            if at_indexed.as_ref() != Some(&indexed) {
+                return Err(drift(&indexed, at_indexed.as_ref().unwrap_or(&ChainHead::default())));
+            }
+            insert(tx, record)?;
+            appended += 1;
+        }
+        Ok(())
+    };
+    let (head, offset) = if log.is_dir() {
+        (scan_log(log, &mut visit)?, None)
+    } else {
+        let to = scan_log_from(log, &LogPosition::default(), &mut visit)?;
+        (to.head, Some(to.offset))
+    };
+    // A log shorter than the index, or diverging at its last record.
+    if at_indexed.as_ref() != Some(&indexed) {
+        return Err(drift(&indexed, &head));
+    }
+    write_position(tx, &head, offset)?;
+    Ok((head, appended))
+}
+
+fn rebuild(conn: &mut Connection, log: &Path) -> ProofResult<(ChainHead, u64)> {
+    let tx = conn.transaction()?;
+    tx.execute_batch(
+        "DELETE FROM artifacts; DELETE FROM records;
+         DELETE FROM meta WHERE key IN ('head_next_seq', 'head_hash', 'log_offset');",
+    )?;
+    let result = append_from(&tx, log)?;
+    tx.commit()?;
+    Ok(result)
+}
+
+fn insert(tx: &Transaction<'_>, record: &ChainedProofRecord) -> ProofResult<()> {
+    let seq = record.body.seq as i64;
+    tx.execute(
+        "INSERT INTO records (seq, hash, record_json) VALUES (?1, ?2, ?3)",
+        params![seq, record.hash, serde_json::to_string(record)?],
+    )?;
+    if let Some(a) = ArtifactRow::from_record(record)? {
+        tx.execute(
+            &format!(
+                "INSERT INTO artifacts (seq, {ARTIFACT_COLUMNS})
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"
+            ),
+            params![
+                seq,
+                a.consensus_seqno,
+                a.utc_ms,
+                a.day,
+                a.event_id,
+                a.domain,
+                a.lifeforce_band,
+                a.lifeforce_ok,
+                a.eco_band,
+                a.pain_band,
+                a.metabolic_mode,
+                a.consent_proof_hash_hex,
+                a.provenance_hash_hex,
+            ],
+        )?;
+    }
+    Ok(())
+}
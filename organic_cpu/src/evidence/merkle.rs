@@ -0,0 +1,266 @@
+//! Daily Merkle roots over chained proof artifacts.
+//!
+//! Leaves are the chain hashes of one UTC day's artifact records, in log
+//! order. Leaves and inner nodes are domain-separated (RFC 6962 style:
+//! `H(0x00 || leaf)`, `H(0x01 || left || right)`); an unpaired node is
+//! carried up unchanged. An `InclusionProof` shows that one artifact is
+//! under a day's root without revealing the other artifacts.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::chain::{link_hash, scan_jsonl, ChainEntry, ChainedProofRecord};
+use super::redact::FieldSalts;
+use super::{BiophysicalProofArtifact, ProofResult};
+
+type Node = [u8; 32];
+
+fn leaf_node(record_hash: &str) -> Node {
+    let mut h = Sha256::new();
+    h.update([0x00]);
+    h.update(record_hash.as_bytes());
+    h.finalize().into()
+}
+
+fn inner_node(left: &Node, right: &Node) -> Node {
+    let mut h = Sha256::new();
+    h.update([0x01]);
+    h.update(left);
+    h.update(right);
+    h.finalize().into()
+}
+
+/// `YYYY-MM-DD` for a UTC millisecond timestamp.
+pub fn utc_day(utc_ms: i64) -> String {
+    // Civil date from days since epoch (Hinnant's algorithm).
+    let z = utc_ms.div_euclid(86_400_000) + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// `levels[0]` are the leaves; the last level holds the root.
+    levels: Vec<Vec<Node>>,
+}
+
+impl MerkleTree {
+    pub fn from_record_hashes<S: AsRef<str>>(hashes: &[S]) -> Self {
+        let mut levels = vec![hashes.iter().map(|h| leaf_node(h.as_ref())).collect::<Vec<_>>()];
+        while levels.last().map_or(false, |l| l.len() > 1) {
+            let next = levels
+                .last()
+                .map(|level| {
+                    level
+                        .chunks(2)
+                        .map(|pair| match pair {
+                            [l, r] => inner_node(l, r),
+                            [single] => *single,
+                            _ => unreachable!(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hex root; an empty tree has root `H("")`.
+    pub fn root_hex(&self) -> String {
+        match self.levels.last().and_then(|l| l.first()) {
+            Some(root) if !self.is_empty() => hex::encode(root),
+            _ => hex::encode(Sha256::digest(b"")),
+        }
+    }
+
+    /// Sibling path for leaf `index`, bottom-up.
+    pub fn path(&self, index: usize) -> Option<Vec<PathStep>> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut steps = Vec::new();
+        let mut i = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = i ^ 1;
+            if sibling < level.len() {
+                steps.push(PathStep {
+                    sibling_hex: hex::encode(level[sibling]),
+                    sibling_on_left: sibling < i,
+                });
+            }
+            i /= 2;
+        }
+        Some(steps)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathStep {
+    pub sibling_hex: String,
+    pub sibling_on_left: bool,
+}
+
+/// Manifest entry committing to one UTC day of proof artifacts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayRoot {
+    pub day: String,
+    pub merkle_root_hex: String,
+    pub artifact_count: usize,
+    /// Log seq range of the day's artifacts.
+    pub first_seq: Option<u64>,
+    pub last_seq: Option<u64>,
+}
+
+/// Proof that one artifact is a leaf under a `DayRoot`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    pub day: String,
+    pub seq: u64,
+    pub prev_hash: String,
+    pub leaf_index: usize,
+    pub path: Vec<PathStep>,
+    /// Field salts of the record, if it was written with commitments.
+    #[serde(default, skip_serializing_if = "FieldSalts::is_empty")]
+    pub salts: FieldSalts,
+}
+
+impl InclusionProof {
+    /// Recompute the record hash from `artifact` and fold the path up to
+    /// the root.
+    pub fn verify(&self, artifact: &BiophysicalProofArtifact, root: &DayRoot) -> bool {
+        if self.day != root.day || utc_day(artifact.utc_ms) != root.day {
+            return false;
+        }
+        let Ok(digest) = (ChainEntry::Artifact {
+            artifact: artifact.clone(),
+            salts: self.salts.clone(),
+        })
+        .digest() else {
+            return false;
+        };
+        let mut node = leaf_node(&link_hash(self.seq, &self.prev_hash, &digest));
+        for step in &self.path {
+            let Some(sibling) = hex::decode(&step.sibling_hex)
+                .ok()
+                .and_then(|b| <Node>::try_from(b).ok())
+            else {
+                return false;
+            };
+            node = if step.sibling_on_left {
+                inner_node(&sibling, &node)
+            } else {
+                inner_node(&node, &sibling)
+            };
+        }
+        hex::encode(node) == root.merkle_root_hex
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Leaf {
+    seq: u64,
+    prev_hash: String,
+    hash: String,
+    salts: FieldSalts,
+}
+
+/// Collects one day's artifact records from records fed in log order.
+#[derive(Clone, Debug)]
+pub struct DayTreeBuilder {
+    day: String,
+    leaves: Vec<Leaf>,
+}
+
+impl DayTreeBuilder {
+    pub fn new(day: &str) -> Self {
+        Self {
+            day: day.to_string(),
+            leaves: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, record: &ChainedProofRecord) {
+        if let ChainEntry::Artifact { artifact, salts } = &record.body.entry {
+            if utc_day(artifact.utc_ms) == self.day {
+                self.leaves.push(Leaf {
+                    seq: record.body.seq,
+                    prev_hash: record.body.prev_hash.clone(),
+                    hash: record.hash.clone(),
+                    salts: salts.clone(),
+                });
+            }
+        }
+    }
+
+    pub fn finish(self) -> DayTree {
+        let hashes: Vec<&str> = self.leaves.iter().map(|l| l.hash.as_str()).collect();
+        DayTree {
+            tree: MerkleTree::from_record_hashes(&hashes),
+            day: self.day,
+            leaves: self.leaves,
+        }
+    }
+}
+
+/// One day's artifact records and their tree.
+#[derive(Clone, Debug)]
+pub struct DayTree {
+    pub day: String,
+    leaves: Vec<Leaf>,
+    tree: MerkleTree,
+}
+
+impl DayTree {
+    /// Collect `day`'s artifacts from a chained JSONL log (verified while
+    /// reading).
+    pub fn from_jsonl<P: AsRef<Path>>(path: P, day: &str) -> ProofResult<Self> {
+        let mut tree = DayTreeBuilder::new(day);
+        scan_jsonl(path, |record| {
+            tree.push(record);
+            Ok(())
+        })?;
+        Ok(tree.finish())
+    }
+
+    pub fn root(&self) -> DayRoot {
+        DayRoot {
+            day: self.day.clone(),
+            merkle_root_hex: self.tree.root_hex(),
+            artifact_count: self.leaves.len(),
+            first_seq: self.leaves.first().map(|l| l.seq),
+            last_seq: self.leaves.last().map(|l| l.seq),
+        }
+    }
+
+    /// Inclusion proof for the artifact at log `seq`.
+    pub fn prove(&self, seq: u64) -> Option<InclusionProof> {
+        let index = self.leaves.iter().position(|l| l.seq == seq)?;
+        let leaf = &self.leaves[index];
+        Some(InclusionProof {
+            day: self.day.clone(),
+            seq,
+            prev_hash: leaf.prev_hash.clone(),
+            leaf_index: index,
+            path: self.tree.path(index)?,
+            salts: leaf.salts.clone(),
+        })
+    }
+}
@@ -0,0 +1,161 @@
+//! Write-ahead store for artifacts of committed frames.
+//!
+//! A host writes each frame's artifact here before it commits the frame,
+//! then emits from here. After a crash, `pending` returns what was recorded
+//! but not yet emitted, bounded by the last committed frame: an artifact
+//! recorded for a frame that never committed is not emitted, and a later
+//! record for the same seqno supersedes it.
+//!
+//! Emission progress comes from `ProofEmitter::emitted_through` when the
+//! emitter tracks one; otherwise from a cursor file next to the outbox.
+//! `trim` drops what has been emitted, so the outbox holds roughly the
+//! backlog plus `TRIM_AFTER` records.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::durable::{truncate_torn_tail, FsyncPolicy, LogAppender};
+use super::{BiophysicalProofArtifact, ProofResult};
+
+/// Records appended between two rewrites by `trim`.
+pub const TRIM_AFTER: u32 = 256;
+
+#[derive(Debug)]
+struct OutboxState {
+    appender: LogAppender,
+    /// Records appended since the last rewrite.
+    since_trim: u32,
+}
+
+#[derive(Debug)]
+pub struct ProofOutbox {
+    cursor_path: PathBuf,
+    state: Mutex<OutboxState>,
+}
+
+impl ProofOutbox {
+    /// Open or create the outbox at `path`, cutting a record torn by a
+    /// crash; the cursor lives at `<path>.cursor`.
+    pub fn open<P: AsRef<Path>>(path: P, fsync: FsyncPolicy) -> ProofResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        truncate_torn_tail(&path)?;
+        let mut cursor_path = path.clone().into_os_string();
+        cursor_path.push(".cursor");
+        Ok(Self {
+            cursor_path: cursor_path.into(),
+            state: Mutex::new(OutboxState {
+                appender: LogAppender::lines(path, fsync),
+                since_trim: 0,
+            }),
+        })
+    }
+
+    /// Record the artifact for a frame about to be committed.
+    pub fn record(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<()> {
+        let line = serde_json::to_vec(artifact)?;
+        let mut state = self.lock();
+        state.appender.append_line(&line)?;
+        state.since_trim += 1;
+        Ok(())
+    }
+
+    /// Artifacts with `after < consensus_seqno <= committed`, in seqno
+    /// order; the last record per seqno wins.
+    pub fn pending(&self, after: Option<u64>, committed: u64) -> ProofResult<Vec<BiophysicalProofArtifact>> {
+        let state = self.lock();
+        let mut by_seqno = BTreeMap::new();
+        for artifact in read_records(state.appender.path())? {
+            let seqno = artifact.consensus_seqno;
+            if after.map_or(true, |a| seqno > a) && seqno <= committed {
+                by_seqno.insert(seqno, artifact);
+            }
+        }
+        Ok(by_seqno.into_values().collect())
+    }
+
+    /// Drop records with `consensus_seqno <= through`, once `TRIM_AFTER`
+    /// records have been appended since the last rewrite. The rest are
+    /// rewritten via a synced temp file and rename. Returns whether the
+    /// outbox was rewritten.
+    pub fn trim(&self, through: u64) -> ProofResult<bool> {
+        let mut state = self.lock();
+        if state.since_trim < TRIM_AFTER {
+            return Ok(false);
+        }
+        let path = state.appender.path().to_path_buf();
+        let keep: Vec<BiophysicalProofArtifact> = read_records(&path)?
+            .into_iter()
+            .filter(|a| a.consensus_seqno > through)
+            .collect();
+
+        // Later appends must reopen the renamed file, not the old inode.
+        state.appender.switch_to(path.clone())?;
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        let mut file = File::create(&tmp)?;
+        for artifact in &keep {
+            let mut line = serde_json::to_vec(artifact)?;
+            line.push(b'\n');
+            file.write_all(&line)?;
+        }
+        file.sync_all()?;
+        std::fs::rename(&tmp, &path)?;
+        state.since_trim = 0;
+        Ok(true)
+    }
+
+    /// Highest seqno recorded as emitted, for emitters that do not track one.
+    pub fn cursor(&self) -> ProofResult<Option<u64>> {
+        match std::fs::read_to_string(&self.cursor_path) {
+            Ok(s) => Ok(Some(s.trim().parse().map_err(|_| {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, "malformed proof outbox cursor")
+            })?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write via a synced temp file and rename.
+    pub fn set_cursor(&self, seqno: u64) -> ProofResult<()> {
+        let mut tmp = self.cursor_path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        let mut file = File::create(&tmp)?;
+        file.write_all(seqno.to_string().as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.cursor_path)?;
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, OutboxState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Every complete record in the outbox. An unterminated last line is a
+/// record torn mid-append and is skipped; the next append cuts it.
+fn read_records(path: &Path) -> ProofResult<Vec<BiophysicalProofArtifact>> {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut reader = BufReader::new(file);
+    let mut records = Vec::new();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || !line.ends_with('\n') {
+            break;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        records.push(serde_json::from_str(&line)?);
+    }
+    Ok(records)
+}
@@ -0,0 +1,243 @@
+//! Salted field commitments for redacted export.
+//!
+//! A writer configured with `ChainWriter::commit_fields` stores a salt per
+//! chosen field in the artifact entry. The entry digest of such a record is
+//! taken over the artifact with those fields replaced by their commitments
+//! `H(field, salt, value)`, so the record hash can be recomputed from the
+//! commitments alone. A `RedactedRecord` drops the value and salt of the
+//! redacted fields and still links into the original chain; a
+//! `FieldReveal` later discloses one field with its salt.
+//!
+//! Records written without salts keep the plain digest and can only be
+//! withheld whole (see `bundle`).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::chain::{link_hash, ChainEntry, ChainedProofRecord, ChainedRecordBody};
+use super::BiophysicalProofArtifact;
+
+const COMMIT_DOMAIN: &str = "organic_cpu.proof.commit.v1";
+const COMMITTED_DIGEST_DOMAIN: &str = "organic_cpu.proof.committed.v1";
+const SALT_DOMAIN: &str = "organic_cpu.proof.salt.v1";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RedactableField {
+    #[serde(rename = "civic_audit_id")]
+    CivicAuditId,
+    #[serde(rename = "consent_proof_hash_hex")]
+    ConsentProofHash,
+    #[serde(rename = "provenance_hash_hex")]
+    ProvenanceHash,
+}
+
+impl RedactableField {
+    pub fn name(self) -> &'static str {
+        match self {
+            RedactableField::CivicAuditId => "civic_audit_id",
+            RedactableField::ConsentProofHash => "consent_proof_hash_hex",
+            RedactableField::ProvenanceHash => "provenance_hash_hex",
+        }
+    }
+
+    pub fn get(self, artifact: &BiophysicalProofArtifact) -> Option<&str> {
+        match self {
+            RedactableField::CivicAuditId => artifact.civic_audit_id.as_deref(),
+            RedactableField::ConsentProofHash => artifact.consent_proof_hash_hex.as_deref(),
+            RedactableField::ProvenanceHash => artifact.provenance_hash_hex.as_deref(),
+        }
+    }
+
+    fn slot(self, artifact: &mut BiophysicalProofArtifact) -> &mut Option<String> {
+        match self {
+            RedactableField::CivicAuditId => &mut artifact.civic_audit_id,
+            RedactableField::ConsentProofHash => &mut artifact.consent_proof_hash_hex,
+            RedactableField::ProvenanceHash => &mut artifact.provenance_hash_hex,
+        }
+    }
+}
+
+/// Salt hex per committed field.
+pub type FieldSalts = BTreeMap<RedactableField, String>;
+/// Commitment hex per committed field.
+pub type FieldCommitments = BTreeMap<RedactableField, String>;
+
+/// Host secret salts are derived from; keep it with the signing key.
+#[derive(Clone)]
+pub struct SaltKey([u8; 32]);
+
+impl SaltKey {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Salt for `field` of the record at log `seq`.
+    pub fn salt(&self, seq: u64, field: RedactableField) -> String {
+        let mut h = Sha256::new();
+        h.update(format!("{SALT_DOMAIN}:{seq}:{}:", field.name()).as_bytes());
+        h.update(self.0);
+        hex::encode(h.finalize())
+    }
+}
+
+impl std::fmt::Debug for SaltKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SaltKey(..)")
+    }
+}
+
+/// `H(domain:field:salt:value)`, with an absent value committed as `null`.
+pub fn commit_field(field: RedactableField, salt_hex: &str, value: Option<&str>) -> String {
+    let value = serde_json::to_string(&value).unwrap_or_default();
+    hex::encode(Sha256::digest(
+        format!("{COMMIT_DOMAIN}:{}:{salt_hex}:{value}", field.name()).as_bytes(),
+    ))
+}
+
+pub fn commitments(artifact: &BiophysicalProofArtifact, salts: &FieldSalts) -> FieldCommitments {
+    salts
+        .iter()
+        .map(|(f, salt)| (*f, commit_field(*f, salt, f.get(artifact))))
+        .collect()
+}
+
+/// Entry digest of a salted artifact, from its commitments: the canonical
+/// entry with each committed field set to `commit:<hex>`, under its own
+/// domain so it never equals a plain digest.
+pub fn committed_digest(
+    artifact: &BiophysicalProofArtifact,
+    commitments: &FieldCommitments,
+) -> Result<String, serde_json::Error> {
+    let mut committed = artifact.clone();
+    for (f, c) in commitments {
+        *f.slot(&mut committed) = Some(format!("commit:{c}"));
+    }
+    let entry = ChainEntry::Artifact {
+        artifact: committed,
+        salts: FieldSalts::new(),
+    };
+    let mut h = Sha256::new();
+    h.update(format!("{COMMITTED_DIGEST_DOMAIN}:").as_bytes());
+    h.update(entry.canonical_bytes()?);
+    Ok(hex::encode(h.finalize()))
+}
+
+/// An artifact record with some committed fields withheld.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RedactedRecord {
+    pub seq: u64,
+    pub prev_hash: String,
+    /// Redacted fields are `None`.
+    pub artifact: BiophysicalProofArtifact,
+    /// Every committed field of the original record.
+    pub commitments: FieldCommitments,
+    /// Salts of committed fields that are shown.
+    pub salts: FieldSalts,
+    pub hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl RedactedRecord {
+    /// Withhold `fields` of `record`. `None` if the record is not an
+    /// artifact or a field to withhold has a value but no commitment.
+    pub fn from_record(record: &ChainedProofRecord, fields: &[RedactableField]) -> Option<Self> {
+        let ChainEntry::Artifact { artifact, salts } = &record.body.entry else {
+            return None;
+        };
+        let mut shown = artifact.clone();
+        let mut kept_salts = salts.clone();
+        for f in fields {
+            if salts.contains_key(f) {
+                *f.slot(&mut shown) = None;
+                kept_salts.remove(f);
+            } else if f.get(artifact).is_some() {
+                return None;
+            }
+        }
+        Some(Self {
+            seq: record.body.seq,
+            prev_hash: record.body.prev_hash.clone(),
+            artifact: shown,
+            commitments: commitments(artifact, salts),
+            salts: kept_salts,
+            hash: record.hash.clone(),
+            signature: record.signature.clone(),
+        })
+    }
+
+    pub fn redacted_fields(&self) -> impl Iterator<Item = RedactableField> + '_ {
+        self.commitments.keys().copied().filter(|f| !self.salts.contains_key(f))
+    }
+
+    /// Recompute the record hash. `None` if a shown field does not match
+    /// its commitment, a redacted field still has a value, or a salt has
+    /// no commitment.
+    pub fn computed_hash(&self) -> Option<String> {
+        if self.salts.keys().any(|f| !self.commitments.contains_key(f)) {
+            return None;
+        }
+        for (f, c) in &self.commitments {
+            let ok = match self.salts.get(f) {
+                Some(salt) => commit_field(*f, salt, f.get(&self.artifact)) == *c,
+                None => f.get(&self.artifact).is_none(),
+            };
+            if !ok {
+                return None;
+            }
+        }
+        let digest = committed_digest(&self.artifact, &self.commitments).ok()?;
+        Some(link_hash(self.seq, &self.prev_hash, &digest))
+    }
+
+    /// The record as the seal verifier sees it: redacted artifact, claimed
+    /// hash and signature. Check `computed_hash` first.
+    pub fn as_sealed_record(&self) -> ChainedProofRecord {
+        ChainedProofRecord {
+            body: ChainedRecordBody {
+                seq: self.seq,
+                prev_hash: self.prev_hash.clone(),
+                entry: ChainEntry::Artifact {
+                    artifact: self.artifact.clone(),
+                    salts: FieldSalts::new(),
+                },
+            },
+            hash: self.hash.clone(),
+            signature: self.signature.clone(),
+        }
+    }
+}
+
+/// Later disclosure of one redacted field.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldReveal {
+    pub seq: u64,
+    pub field: RedactableField,
+    pub value: Option<String>,
+    pub salt_hex: String,
+}
+
+impl FieldReveal {
+    /// Reveal `field` of an original (salted) record.
+    pub fn from_record(record: &ChainedProofRecord, field: RedactableField) -> Option<Self> {
+        let ChainEntry::Artifact { artifact, salts } = &record.body.entry else {
+            return None;
+        };
+        Some(Self {
+            seq: record.body.seq,
+            field,
+            value: field.get(artifact).map(str::to_string),
+            salt_hex: salts.get(&field)?.clone(),
+        })
+    }
+
+    pub fn verify(&self, record: &RedactedRecord) -> bool {
+        self.seq == record.seq
+            && record
+                .commitments
+                .get(&self.field)
+                .is_some_and(|c| *c == commit_field(self.field, &self.salt_hex, self.value.as_deref()))
+    }
+}
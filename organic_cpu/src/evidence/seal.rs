@@ -0,0 +1,342 @@
+//! Host seals for the chained proof log.
+//!
+//! A host key (ed25519) is bound to `HostIdentity.did` by a `KeyRotation`
+//! entry in the log itself. The first entry of a sealed log is the genesis
+//! rotation, signed by the new key; every later rotation is signed by the
+//! key it replaces and carries a proof of possession from the new key.
+//!
+//! A record signature covers the record hash, and through the chain every
+//! earlier record. One signature may therefore seal a batch: each unsealed
+//! artifact is covered by the next signature in the log, which must be made
+//! by the key active at that artifact's `consensus_seqno`.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use super::chain::{ChainEntry, ChainedProofRecord};
+
+const SEAL_DOMAIN: &str = "organic_cpu.proof.seal.v1";
+const ROTATION_DOMAIN: &str = "organic_cpu.proof.rotation.v1";
+const SUMMARY_DOMAIN: &str = "organic_cpu.proof.summary.v1";
+
+/// Binds a host key to the host DID from `effective_from_seqno` onward.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotation {
+    pub did: String,
+    pub new_key_hex: String,
+    /// First `consensus_seqno` the new key seals.
+    pub effective_from_seqno: u64,
+    /// Signature by the new key over the rotation terms.
+    pub possession_sig_hex: String,
+}
+
+impl KeyRotation {
+    pub fn new(did: &str, new_key: &SigningKey, effective_from_seqno: u64) -> Self {
+        let new_key_hex = hex::encode(new_key.verifying_key().as_bytes());
+        let msg = rotation_message(did, &new_key_hex, effective_from_seqno);
+        Self {
+            did: did.to_string(),
+            possession_sig_hex: hex::encode(new_key.sign(msg.as_bytes()).to_bytes()),
+            new_key_hex,
+            effective_from_seqno,
+        }
+    }
+
+    pub fn verifying_key(&self) -> Result<VerifyingKey, SealError> {
+        parse_key(&self.new_key_hex)
+    }
+
+    fn check_possession(&self) -> Result<VerifyingKey, SealError> {
+        let key = self.verifying_key()?;
+        let msg = rotation_message(&self.did, &self.new_key_hex, self.effective_from_seqno);
+        let sig = parse_signature(&self.possession_sig_hex)?;
+        key.verify(msg.as_bytes(), &sig)
+            .map_err(|_| SealError::NoPossessionProof)?;
+        Ok(key)
+    }
+}
+
+fn rotation_message(did: &str, new_key_hex: &str, effective_from_seqno: u64) -> String {
+    format!("{ROTATION_DOMAIN}:{did}:{new_key_hex}:{effective_from_seqno}")
+}
+
+fn seal_message(record_hash: &str) -> String {
+    format!("{SEAL_DOMAIN}:{record_hash}")
+}
+
+fn parse_key(hex_key: &str) -> Result<VerifyingKey, SealError> {
+    let bytes: [u8; 32] = hex::decode(hex_key)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or(SealError::MalformedKey)?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| SealError::MalformedKey)
+}
+
+fn parse_signature(hex_sig: &str) -> Result<Signature, SealError> {
+    let bytes: [u8; 64] = hex::decode(hex_sig)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or(SealError::MalformedSignature)?;
+    Ok(Signature::from_bytes(&bytes))
+}
+
+/// Host signing key, bound to the host DID.
+#[derive(Clone, Debug)]
+pub struct HostSigner {
+    pub did: String,
+    key: SigningKey,
+}
+
+impl HostSigner {
+    pub fn new(did: &str, key: SigningKey) -> Self {
+        Self {
+            did: did.to_string(),
+            key,
+        }
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.key.verifying_key()
+    }
+
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.key
+    }
+
+    /// Hex signature over a record hash.
+    pub fn seal(&self, record_hash: &str) -> String {
+        hex::encode(self.key.sign(seal_message(record_hash).as_bytes()).to_bytes())
+    }
+
+    /// Hex signature over the digest of a compaction summary.
+    pub fn sign_summary(&self, digest: &str) -> String {
+        let msg = format!("{SUMMARY_DOMAIN}:{digest}");
+        hex::encode(self.key.sign(msg.as_bytes()).to_bytes())
+    }
+}
+
+/// Check a `HostSigner::sign_summary` signature.
+pub fn verify_summary(key_hex: &str, digest: &str, sig_hex: &str) -> Result<(), SealError> {
+    let msg = format!("{SUMMARY_DOMAIN}:{digest}");
+    parse_key(key_hex)?
+        .verify(msg.as_bytes(), &parse_signature(sig_hex)?)
+        .map_err(|_| SealError::BadSummarySignature)
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum SealError {
+    #[error("malformed ed25519 public key")]
+    MalformedKey,
+    #[error("malformed ed25519 signature")]
+    MalformedSignature,
+    #[error("key rotation lacks a valid proof of possession")]
+    NoPossessionProof,
+    #[error("seq {seq}: sealed log must start with a genesis key rotation")]
+    MissingGenesisKey { seq: u64 },
+    #[error("seq {seq}: genesis key is not the trusted host key")]
+    UntrustedGenesis { seq: u64 },
+    #[error("seq {seq}: entry names host {found}, log is bound to {expected}")]
+    HostMismatch { seq: u64, expected: String, found: String },
+    #[error("seq {seq}: rotation effective from {effective_from} but consensus_seqno {seen} was already sealed")]
+    RetroactiveRotation { seq: u64, effective_from: u64, seen: u64 },
+    #[error("seq {seq}: rotation effective from {effective_from} must take effect at the next consensus_seqno {next}")]
+    FutureRotation { seq: u64, effective_from: u64, next: u64 },
+    #[error("compaction summary signature does not verify")]
+    BadSummarySignature,
+    #[error("compaction summary keys do not follow from its rotations")]
+    SummaryKeyMismatch,
+    #[error("signer key is not the log's current key for {did}")]
+    SignerMismatch { did: String },
+    #[error("seq {seq}: signature does not verify")]
+    BadSignature { seq: u64 },
+    #[error("seq {seq}: artifact at consensus_seqno {consensus_seqno} sealed by a key not active for it")]
+    KeyNotActive { seq: u64, consensus_seqno: u64 },
+}
+
+/// Outcome of a successful seal check.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealReport {
+    pub did: String,
+    /// (effective_from_seqno, key hex), in rotation order.
+    pub keys: Vec<(u64, String)>,
+    pub sealed_artifacts: u64,
+    pub max_consensus_seqno: Option<u64>,
+    /// Log seqs of trailing artifacts no signature covers yet.
+    pub unsealed_tail: Vec<u64>,
+}
+
+/// Incremental seal verifier; feed chain-verified records in log order.
+#[derive(Clone, Debug, Default)]
+pub struct SealVerifier {
+    trusted_genesis: Option<VerifyingKey>,
+    did: Option<String>,
+    keys: Vec<(u64, VerifyingKey)>,
+    /// (log seq, consensus_seqno) of artifacts awaiting a signature.
+    pending: Vec<(u64, u64)>,
+    max_consensus_seqno: Option<u64>,
+    sealed: u64,
+}
+
+impl SealVerifier {
+    /// Require the genesis key to be `key` (e.g. from the DID document).
+    pub fn trusting(key: VerifyingKey) -> Self {
+        Self {
+            trusted_genesis: Some(key),
+            ..Self::default()
+        }
+    }
+
+    /// Continue from the seal state in `report` (e.g. a compaction summary).
+    /// Fails if the report has unsealed artifacts or unparseable keys.
+    pub fn resume(report: &SealReport) -> Result<Self, SealError> {
+        if let Some(seq) = report.unsealed_tail.first() {
+            return Err(SealError::BadSignature { seq: *seq });
+        }
+        let keys = report
+            .keys
+            .iter()
+            .map(|(from, k)| Ok((*from, parse_key(k)?)))
+            .collect::<Result<Vec<_>, SealError>>()?;
+        Ok(Self {
+            trusted_genesis: None,
+            did: (!report.did.is_empty()).then(|| report.did.clone()),
+            keys,
+            pending: Vec::new(),
+            max_consensus_seqno: report.max_consensus_seqno,
+            sealed: report.sealed_artifacts,
+        })
+    }
+
+    /// Key active at `consensus_seqno`.
+    pub fn key_at(&self, consensus_seqno: u64) -> Option<&VerifyingKey> {
+        self.keys
+            .iter()
+            .rev()
+            .find(|(from, _)| *from <= consensus_seqno)
+            .map(|(_, k)| k)
+    }
+
+    pub fn push(&mut self, record: &ChainedProofRecord) -> Result<(), SealError> {
+        let seq = record.body.seq;
+        let Some(did) = self.did.clone() else {
+            return self.genesis(record);
+        };
+
+        match &record.body.entry {
+            ChainEntry::Artifact { artifact, .. } => {
+                if artifact.host.did != did {
+                    return Err(SealError::HostMismatch {
+                        seq,
+                        expected: did,
+                        found: artifact.host.did.clone(),
+                    });
+                }
+                let s = artifact.consensus_seqno;
+                self.max_consensus_seqno = Some(self.max_consensus_seqno.map_or(s, |m| m.max(s)));
+                self.pending.push((seq, s));
+            }
+            ChainEntry::KeyRotation(rotation) => {
+                if rotation.did != did {
+                    return Err(SealError::HostMismatch {
+                        seq,
+                        expected: did,
+                        found: rotation.did.clone(),
+                    });
+                }
+                if let Some(seen) = self.max_consensus_seqno {
+                    if rotation.effective_from_seqno <= seen {
+                        return Err(SealError::RetroactiveRotation {
+                            seq,
+                            effective_from: rotation.effective_from_seqno,
+                            seen,
+                        });
+                    }
+                }
+            }
+            ChainEntry::Checkpoint { .. } => {}
+        }
+
+        let Some(sig_hex) = &record.signature else {
+            return match &record.body.entry {
+                ChainEntry::Artifact { .. } => Ok(()),
+                _ => Err(SealError::BadSignature { seq }),
+            };
+        };
+
+        // Artifacts are sealed by the key active at their own seqno;
+        // rotations and checkpoints by the latest key.
+        let signer = match &record.body.entry {
+            ChainEntry::Artifact { artifact, .. } => self.key_at(artifact.consensus_seqno),
+            _ => self.keys.last().map(|(_, k)| k),
+        }
+        .copied()
+        .ok_or(SealError::BadSignature { seq })?;
+        self.seal_pending(seq, &record.hash, sig_hex, signer)?;
+
+        if let ChainEntry::KeyRotation(rotation) = &record.body.entry {
+            let key = rotation.check_possession()?;
+            self.keys.push((rotation.effective_from_seqno, key));
+        }
+        Ok(())
+    }
+
+    /// A record whose entry is withheld (see `bundle`). Its signature, if
+    /// any, is checked against the latest key and seals pending artifacts.
+    pub fn push_omitted(&mut self, seq: u64, hash: &str, signature: Option<&str>) -> Result<(), SealError> {
+        if self.did.is_none() {
+            return Err(SealError::MissingGenesisKey { seq });
+        }
+        let Some(sig_hex) = signature else {
+            return Ok(());
+        };
+        let signer = self.keys.last().map(|(_, k)| *k).ok_or(SealError::BadSignature { seq })?;
+        self.seal_pending(seq, hash, sig_hex, signer)
+    }
+
+    fn seal_pending(&mut self, seq: u64, hash: &str, sig_hex: &str, signer: VerifyingKey) -> Result<(), SealError> {
+        signer
+            .verify(seal_message(hash).as_bytes(), &parse_signature(sig_hex)?)
+            .map_err(|_| SealError::BadSignature { seq })?;
+        for (pseq, consensus_seqno) in std::mem::take(&mut self.pending) {
+            if self.key_at(consensus_seqno) != Some(&signer) {
+                return Err(SealError::KeyNotActive {
+                    seq: pseq,
+                    consensus_seqno,
+                });
+            }
+            self.sealed += 1;
+        }
+        Ok(())
+    }
+
+    fn genesis(&mut self, record: &ChainedProofRecord) -> Result<(), SealError> {
+        let seq = record.body.seq;
+        let ChainEntry::KeyRotation(rotation) = &record.body.entry else {
+            return Err(SealError::MissingGenesisKey { seq });
+        };
+        let key = rotation.check_possession()?;
+        if self.trusted_genesis.is_some_and(|t| t != key) {
+            return Err(SealError::UntrustedGenesis { seq });
+        }
+        let sig = record.signature.as_deref().ok_or(SealError::BadSignature { seq })?;
+        key.verify(seal_message(&record.hash).as_bytes(), &parse_signature(sig)?)
+            .map_err(|_| SealError::BadSignature { seq })?;
+        self.did = Some(rotation.did.clone());
+        self.keys.push((rotation.effective_from_seqno, key));
+        Ok(())
+    }
+
+    pub fn finish(self) -> SealReport {
+        SealReport {
+            did: self.did.unwrap_or_default(),
+            keys: self
+                .keys
+                .iter()
+                .map(|(from, k)| (*from, hex::encode(k.as_bytes())))
+                .collect(),
+            sealed_artifacts: self.sealed,
+            max_consensus_seqno: self.max_consensus_seqno,
+            unsealed_tail: self.pending.iter().map(|(seq, _)| *seq).collect(),
+        }
+    }
+}
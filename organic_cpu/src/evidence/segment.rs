@@ -0,0 +1,674 @@
+//! Segmented proof logs with retention.
+//!
+//! A log directory holds numbered JSONL segments and `segments.json`, a
+//! manifest recording each segment's start and end chain heads. One chain
+//! runs through all segments: a segment starts at the head the previous
+//! one ended on. Segments roll over by size or by UTC day; a sealed log
+//! writes a checkpoint before closing a segment so each closed segment is
+//! fully sealed.
+//!
+//! Retention compacts a prefix of closed segments into a signed
+//! `CompactionSummary` that keeps the end head, the seal state and the
+//! Merkle root of every day those segments touched, then deletes them.
+//! Historical daily manifests remain verifiable against the kept roots.
+//! The summary also keeps the segments' key rotations: its key list is
+//! replayed from the genesis key (or the previous summary's keys) rather
+//! than taken on the summary's word. It is signed with the key current at
+//! compaction time; rotations in retained segments that lead from the
+//! compacted state to that key are kept with it too, so its signer is
+//! replayed the same way.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::chain::{
+    scan_jsonl_with, BreakKind, ChainBreak, ChainEntry, ChainHead, ChainVerifier, ChainWriter, ChainedProofRecord,
+    ChainedRecordBody, SeqnoSet,
+};
+use super::durable::{truncate_torn_tail, FsyncPolicy, LogAppender};
+use super::merkle::{utc_day, DayRoot, DayTreeBuilder};
+use super::redact::{RedactableField, SaltKey};
+use super::seal::{verify_summary, HostSigner, KeyRotation, SealError, SealReport, SealVerifier};
+use super::{current_utc_ms, BiophysicalProofArtifact, ProofEmitter, ProofResult};
+
+pub const SEGMENT_MANIFEST: &str = "segments.json";
+pub const SEGMENT_FORMAT: &str = "organic_cpu.proof.segments.v1";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RotateBy {
+    /// Start a new segment before one would exceed this many bytes.
+    Size(u64),
+    /// Start a new segment when an artifact falls on a new UTC day.
+    UtcDay,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentInfo {
+    pub index: u32,
+    pub file: String,
+    /// Chain head before the segment's first record.
+    pub start: ChainHead,
+    /// Chain head after its last record; `None` while open.
+    pub end: Option<ChainHead>,
+    /// UTC days of its first and last artifacts, set when closed.
+    pub first_day: Option<String>,
+    pub last_day: Option<String>,
+}
+
+/// A key rotation record from compacted segments.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeptRotation {
+    pub seq: u64,
+    pub prev_hash: String,
+    pub rotation: KeyRotation,
+    pub hash: String,
+    pub signature: Option<String>,
+}
+
+impl KeptRotation {
+    fn from_record(record: &ChainedProofRecord) -> Option<Self> {
+        match &record.body.entry {
+            ChainEntry::KeyRotation(rotation) => Some(Self {
+                seq: record.body.seq,
+                prev_hash: record.body.prev_hash.clone(),
+                rotation: rotation.clone(),
+                hash: record.hash.clone(),
+                signature: record.signature.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The original record, with its hash recomputed.
+    fn record(&self) -> ProofResult<ChainedProofRecord> {
+        let body = ChainedRecordBody {
+            seq: self.seq,
+            prev_hash: self.prev_hash.clone(),
+            entry: ChainEntry::KeyRotation(self.rotation.clone()),
+        };
+        if body.hash()? != self.hash {
+            return Err(SealError::BadSignature { seq: self.seq }.into());
+        }
+        Ok(ChainedProofRecord {
+            body,
+            hash: self.hash.clone(),
+            signature: self.signature.clone(),
+        })
+    }
+}
+
+/// Signed part of a compaction summary.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactionBody {
+    pub segments: Vec<SegmentInfo>,
+    pub end: ChainHead,
+    /// Key rotations in these segments, in log order.
+    pub rotations: Vec<KeptRotation>,
+    /// Rotations after `end`, still in retained segments, that hand sealing
+    /// to the summary's signer; empty if no key rotated since `end`.
+    #[serde(default)]
+    pub signer_rotations: Vec<KeptRotation>,
+    /// Seal state at `end`, so verification can resume there. Its keys
+    /// must be what replaying `rotations` yields.
+    pub seal: SealReport,
+    /// Roots of every day with artifacts in these segments, over the
+    /// whole day.
+    pub day_roots: Vec<DayRoot>,
+    pub compacted_utc_ms: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactionSummary {
+    #[serde(flatten)]
+    pub body: CompactionBody,
+    pub digest: String,
+    pub signer_key_hex: String,
+    pub signature: String,
+}
+
+impl CompactionBody {
+    pub fn digest(&self) -> Result<String, serde_json::Error> {
+        Ok(hex::encode(Sha256::digest(serde_json::to_vec(self)?)))
+    }
+}
+
+impl CompactionSummary {
+    /// Digest and signature, and that the signer is the key reached by
+    /// replaying `signer_rotations` from `seal`. The rotations themselves
+    /// are checked against the chain of keys in `Walker::new`.
+    pub fn verify(&self) -> Result<(), SealError> {
+        let digest = self.body.digest().map_err(|_| SealError::BadSummarySignature)?;
+        if digest != self.digest {
+            return Err(SealError::BadSummarySignature);
+        }
+        let current = match self.body.signer_rotations.last() {
+            Some(kept) => Some(kept.rotation.new_key_hex.as_str()),
+            None => self.body.seal.keys.last().map(|(_, k)| k.as_str()),
+        };
+        if current != Some(self.signer_key_hex.as_str()) {
+            return Err(SealError::BadSummarySignature);
+        }
+        verify_summary(&self.signer_key_hex, &self.digest, &self.signature)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentManifest {
+    pub format: String,
+    pub compactions: Vec<CompactionSummary>,
+    pub segments: Vec<SegmentInfo>,
+}
+
+impl SegmentManifest {
+    pub fn load(dir: &Path) -> ProofResult<Self> {
+        let path = dir.join(SEGMENT_MANIFEST);
+        if !path.exists() {
+            return Ok(Self {
+                format: SEGMENT_FORMAT.to_string(),
+                ..Self::default()
+            });
+        }
+        Ok(serde_json::from_reader(File::open(path)?)?)
+    }
+
+    /// Write via a synced temp file and rename.
+    pub fn save(&self, dir: &Path) -> ProofResult<()> {
+        std::fs::create_dir_all(dir)?;
+        let tmp = dir.join(format!("{SEGMENT_MANIFEST}.tmp"));
+        let mut file = File::create(&tmp)?;
+        file.write_all(&serde_json::to_vec_pretty(self)?)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, dir.join(SEGMENT_MANIFEST))?;
+        File::open(dir)?.sync_all()?;
+        Ok(())
+    }
+
+    /// Head where the retained segments start.
+    fn base(&self) -> ChainHead {
+        self.compactions
+            .last()
+            .map(|c| c.body.end.clone())
+            .unwrap_or_default()
+    }
+
+    fn compacted_days(&self) -> BTreeSet<&str> {
+        self.compactions
+            .iter()
+            .flat_map(|c| c.body.day_roots.iter().map(|r| r.day.as_str()))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentedReport {
+    pub head: ChainHead,
+    pub segments: usize,
+    pub compactions: usize,
+    pub seal: Option<SealReport>,
+}
+
+/// Walks a segmented log in order, verifying links, segment boundaries
+/// and (for sealed logs) seals.
+struct Walker {
+    chain: ChainVerifier,
+    seals: Option<SealVerifier>,
+    trusted: Option<ed25519_dalek::VerifyingKey>,
+}
+
+impl Walker {
+    fn new(manifest: &SegmentManifest, trusted: Option<ed25519_dalek::VerifyingKey>) -> ProofResult<Self> {
+        let mut prev_end = ChainHead::default();
+        let mut seals: Option<SealVerifier> = None;
+        for c in &manifest.compactions {
+            let starts_at = c.body.segments.first().map(|s| &s.start);
+            if starts_at != Some(&prev_end) {
+                return Err(boundary_break(&prev_end, starts_at));
+            }
+            // Keys come from the previous verified state, extended by this
+            // summary's own rotations; only then is its signer trusted.
+            let mut replay = seals
+                .take()
+                .unwrap_or_else(|| trusted.map(SealVerifier::trusting).unwrap_or_default());
+            for kept in &c.body.rotations {
+                replay.push(&kept.record()?)?;
+            }
+            let replayed = replay.finish();
+            if replayed.did != c.body.seal.did || replayed.keys != c.body.seal.keys {
+                return Err(SealError::SummaryKeyMismatch.into());
+            }
+            // The signer's key must follow from the same chain of keys.
+            let mut handoff = SealVerifier::resume(&replayed)?;
+            for kept in &c.body.signer_rotations {
+                handoff.push(&kept.record()?)?;
+            }
+            c.verify()?;
+            seals = Some(SealVerifier::resume(&c.body.seal)?);
+            prev_end = c.body.end.clone();
+        }
+        Ok(Self {
+            chain: ChainVerifier::from_head(manifest.base()),
+            seals,
+            trusted,
+        })
+    }
+
+    fn segment<F>(&mut self, dir: &Path, info: &SegmentInfo, mut f: F) -> ProofResult<()>
+    where
+        F: FnMut(&ChainedProofRecord) -> ProofResult<()>,
+    {
+        if *self.chain.head() != info.start {
+            return Err(boundary_break(self.chain.head(), Some(&info.start)));
+        }
+        let (chain, seals, trusted) = (&mut self.chain, &mut self.seals, self.trusted);
+        scan_jsonl_with(dir.join(&info.file), chain, |record| {
+            // A log is sealed iff its first record is a key rotation.
+            if seals.is_none() && record.body.seq == 0 {
+                if let ChainEntry::KeyRotation(_) = record.body.entry {
+                    *seals = Some(trusted.map(SealVerifier::trusting).unwrap_or_default());
+                }
+            }
+            if let Some(s) = seals.as_mut() {
+                s.push(record)?;
+            }
+            f(record)
+        })?;
+        if let Some(end) = &info.end {
+            if self.chain.head() != end {
+                return Err(boundary_break(self.chain.head(), Some(end)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn boundary_break(found: &ChainHead, expected: Option<&ChainHead>) -> super::ProofError {
+    ChainBreak {
+        line: 0,
+        kind: BreakKind::AnchorMismatch {
+            expected_head: expected.map(|h| h.hash.clone()).unwrap_or_default(),
+            found_head: found.hash.clone(),
+        },
+    }
+    .into()
+}
+
+/// Verify a segmented log directory. With `trusted`, the genesis key (or
+/// the first compaction's genesis key) must match.
+pub fn verify_segments(dir: &Path, trusted: Option<ed25519_dalek::VerifyingKey>) -> ProofResult<SegmentedReport> {
+    let manifest = SegmentManifest::load(dir)?;
+    let mut walker = Walker::new(&manifest, trusted)?;
+    for info in &manifest.segments {
+        walker.segment(dir, info, |_| Ok(()))?;
+    }
+    if trusted.is_some() && walker.seals.is_none() {
+        return Err(SealError::MissingGenesisKey { seq: 0 }.into());
+    }
+    Ok(SegmentedReport {
+        head: walker.chain.head().clone(),
+        segments: manifest.segments.len(),
+        compactions: manifest.compactions.len(),
+        seal: walker.seals.map(SealVerifier::finish),
+    })
+}
+
+/// Walk the retained segments of a log directory in order, verifying
+/// links, boundaries and compaction summaries, and call `f` on each
+/// record. Compacted records are gone; the walk starts at the last
+/// summary's end head.
+pub fn scan_segments<F>(dir: &Path, mut f: F) -> ProofResult<ChainHead>
+where
+    F: FnMut(&ChainedProofRecord) -> ProofResult<()>,
+{
+    let manifest = SegmentManifest::load(dir)?;
+    let mut walker = Walker::new(&manifest, None)?;
+    for info in &manifest.segments {
+        walker.segment(dir, info, &mut f)?;
+    }
+    Ok(walker.chain.head().clone())
+}
+
+/// Merkle root for `day`: from a compaction summary if the day was
+/// compacted, otherwise computed over the retained segments. Summaries
+/// are verified before any of their roots is returned.
+pub fn day_root(dir: &Path, day: &str) -> ProofResult<DayRoot> {
+    let manifest = SegmentManifest::load(dir)?;
+    let mut walker = Walker::new(&manifest, None)?;
+    if let Some(root) = manifest
+        .compactions
+        .iter()
+        .flat_map(|c| c.body.day_roots.iter())
+        .find(|r| r.day == day)
+    {
+        return Ok(root.clone());
+    }
+    let mut builder = DayTreeBuilder::new(day);
+    for info in &manifest.segments {
+        walker.segment(dir, info, |r| {
+            builder.push(r);
+            Ok(())
+        })?;
+    }
+    Ok(builder.finish().root())
+}
+
+#[derive(Debug)]
+struct SegmentState {
+    writer: ChainWriter,
+    manifest: SegmentManifest,
+    /// Open for the last (open) segment.
+    appender: LogAppender,
+    bytes: u64,
+    first_day: Option<String>,
+    last_day: Option<String>,
+}
+
+/// Proof emitter over a segmented log directory.
+#[derive(Debug)]
+pub struct SegmentedProofEmitter {
+    dir: PathBuf,
+    rotate: RotateBy,
+    state: Mutex<SegmentState>,
+}
+
+impl SegmentedProofEmitter {
+    /// Open or create a log directory. With `signer`, the log is sealed
+    /// (see `ChainWriter::sealed`); `seal_every` as there.
+    pub fn open<P: AsRef<Path>>(
+        dir: P,
+        rotate: RotateBy,
+        fsync: FsyncPolicy,
+        signer: Option<(HostSigner, u32)>,
+    ) -> ProofResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        let mut manifest = SegmentManifest::load(&dir)?;
+        if let Some(open) = manifest.segments.last() {
+            truncate_torn_tail(dir.join(&open.file))?;
+        }
+
+        let mut walker = Walker::new(&manifest, None)?;
+        let (mut bytes, mut first_day, mut last_day) = (0, None, None);
+        // Compacted history counts as written.
+        let mut written = manifest
+            .compactions
+            .last()
+            .and_then(|c| c.body.seal.max_consensus_seqno)
+            .map_or_else(SeqnoSet::default, SeqnoSet::up_to);
+        for info in &manifest.segments {
+            let path = dir.join(&info.file);
+            if !path.exists() && info.end.is_none() {
+                // Created in the manifest, crashed before the first write.
+                File::create(&path)?;
+            }
+            walker.segment(&dir, info, |r| {
+                if let Some(a) = r.artifact() {
+                    written.insert(a.consensus_seqno);
+                    let day = utc_day(a.utc_ms);
+                    first_day.get_or_insert_with(|| day.clone());
+                    last_day = Some(day);
+                }
+                Ok(())
+            })?;
+            bytes = std::fs::metadata(&path)?.len();
+            if info.end.is_some() {
+                (first_day, last_day) = (None, None);
+            }
+        }
+        let head = walker.chain.head().clone();
+
+        let (writer, genesis) = match signer {
+            Some((signer, seal_every)) => {
+                if walker.seals.is_none() && head.next_seq > 0 {
+                    return Err(SealError::MissingGenesisKey { seq: 0 }.into());
+                }
+                let report = walker.seals.map(SealVerifier::finish).unwrap_or_default();
+                ChainWriter::sealed(head.clone(), &report, written, signer, seal_every)?
+            }
+            None => (ChainWriter::unsealed(head.clone(), written), None),
+        };
+
+        if manifest.segments.last().map_or(true, |s| s.end.is_some()) {
+            manifest.segments.push(new_segment(&manifest, head));
+            manifest.save(&dir)?;
+            bytes = 0;
+        }
+
+        let open = manifest.segments.last().map(|s| dir.join(&s.file)).unwrap_or_default();
+        let emitter = Self {
+            dir,
+            rotate,
+            state: Mutex::new(SegmentState {
+                writer,
+                manifest,
+                appender: LogAppender::lines(open, fsync),
+                bytes,
+                first_day,
+                last_day,
+            }),
+        };
+        if let Some(record) = genesis {
+            emitter.write(&mut emitter.lock(), &record)?;
+        }
+        Ok(emitter)
+    }
+
+    /// See `ChainWriter::commit_fields`.
+    pub fn with_committed_fields(self, key: SaltKey, fields: Vec<RedactableField>) -> Self {
+        self.lock().writer.commit_fields(key, fields);
+        self
+    }
+
+    pub fn manifest(&self) -> SegmentManifest {
+        self.lock().manifest.clone()
+    }
+
+    /// Force buffered records to disk regardless of the fsync policy.
+    pub fn sync(&self) -> ProofResult<()> {
+        Ok(self.lock().appender.sync()?)
+    }
+
+    /// Sign a checkpoint covering every artifact written so far. Fails with
+    /// `SealError::MissingGenesisKey` on an unsealed log.
+    pub fn seal(&self) -> ProofResult<()> {
+        let mut state = self.lock();
+        let record = state.writer.checkpoint_record()?;
+        self.write(&mut state, &record)
+    }
+
+    /// As `ChainedJsonlProofEmitter::rotate_key`.
+    pub fn rotate_key(&self, new_key: ed25519_dalek::SigningKey, effective_from_seqno: u64) -> ProofResult<()> {
+        let mut state = self.lock();
+        let record = state.writer.rotation_record(&new_key, effective_from_seqno)?;
+        self.write(&mut state, &record)?;
+        state.writer.install_key(new_key, effective_from_seqno);
+        Ok(())
+    }
+
+    /// Compact closed segments whose artifacts all fall before
+    /// `cutoff_day` (`YYYY-MM-DD`, a day that has ended) into a signed
+    /// summary and delete them. Needs a sealed log.
+    pub fn compact_before(&self, cutoff_day: &str) -> ProofResult<Option<CompactionSummary>> {
+        let mut state = self.lock();
+        let Some(signer) = state.writer.signer().cloned() else {
+            return Err(SealError::MissingGenesisKey { seq: 0 }.into());
+        };
+
+        let count = state
+            .manifest
+            .segments
+            .iter()
+            .take_while(|s| s.end.is_some() && s.last_day.as_deref().map_or(true, |d| d < cutoff_day))
+            .count();
+        if count == 0 {
+            return Ok(None);
+        }
+
+        let manifest = state.manifest.clone();
+        let (compacted, retained) = manifest.segments.split_at(count);
+        let compacted_days = manifest.compacted_days();
+
+        // One pass over every retained segment: roots cover whole days,
+        // including the part of a day that spills past the last compacted
+        // segment.
+        let mut walker = Walker::new(&manifest, None)?;
+        let mut builders: Vec<DayTreeBuilder> = Vec::new();
+        let mut days: BTreeSet<String> = BTreeSet::new();
+        let mut rotations = Vec::new();
+        let mut signer_rotations = Vec::new();
+        let mut seal_at_end = None;
+        for (i, info) in manifest.segments.iter().enumerate() {
+            let collecting = i < count;
+            walker.segment(&self.dir, info, |r| {
+                if collecting {
+                    rotations.extend(KeptRotation::from_record(r));
+                } else {
+                    signer_rotations.extend(KeptRotation::from_record(r));
+                }
+                if let Some(a) = r.artifact() {
+                    let day = utc_day(a.utc_ms);
+                    if collecting && !compacted_days.contains(day.as_str()) && days.insert(day.clone()) {
+                        builders.push(DayTreeBuilder::new(&day));
+                    }
+                }
+                for b in builders.iter_mut() {
+                    b.push(r);
+                }
+                Ok(())
+            })?;
+            if i + 1 == count {
+                seal_at_end = walker.seals.clone().map(SealVerifier::finish);
+            }
+        }
+        let seal = seal_at_end.ok_or(SealError::MissingGenesisKey { seq: 0 })?;
+
+        let body = CompactionBody {
+            segments: compacted.to_vec(),
+            end: compacted
+                .last()
+                .and_then(|s| s.end.clone())
+                .unwrap_or_default(),
+            rotations,
+            signer_rotations,
+            seal,
+            day_roots: builders.into_iter().map(|b| b.finish().root()).collect(),
+            compacted_utc_ms: current_utc_ms(),
+        };
+        let digest = body.digest()?;
+        let summary = CompactionSummary {
+            signature: signer.sign_summary(&digest),
+            signer_key_hex: hex::encode(signer.verifying_key().as_bytes()),
+            digest,
+            body,
+        };
+        summary.verify()?;
+
+        let mut next = manifest.clone();
+        next.compactions.push(summary.clone());
+        next.segments = retained.to_vec();
+        next.save(&self.dir)?;
+        state.manifest = next;
+        for info in compacted {
+            let _ = std::fs::remove_file(self.dir.join(&info.file));
+        }
+        Ok(Some(summary))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SegmentState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Close the open segment and start the next one.
+    fn roll(&self, state: &mut SegmentState) -> ProofResult<()> {
+        if state.writer.signer().is_some() {
+            let checkpoint = state.writer.checkpoint_record()?;
+            self.write(state, &checkpoint)?;
+        }
+        let head = state.writer.head().clone();
+        if let Some(open) = state.manifest.segments.last_mut() {
+            open.end = Some(head.clone());
+            open.first_day = state.first_day.take();
+            open.last_day = state.last_day.take();
+        }
+        let next = new_segment(&state.manifest, head);
+        state.appender.switch_to(self.dir.join(&next.file))?;
+        state.manifest.segments.push(next);
+        state.manifest.save(&self.dir)?;
+        state.bytes = 0;
+        Ok(())
+    }
+
+    fn write(&self, state: &mut SegmentState, record: &ChainedProofRecord) -> ProofResult<()> {
+        let line = serde_json::to_vec(record)?;
+        state.appender.append_line(&line)?;
+        state.bytes += line.len() as u64 + 1;
+        if let Some(a) = record.artifact() {
+            let day = utc_day(a.utc_ms);
+            state.first_day.get_or_insert_with(|| day.clone());
+            state.last_day = Some(day);
+        }
+        state.writer.advance(record);
+        Ok(())
+    }
+}
+
+fn new_segment(manifest: &SegmentManifest, start: ChainHead) -> SegmentInfo {
+    let index = manifest
+        .segments
+        .last()
+        .map(|s| s.index + 1)
+        .or_else(|| {
+            manifest
+                .compactions
+                .last()
+                .and_then(|c| c.body.segments.last())
+                .map(|s| s.index + 1)
+        })
+        .unwrap_or(0);
+    SegmentInfo {
+        index,
+        file: format!("segment-{index:06}.jsonl"),
+        start,
+        end: None,
+        first_day: None,
+        last_day: None,
+    }
+}
+
+impl ProofEmitter for SegmentedProofEmitter {
+    fn emit_proof_artifact(&self, artifact: &BiophysicalProofArtifact) -> ProofResult<()> {
+        let mut state = self.lock();
+        let Some(record) = state.writer.artifact_record(artifact)? else {
+            return Ok(());
+        };
+        let roll = match self.rotate {
+            RotateBy::Size(max) => {
+                let len = serde_json::to_vec(&record)?.len() as u64 + 1;
+                state.bytes > 0 && state.bytes + len > max
+            }
+            RotateBy::UtcDay => state
+                .last_day
+                .as_deref()
+                .is_some_and(|d| d != utc_day(artifact.utc_ms)),
+        };
+        let record = if roll {
+            self.roll(&mut state)?;
+            // The head moved if a checkpoint was written.
+            match state.writer.artifact_record(artifact)? {
+                Some(record) => record,
+                None => return Ok(()),
+            }
+        } else {
+            record
+        };
+        self.write(&mut state, &record)
+    }
+
+    fn emitted_through(&self) -> Option<u64> {
+        self.lock().writer.emitted_through()
+    }
+}
@@ -0,0 +1,449 @@
+//! Chained, sealed and exported proof logs, end to end on disk.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::SigningKey;
+
+use organic_cpu::evidence::bundle::ProofBundle;
+use organic_cpu::evidence::cbor::{
+    cbor_to_jsonl, jsonl_to_cbor, verify_cbor, verify_sealed_cbor, CborProofEmitter,
+};
+use organic_cpu::evidence::chain::{
+    verify_jsonl, verify_jsonl_against, verify_sealed_jsonl, BreakKind, ChainBreak, ChainEntry, ChainHead,
+    ChainWriter, ChainedJsonlProofEmitter, ChainedProofRecord, SeqnoSet,
+};
+use organic_cpu::evidence::durable::FsyncPolicy;
+use organic_cpu::evidence::merkle::{utc_day, DayTree};
+use organic_cpu::evidence::redact::{FieldReveal, FieldSalts, RedactableField, SaltKey};
+use organic_cpu::evidence::seal::{HostSigner, SealError, SealReport, SealVerifier};
+use organic_cpu::evidence::*;
+
+const HOST_DID: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+/// 2025-10-19T00:00:00Z.
+const DAY_MS: i64 = 1_760_832_000_000;
+
+fn key_a() -> SigningKey {
+    SigningKey::from_bytes(&[1u8; 32])
+}
+
+fn key_b() -> SigningKey {
+    SigningKey::from_bytes(&[2u8; 32])
+}
+
+/// Fresh directory per test under the system temp dir.
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("organic_cpu-evidence-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn artifact(seqno: u64, utc_ms: i64) -> BiophysicalProofArtifact {
+    BiophysicalProofArtifact {
+        host: HostIdentity {
+            did: HOST_DID.to_string(),
+            shard: "phx-main".to_string(),
+        },
+        lorentz_ts: LorentzTimestamp {
+            proper_time_ns: i128::from(seqno) * 1_000,
+            frame_offset_ps: 0,
+        },
+        utc_ms,
+        origin_plane: "bioscale-metabolic".to_string(),
+        event: EventSummary {
+            event_id: format!("evt-{seqno}"),
+            domain: EventDomain::WaveLoad,
+            reason: None,
+        },
+        pre_state_hash_hex: format!("{seqno:064x}"),
+        post_state_hash_hex: format!("{:064x}", seqno + 1),
+        consensus_seqno: seqno,
+        lifeforce_band: LifeforceBand::Safe,
+        lifeforce_ok: true,
+        eco_band: EcoBand::Low,
+        eco_cost_hint: 0.25,
+        pain_band: None,
+        metabolic_mode: MetabolicConsentMode::AutoMicro,
+        consent_proof_hash_hex: Some(format!("consent-{seqno}")),
+        provenance_hash_hex: None,
+        civic_audit_id: Some(format!("civic-{seqno}")),
+    }
+}
+
+fn unsealed_log(path: &Path, count: u64) -> ChainHead {
+    let emitter = ChainedJsonlProofEmitter::open(path, FsyncPolicy::Always).unwrap();
+    for s in 0..count {
+        emitter.emit_proof_artifact(&artifact(s, DAY_MS + s as i64)).unwrap();
+    }
+    emitter.head()
+}
+
+fn chain_break(result: ProofResult<ChainHead>) -> ChainBreak {
+    match result {
+        Err(ProofError::Chain(b)) => b,
+        other => panic!("expected a chain break, got {other:?}"),
+    }
+}
+
+fn seal_error<T: std::fmt::Debug>(result: ProofResult<T>) -> SealError {
+    match result {
+        Err(ProofError::Seal(e)) => e,
+        other => panic!("expected a seal error, got {other:?}"),
+    }
+}
+
+fn lines(path: &Path) -> Vec<String> {
+    std::fs::read_to_string(path).unwrap().lines().map(str::to_string).collect()
+}
+
+fn write_lines(path: &Path, lines: &[String]) {
+    std::fs::write(path, lines.join("\n") + "\n").unwrap();
+}
+
+#[test]
+fn intact_log_verifies_and_matches_its_head() {
+    let dir = scratch("intact");
+    let log = dir.join("host.jsonl");
+    let head = unsealed_log(&log, 5);
+    assert_eq!(verify_jsonl(&log).unwrap(), head);
+    assert_eq!(head.next_seq, 5);
+}
+
+#[test]
+fn inserted_line_breaks_the_chain_where_it_lands() {
+    let dir = scratch("insert");
+    let log = dir.join("host.jsonl");
+    unsealed_log(&log, 5);
+    let mut l = lines(&log);
+    let copy = l[1].clone();
+    l.insert(2, copy);
+    write_lines(&log, &l);
+
+    let b = chain_break(verify_jsonl(&log));
+    assert_eq!(b.line, 3);
+    assert_eq!(b.kind, BreakKind::SeqMismatch { expected: 2, found: 1 });
+}
+
+#[test]
+fn deleted_line_breaks_the_chain_at_the_next_one() {
+    let dir = scratch("delete");
+    let log = dir.join("host.jsonl");
+    unsealed_log(&log, 5);
+    let mut l = lines(&log);
+    l.remove(2);
+    write_lines(&log, &l);
+
+    let b = chain_break(verify_jsonl(&log));
+    assert_eq!(b.line, 3);
+    assert_eq!(b.kind, BreakKind::SeqMismatch { expected: 2, found: 3 });
+}
+
+#[test]
+fn reordered_lines_break_the_chain_at_the_first_moved() {
+    let dir = scratch("reorder");
+    let log = dir.join("host.jsonl");
+    unsealed_log(&log, 5);
+    let mut l = lines(&log);
+    l.swap(1, 2);
+    write_lines(&log, &l);
+
+    let b = chain_break(verify_jsonl(&log));
+    assert_eq!(b.line, 2);
+    assert_eq!(b.kind, BreakKind::SeqMismatch { expected: 1, found: 2 });
+}
+
+#[test]
+fn edited_line_fails_its_hash_and_a_rehash_fails_the_next_link() {
+    let dir = scratch("edit");
+    let log = dir.join("host.jsonl");
+    unsealed_log(&log, 5);
+    let mut l = lines(&log);
+
+    let mut record: ChainedProofRecord = serde_json::from_str(&l[3]).unwrap();
+    if let ChainEntry::Artifact { artifact, .. } = &mut record.body.entry {
+        artifact.civic_audit_id = Some("forged".to_string());
+    }
+    l[3] = serde_json::to_string(&record).unwrap();
+    write_lines(&log, &l);
+    let b = chain_break(verify_jsonl(&log));
+    assert_eq!((b.line, b.kind), (4, BreakKind::HashMismatch));
+
+    record.hash = record.body.hash().unwrap();
+    l[3] = serde_json::to_string(&record).unwrap();
+    write_lines(&log, &l);
+    let b = chain_break(verify_jsonl(&log));
+    assert_eq!((b.line, b.kind), (5, BreakKind::PrevHashMismatch));
+}
+
+#[test]
+fn truncated_tail_is_caught_only_against_an_anchor() {
+    let dir = scratch("anchor");
+    let log = dir.join("host.jsonl");
+    let head = unsealed_log(&log, 5);
+    assert_eq!(verify_jsonl_against(&log, &head).unwrap(), head);
+
+    let l = lines(&log);
+    write_lines(&log, &l[..3]);
+    assert_eq!(verify_jsonl(&log).unwrap().next_seq, 3);
+    let b = chain_break(verify_jsonl_against(&log, &head));
+    assert!(matches!(b.kind, BreakKind::AnchorMismatch { .. }));
+}
+
+#[test]
+fn torn_jsonl_tail_is_cut_on_open_and_the_log_resumes() {
+    let dir = scratch("torn-jsonl");
+    let log = dir.join("host.jsonl");
+    let head = unsealed_log(&log, 3);
+    let torn = br#"{"seq":3,"prev_hash":"#;
+    OpenOptions::new().append(true).open(&log).unwrap().write_all(torn).unwrap();
+
+    let emitter = ChainedJsonlProofEmitter::open(&log, FsyncPolicy::Always).unwrap();
+    assert_eq!(emitter.recovered_bytes(), torn.len() as u64);
+    assert_eq!(emitter.head(), head);
+    emitter.emit_proof_artifact(&artifact(3, DAY_MS)).unwrap();
+    assert_eq!(verify_jsonl(&log).unwrap(), emitter.head());
+}
+
+#[test]
+fn torn_cbor_frame_is_cut_on_open_and_the_log_resumes() {
+    let dir = scratch("torn-cbor");
+    let log = dir.join("host.cbor");
+    let emitter = CborProofEmitter::open(&log, FsyncPolicy::Always).unwrap();
+    for s in 0..3 {
+        emitter.emit_proof_artifact(&artifact(s, DAY_MS)).unwrap();
+    }
+    let head = emitter.head();
+    let len = std::fs::metadata(&log).unwrap().len();
+    emitter.emit_proof_artifact(&artifact(3, DAY_MS)).unwrap();
+    drop(emitter);
+    // Header plus two payload bytes of the fourth frame survive the crash.
+    OpenOptions::new().write(true).open(&log).unwrap().set_len(len + 7).unwrap();
+
+    let emitter = CborProofEmitter::open(&log, FsyncPolicy::Always).unwrap();
+    assert_eq!(emitter.recovered_bytes(), 7);
+    assert_eq!(emitter.head(), head);
+    emitter.emit_proof_artifact(&artifact(3, DAY_MS)).unwrap();
+    assert_eq!(verify_cbor(&log).unwrap(), emitter.head());
+}
+
+#[test]
+fn rotation_hands_sealing_to_the_new_key() {
+    let dir = scratch("rotate");
+    let log = dir.join("host.jsonl");
+    let emitter =
+        ChainedJsonlProofEmitter::open_sealed(&log, HostSigner::new(HOST_DID, key_a()), 1, FsyncPolicy::Always)
+            .unwrap();
+    for s in 0..3 {
+        emitter.emit_proof_artifact(&artifact(s, DAY_MS)).unwrap();
+    }
+    assert_eq!(
+        seal_error(emitter.rotate_key(key_b(), 2)),
+        SealError::RetroactiveRotation { seq: 4, effective_from: 2, seen: 2 }
+    );
+    assert_eq!(
+        seal_error(emitter.rotate_key(key_b(), 10)),
+        SealError::FutureRotation { seq: 4, effective_from: 10, next: 3 }
+    );
+    emitter.rotate_key(key_b(), 3).unwrap();
+    for s in 3..5 {
+        emitter.emit_proof_artifact(&artifact(s, DAY_MS)).unwrap();
+    }
+    drop(emitter);
+
+    let (_, report) = verify_sealed_jsonl(&log, Some(key_a().verifying_key())).unwrap();
+    assert_eq!(report.did, HOST_DID);
+    assert_eq!(
+        report.keys,
+        vec![
+            (0, hex::encode(key_a().verifying_key().as_bytes())),
+            (3, hex::encode(key_b().verifying_key().as_bytes())),
+        ]
+    );
+    assert_eq!(report.sealed_artifacts, 5);
+    assert!(report.unsealed_tail.is_empty());
+
+    assert!(matches!(
+        seal_error(verify_sealed_jsonl(&log, Some(key_b().verifying_key()))),
+        SealError::UntrustedGenesis { seq: 0 }
+    ));
+    assert!(matches!(
+        seal_error(ChainedJsonlProofEmitter::open_sealed(
+            &log,
+            HostSigner::new(HOST_DID, key_a()),
+            1,
+            FsyncPolicy::Always
+        )),
+        SealError::SignerMismatch { .. }
+    ));
+    ChainedJsonlProofEmitter::open_sealed(&log, HostSigner::new(HOST_DID, key_b()), 1, FsyncPolicy::Always).unwrap();
+}
+
+#[test]
+fn artifact_below_the_new_key_is_refused_by_the_writer() {
+    let dir = scratch("not-active-writer");
+    let log = dir.join("host.jsonl");
+    let emitter =
+        ChainedJsonlProofEmitter::open_sealed(&log, HostSigner::new(HOST_DID, key_a()), 1, FsyncPolicy::Always)
+            .unwrap();
+    emitter.emit_proof_artifact(&artifact(0, DAY_MS)).unwrap();
+    emitter.emit_proof_artifact(&artifact(2, DAY_MS)).unwrap();
+    emitter.rotate_key(key_b(), 3).unwrap();
+    assert_eq!(
+        seal_error(emitter.emit_proof_artifact(&artifact(1, DAY_MS))),
+        SealError::KeyNotActive { seq: 4, consensus_seqno: 1 }
+    );
+}
+
+#[test]
+fn artifact_sealed_by_a_key_not_active_for_it_fails_verification() {
+    let (mut writer, genesis) = ChainWriter::sealed(
+        ChainHead::default(),
+        &SealReport::default(),
+        SeqnoSet::default(),
+        HostSigner::new(HOST_DID, key_a()),
+        1,
+    )
+    .unwrap();
+    let mut records = Vec::new();
+    let mut keep = |writer: &mut ChainWriter, record: ChainedProofRecord| {
+        writer.advance(&record);
+        records.push(record);
+    };
+    keep(&mut writer, genesis.unwrap());
+    for s in [0, 2] {
+        let record = writer.artifact_record(&artifact(s, DAY_MS)).unwrap().unwrap();
+        keep(&mut writer, record);
+    }
+    let rotation = writer.rotation_record(&key_b(), 3).unwrap();
+    keep(&mut writer, rotation);
+    writer.install_key(key_b(), 3);
+    // Bypass the writer's check: seqno 1 belongs to the old key.
+    let forged = writer
+        .record(
+            ChainEntry::Artifact {
+                artifact: artifact(1, DAY_MS),
+                salts: FieldSalts::new(),
+            },
+            false,
+        )
+        .unwrap();
+    keep(&mut writer, forged);
+    let checkpoint = writer.checkpoint_record().unwrap();
+    keep(&mut writer, checkpoint);
+
+    let mut seals = SealVerifier::trusting(key_a().verifying_key());
+    let (last, rest) = records.split_last().unwrap();
+    for record in rest {
+        seals.push(record).unwrap();
+    }
+    assert_eq!(
+        seals.push(last),
+        Err(SealError::KeyNotActive { seq: 4, consensus_seqno: 1 })
+    );
+}
+
+#[test]
+fn jsonl_and_cbor_round_trip_with_identical_heads() {
+    let dir = scratch("round-trip");
+    let jsonl = dir.join("host.jsonl");
+    let emitter =
+        ChainedJsonlProofEmitter::open_sealed(&jsonl, HostSigner::new(HOST_DID, key_a()), 2, FsyncPolicy::Always)
+            .unwrap()
+            .with_committed_fields(SaltKey::from_bytes([9u8; 32]), vec![RedactableField::CivicAuditId]);
+    for s in 0..3 {
+        emitter.emit_proof_artifact(&artifact(s, DAY_MS)).unwrap();
+    }
+    emitter.rotate_key(key_b(), 3).unwrap();
+    emitter.emit_proof_artifact(&artifact(3, DAY_MS)).unwrap();
+    emitter.seal().unwrap();
+    drop(emitter);
+
+    let trusted = Some(key_a().verifying_key());
+    let (head, report) = verify_sealed_jsonl(&jsonl, trusted).unwrap();
+    let cbor = dir.join("host.cbor");
+    assert_eq!(jsonl_to_cbor(&jsonl, &cbor).unwrap(), head);
+    assert_eq!(verify_sealed_cbor(&cbor, trusted).unwrap(), (head.clone(), report.clone()));
+
+    let back = dir.join("back.jsonl");
+    assert_eq!(cbor_to_jsonl(&cbor, &back).unwrap(), head);
+    assert_eq!(verify_sealed_jsonl(&back, trusted).unwrap(), (head, report));
+}
+
+#[test]
+fn redacted_bundle_verifies_against_the_source_head() {
+    let dir = scratch("redacted-bundle");
+    let log = dir.join("host.jsonl");
+    let emitter =
+        ChainedJsonlProofEmitter::open_sealed(&log, HostSigner::new(HOST_DID, key_a()), 0, FsyncPolicy::Always)
+            .unwrap()
+            .with_committed_fields(SaltKey::from_bytes([9u8; 32]), vec![RedactableField::CivicAuditId]);
+    for s in 0..4 {
+        emitter.emit_proof_artifact(&artifact(s, DAY_MS)).unwrap();
+    }
+    emitter.seal().unwrap();
+    drop(emitter);
+    let (source_head, _) = verify_sealed_jsonl(&log, None).unwrap();
+
+    let bundle = ProofBundle::from_jsonl_redacted(
+        &log,
+        |r| r.artifact().is_some_and(|a| a.consensus_seqno % 2 == 0),
+        &[RedactableField::CivicAuditId],
+    )
+    .unwrap();
+    assert_eq!(bundle.source_head, source_head);
+    let report = bundle.verify(Some(key_a().verifying_key())).unwrap();
+    assert_eq!(report.head, source_head);
+    assert_eq!((report.included_artifacts, report.redacted), (2, 2));
+    assert_eq!(report.seal.unwrap().unsealed_tail, Vec::<u64>::new());
+
+    // Log seq 1 holds consensus_seqno 0 (seq 0 is the genesis rotation).
+    let redacted = bundle.redacted(1).unwrap();
+    assert_eq!(redacted.artifact.civic_audit_id, None);
+    let source: ChainedProofRecord = serde_json::from_str(&lines(&log)[1]).unwrap();
+    let reveal = FieldReveal::from_record(&source, RedactableField::CivicAuditId).unwrap();
+    assert_eq!(reveal.value.as_deref(), Some("civic-0"));
+    assert!(bundle.check_reveal(&reveal));
+    let forged = FieldReveal {
+        value: Some("civic-9".to_string()),
+        ..reveal
+    };
+    assert!(!bundle.check_reveal(&forged));
+}
+
+#[test]
+fn merkle_proofs_include_each_days_artifacts_and_nothing_else() {
+    let dir = scratch("merkle");
+    let log = dir.join("host.jsonl");
+    let emitter = ChainedJsonlProofEmitter::open(&log, FsyncPolicy::Always)
+        .unwrap()
+        .with_committed_fields(SaltKey::from_bytes([9u8; 32]), vec![RedactableField::CivicAuditId]);
+    let next_day = DAY_MS + 86_400_000;
+    let artifacts: Vec<_> = (0..5)
+        .map(|s| artifact(s, if s < 3 { DAY_MS + s as i64 } else { next_day }))
+        .collect();
+    for a in &artifacts {
+        emitter.emit_proof_artifact(a).unwrap();
+    }
+    drop(emitter);
+
+    let day = utc_day(DAY_MS);
+    let tree = DayTree::from_jsonl(&log, &day).unwrap();
+    let root = tree.root();
+    assert_eq!((root.artifact_count, root.first_seq, root.last_seq), (3, Some(0), Some(2)));
+    for (seq, a) in artifacts.iter().enumerate().take(3) {
+        let proof = tree.prove(seq as u64).unwrap();
+        assert!(proof.verify(a, &root));
+        let mut edited = a.clone();
+        edited.eco_cost_hint = 0.5;
+        assert!(!proof.verify(&edited, &root));
+    }
+    assert!(tree.prove(3).is_none());
+
+    let other = DayTree::from_jsonl(&log, &utc_day(next_day)).unwrap();
+    let other_root = other.root();
+    assert_eq!(other_root.artifact_count, 2);
+    assert!(other.prove(3).unwrap().verify(&artifacts[3], &other_root));
+    assert!(!tree.prove(0).unwrap().verify(&artifacts[0], &other_root));
+}
@@ -52,7 +52,7 @@ pub fn validate_evolution_step_against_brain_capacity(
 
 /// Runtime guard for Karma/Aura decay multipliers, enforcing the dampening-only rule.
 pub fn enforce_decay_multiplier_bounds(raw: f32) -> Result<f32, BioDefenseDoctrineError> {
-    if raw > 1.0 {
+    if raw.is_nan() || raw > 1.0 {
         return Err(BioDefenseDoctrineError::KarmaAmplificationForbidden);
     }
     Ok(raw.clamp(0.0, 1.0))
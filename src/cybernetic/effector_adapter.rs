@@ -1,5 +1,7 @@
 use std::time::SystemTime;
 
+use sovereigntycore::capability::ActuationCapability;
+
 #[derive(Debug, Clone)]
 pub struct EffectorCommand {
     pub effector_id: String,
@@ -8,9 +10,23 @@ pub struct EffectorCommand {
     pub labels: Vec<String>,
 }
 
+/// Anything that can change the host's physical or neural state.
+///
+/// `apply` consumes an `ActuationCapability`, which only
+/// `SovereigntyCore::authorize` can mint, bound to one effector and valid
+/// for a short window. A propose-only terminal that somehow holds an
+/// effector still cannot drive it, and one grant cannot be replayed.
+///
+/// The compile-time guarantees (no construction, no clone, no
+/// deserialization, no reuse after a move) are doctested with a passing
+/// control in `sovereigntycore::capability`, and a terminal holding an
+/// effector is doctested in `proposal_inbox`. Callers can reach `apply`
+/// without the `Supervisor`, so every implementation must start with
+/// `cap.check(self.id(), now_unix)?`: the grant must name this effector
+/// and must not have expired.
 pub trait EffectorAdapter: Send + Sync {
     fn id(&self) -> &str;
-    fn apply(&self, cmd: EffectorCommand) -> bool;
+    fn apply(&self, cap: ActuationCapability, cmd: EffectorCommand, now_unix: i64) -> Result<bool, String>;
 }
 
 pub struct DummyEffector {
@@ -28,7 +44,8 @@ impl EffectorAdapter for DummyEffector {
         &self.id
     }
 
-    fn apply(&self, _cmd: EffectorCommand) -> bool {
-        true
+    fn apply(&self, cap: ActuationCapability, cmd: EffectorCommand, now_unix: i64) -> Result<bool, String> {
+        cap.check(self.id(), now_unix)?;
+        Ok(cmd.effector_id == self.id)
     }
 }
@@ -59,6 +59,12 @@ impl FeedbackBus {
         self.command_queue.pop_front()
     }
 
+    /// Oldest queued command addressed to `id`, leaving the others queued.
+    pub fn pull_command_for(&mut self, id: &str) -> Option<CommandEnvelope> {
+        let idx = self.command_queue.iter().position(|c| c.id == id)?;
+        self.command_queue.remove(idx)
+    }
+
     pub fn purge_older_than(&mut self, cutoff: SystemTime) {
         while let Some(front) = self.sensor_queue.front() {
             if front.at < cutoff {
@@ -5,6 +5,8 @@ use super::{
 use std::sync::{Arc, Mutex};
 use std::time::SystemTime;
 
+use sovereigntycore::capability::ActuationCapability;
+
 pub struct SupervisorConfig {
     pub queue_len: usize,
 }
@@ -57,31 +59,40 @@ impl Supervisor {
         }
     }
 
-    pub fn drive_effectors(&self) {
-        if let Ok(mut bus) = self.bus.lock() {
-            while let Some(env) = bus.pull_command() {
-                for eff in &self.effectors {
-                    if eff.id() == env.id {
-                        let mut values = Vec::new();
-                        let mut chunk = [0u8; 8];
-                        for c in env.payload.chunks(8) {
-                            if c.len() == 8 {
-                                chunk.copy_from_slice(c);
-                                let v = f64::from_le_bytes(chunk);
-                                values.push(v);
-                            }
-                        }
-                        let cmd = EffectorCommand {
-                            effector_id: env.id.clone(),
-                            at: env.at,
-                            payload: values,
-                            labels: Vec::new(),
-                        };
-                        eff.apply(cmd);
-                    }
-                }
+    /// Apply the oldest queued command for the capability's effector,
+    /// consuming the capability. One `SovereigntyCore::authorize` grant
+    /// drives exactly one command on exactly one effector, and only before
+    /// it expires; everything else stays queued.
+    pub fn drive_effector(&self, cap: ActuationCapability, now_unix: i64) -> Result<bool, String> {
+        let eff = self
+            .effectors
+            .iter()
+            .find(|e| e.id() == cap.effector_id())
+            .ok_or_else(|| format!("no effector registered as {}", cap.effector_id()))?;
+        cap.check(eff.id(), now_unix)?;
+        let env = {
+            let mut bus = self.bus.lock().map_err(|_| "feedback bus poisoned".to_string())?;
+            match bus.pull_command_for(eff.id()) {
+                Some(env) => env,
+                None => return Ok(false),
+            }
+        };
+        let mut values = Vec::new();
+        let mut chunk = [0u8; 8];
+        for c in env.payload.chunks(8) {
+            if c.len() == 8 {
+                chunk.copy_from_slice(c);
+                let v = f64::from_le_bytes(chunk);
+                values.push(v);
             }
         }
+        let cmd = EffectorCommand {
+            effector_id: env.id,
+            at: env.at,
+            payload: values,
+            labels: Vec::new(),
+        };
+        eff.apply(cap, cmd, now_unix)
     }
 
     pub fn enqueue_effector_command(